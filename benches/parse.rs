@@ -5,6 +5,9 @@ use std::str::FromStr;
 fn criterion_benchmark(c: &mut Criterion) {
     let edn = edn_str();
     c.bench_function("parse", |b| b.iter(|| edn_rs::Edn::from_str(&edn)));
+    c.bench_function("parse_borrowed", |b| {
+        b.iter(|| edn_rs::parse_borrowed(&edn));
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
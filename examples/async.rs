@@ -1,17 +1,14 @@
-use std::str::{self, FromStr};
-
-use edn_rs::{Edn, Vector, edn};
+use edn_rs::{AsyncEdnReader, Edn, Vector, edn};
 use tokio::fs::File;
-use tokio::io::AsyncReadExt;
 
 #[tokio::main]
-async fn main() -> std::io::Result<()> {
-    let mut file = File::open("examples/test_edn.txt").await?;
-    let mut contents = vec![];
-    file.read_to_end(&mut contents).await?;
+async fn main() -> Result<(), edn_rs::EdnError> {
+    let file = File::open("examples/test_edn.txt").await?;
+    let mut reader = AsyncEdnReader::new(file);
 
-    let edn = Edn::from_str(str::from_utf8(&contents).unwrap());
-    println!("{edn:?}");
+    while let Some(edn) = reader.next_form().await {
+        println!("{:?}", edn?);
+    }
 
     let edn = edn!([1 1.5 "hello" :key]);
     println!("{edn:?}");
@@ -21,11 +21,11 @@ fn test_edn_from_str() {
     assert_eq!(
         edn,
         Edn::Map(Map::new(map! {
-        ":a".to_string() => Edn::Str("2".to_string()),
-        ":b".to_string() => Edn::Vector(Vector::new(vec![Edn::Bool(true), Edn::Bool(false)])),
-        ":c".to_string() => Edn::Set(Set::new(
+        Edn::Key(":a".to_string()) => Edn::Str("2".to_string()),
+        Edn::Key(":b".to_string()) => Edn::Vector(Vector::new(vec![Edn::Bool(true), Edn::Bool(false)])),
+        Edn::Key(":c".to_string()) => Edn::Set(Set::new(
             set!{
-                Edn::Map(Map::new(map!{":a".to_string() => Edn::Key(":b".to_string())})),
+                Edn::Map(Map::new(map!{Edn::Key(":a".to_string()) => Edn::Key(":b".to_string())})),
                 Edn::Key(":A".to_string()),
                 Edn::Nil}))}))
     );
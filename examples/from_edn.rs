@@ -17,8 +17,8 @@ impl Deserialize for Person {
 
 fn person_ok() -> Result<(), EdnError> {
     let edn = Edn::Map(Map::new(map! {
-        ":name".to_string() => Edn::Str("rose".to_string()),
-        ":age".to_string() => Edn::UInt(66)
+        Edn::Key(":name".to_string()) => Edn::Str("rose".to_string()),
+        Edn::Key(":age".to_string()) => Edn::UInt(66)
     }));
     let person: Person = edn_rs::from_edn(&edn)?;
 
@@ -37,8 +37,8 @@ fn person_ok() -> Result<(), EdnError> {
 
 fn person_mistyped() {
     let bad_edn = Edn::Map(Map::new(map! {
-        ":name".to_string() => Edn::Str("rose".to_string()),
-        ":age".to_string() => Edn::Str("some text".to_string())
+        Edn::Key(":name".to_string()) => Edn::Str("rose".to_string()),
+        Edn::Key(":age".to_string()) => Edn::Str("some text".to_string())
     }));
     let person: Result<Person, EdnError> = edn_rs::from_edn(&bad_edn);
 
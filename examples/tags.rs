@@ -1,6 +1,4 @@
-use std::str::FromStr;
-
-use edn_rs::Edn;
+use edn_rs::{Edn, ReaderOptions, TagRegistry};
 
 fn uuid_printer(uuid: &Edn) {
     println!("Received UUID: {uuid}");
@@ -10,27 +8,31 @@ fn inst_printer(inst: &Edn) {
     println!("Received Inst: {inst}");
 }
 
-fn print_tagged_or_panic(tagged_data: &Edn) {
-    if let Edn::Tagged(t, d) = tagged_data {
-        match t.as_str() {
-            "uuid" => uuid_printer(d),
-            "inst" => inst_printer(d),
-            _ => println!("\"{t}\" tag with {d}"),
-        }
-    } else {
-        panic!();
-    }
-}
-
 fn tagged_data() {
     let edn = "{:date   #inst \"2020-07-16T21:53:14.628-00:00\"
                 :uuid   #uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\"
                 :foobar #arbitrary 0x2A}";
-    let parsed_edn: edn_rs::Edn = edn_rs::Edn::from_str(edn).unwrap();
-
-    print_tagged_or_panic(parsed_edn.get(":date").unwrap());
-    print_tagged_or_panic(parsed_edn.get(":uuid").unwrap());
-    print_tagged_or_panic(parsed_edn.get(":foobar").unwrap());
+    let options = ReaderOptions::new().tags(
+        TagRegistry::new()
+            .register("uuid", |uuid: Edn| {
+                uuid_printer(&uuid);
+                Ok::<_, String>(uuid)
+            })
+            .register("inst", |inst: Edn| {
+                inst_printer(&inst);
+                Ok::<_, String>(inst)
+            })
+            .default_handler(|t: &str, d: Edn| {
+                println!("\"{t}\" tag with {d}");
+                Ok::<_, String>(Edn::Tagged(t.to_string(), Box::new(d)))
+            }),
+    );
+    let parsed_edn: edn_rs::Edn = options.parse(edn).unwrap();
+
+    assert_eq!(
+        parsed_edn[":uuid"],
+        Edn::Str("af6d8699-f442-4dfd-8b26-37d80543186b".to_string())
+    );
 }
 
 fn main() {
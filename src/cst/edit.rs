@@ -0,0 +1,519 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use core::str::FromStr;
+
+use crate::cst::{Collection, Document, Node, NodeKind, Trivia, parse};
+use crate::deserialize::parse as parser;
+use crate::edn::{Edn, EdnRef, Error, Keyword, Symbol};
+use crate::serialize::Serialize;
+
+/// Structural edits of the first form of a document, like Clojure's `assoc-in`, `update-in`,
+/// `dissoc` and `conj`.
+///
+/// A path is a list of EDN elements written as text: keys like `":db"` in maps and indexes
+/// like `"0"` in vectors and lists. Edited values keep the trivia before them, removed entries
+/// take the comments on their line with them and new entries copy the layout of the last
+/// one, so everything else is written back as it was read.
+///
+/// ```rust
+/// use edn_rs::cst::Document;
+/// use std::str::FromStr;
+///
+/// let mut doc = Document::from_str(
+///     "{:db {:host \"localhost\" ; primary\n      :port 5432}\n :debug true}",
+/// )
+/// .unwrap();
+///
+/// doc.assoc_in([":db", ":port"], 5433).unwrap();
+/// doc.assoc_in([":db", ":user"], "app").unwrap();
+/// doc.dissoc([":debug"]).unwrap();
+///
+/// assert_eq!(
+///     doc.to_string(),
+///     "{:db {:host \"localhost\" ; primary\n      :port 5433\n      :user \"app\"}}"
+/// );
+/// ```
+impl Document {
+    /// The node at `path`, `None` when one of its keys is missing.
+    ///
+    /// # Errors
+    ///
+    /// `EdnError::Edit` when the document is empty or `path` goes through something else than a
+    /// map, vector or list, and `EdnError::Parse` when a step is not valid EDN.
+    pub fn get_in<P: AsRef<str>>(
+        &self,
+        path: impl IntoIterator<Item = P>,
+    ) -> Result<Option<&Node>, Error> {
+        let mut node = self.forms.first().ok_or_else(empty)?;
+        for key in steps(path)? {
+            let Some(i) = find(&node.kind, &key)? else {
+                return Ok(None);
+            };
+            node = &collection(&node.kind).children[i];
+        }
+        Ok(Some(node))
+    }
+
+    /// Sets the element at `path` to `value`. Missing keys are added, with maps for the
+    /// missing keys before the last one, and an index one past the end of a vector appends to it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Document::get_in`], and `EdnError::Edit` for an index past the end of a
+    /// vector or a value that does not serialize to a single EDN element.
+    #[allow(clippy::needless_pass_by_value, reason = "takes literals like `5433`")]
+    pub fn assoc_in<P: AsRef<str>>(
+        &mut self,
+        path: impl IntoIterator<Item = P>,
+        value: impl Serialize,
+    ) -> Result<(), Error> {
+        let keys = steps(path)?;
+        let value = element(&value.serialize())?;
+        let root = self.forms.first_mut().ok_or_else(empty)?;
+        assoc(root, &keys, value)
+    }
+
+    /// Sets the element at `path` to `f` of its current value, or of `nil` when it is missing.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Document::assoc_in`].
+    pub fn update<P: AsRef<str>>(
+        &mut self,
+        path: impl IntoIterator<Item = P>,
+        f: impl FnOnce(Edn) -> Edn,
+    ) -> Result<(), Error> {
+        let path = path
+            .into_iter()
+            .map(|step| String::from(step.as_ref()))
+            .collect::<Vec<_>>();
+        let current = match self.get_in(&path)? {
+            Some(node) => node.to_edn()?,
+            None => Edn::Nil,
+        };
+        self.assoc_in(&path, f(current))
+    }
+
+    /// Removes the last key of `path` from the map it leads to, returns whether it was there.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Document::get_in`], and `EdnError::Edit` when `path` is empty or does not lead
+    /// to a map.
+    pub fn dissoc<P: AsRef<str>>(
+        &mut self,
+        path: impl IntoIterator<Item = P>,
+    ) -> Result<bool, Error> {
+        let keys = steps(path)?;
+        let Some((key, parents)) = keys.split_last() else {
+            return Err(Error::Edit(String::from("cannot dissoc an empty path")));
+        };
+        let Some(map) = node_at_mut(self.forms.first_mut().ok_or_else(empty)?, parents)? else {
+            return Ok(false);
+        };
+        if !matches!(map.kind, NodeKind::Map(_) | NodeKind::NamespacedMap { .. }) {
+            return Err(Error::Edit(format!(
+                "cannot dissoc from a {}",
+                name(&map.kind)
+            )));
+        }
+        let Some(i) = find(&map.kind, key)? else {
+            return Ok(false);
+        };
+        remove(collection_mut(&mut map.kind), i - 1, 2);
+        Ok(true)
+    }
+
+    /// Adds `value` to the collection at `path`, at the end of vectors and sets and at the
+    /// start of lists. Like Clojure's `conj`, a set that already holds `value` is left as is.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Document::get_in`], and `EdnError::Edit` when there is no vector, list or
+    /// set at `path` or `value` does not serialize to a single EDN element.
+    #[allow(clippy::needless_pass_by_value, reason = "takes literals like `5433`")]
+    pub fn conj<P: AsRef<str>>(
+        &mut self,
+        path: impl IntoIterator<Item = P>,
+        value: impl Serialize,
+    ) -> Result<(), Error> {
+        let keys = steps(path)?;
+        let value = element(&value.serialize())?;
+        let root = self.forms.first_mut().ok_or_else(empty)?;
+        let Some(node) = node_at_mut(root, &keys)? else {
+            return Err(Error::Edit(String::from(
+                "cannot conj onto a missing element",
+            )));
+        };
+        match &mut node.kind {
+            NodeKind::Vector(c) => append(c, vec![value]),
+            NodeKind::Set(c) => {
+                let value = Node {
+                    leading: Vec::new(),
+                    kind: value,
+                };
+                let edn = value.to_edn()?;
+                for child in &c.children {
+                    if child.to_edn()? == edn {
+                        return Ok(());
+                    }
+                }
+                append(c, vec![value.kind]);
+            }
+            NodeKind::List(c) => prepend(c, value),
+            kind => return Err(Error::Edit(format!("cannot conj onto a {}", name(kind)))),
+        }
+        Ok(())
+    }
+}
+
+fn empty() -> Error {
+    Error::Edit(String::from("the document has no element"))
+}
+
+fn steps<P: AsRef<str>>(path: impl IntoIterator<Item = P>) -> Result<Vec<Edn>, Error> {
+    path.into_iter()
+        .map(|step| Edn::from_str(step.as_ref()))
+        .collect()
+}
+
+/// The single element `source` is made of.
+fn element(source: &str) -> Result<NodeKind, Error> {
+    let mut doc = parse(source)?;
+    match doc.forms.pop() {
+        Some(node) if doc.forms.is_empty() => Ok(node.kind),
+        _ => Err(Error::Edit(format!("{source} is not a single element"))),
+    }
+}
+
+const fn name(kind: &NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Atom(_) => "scalar",
+        NodeKind::List(_) => "list",
+        NodeKind::Vector(_) => "vector",
+        NodeKind::Set(_) => "set",
+        NodeKind::Map(_) | NodeKind::NamespacedMap { .. } => "map",
+        NodeKind::Tagged { .. } => "tagged element",
+    }
+}
+
+// Only called on kinds `find` looked into
+fn collection(kind: &NodeKind) -> &Collection {
+    match kind {
+        NodeKind::List(c)
+        | NodeKind::Vector(c)
+        | NodeKind::Set(c)
+        | NodeKind::Map(c)
+        | NodeKind::NamespacedMap { map: c, .. } => c,
+        NodeKind::Atom(_) | NodeKind::Tagged { .. } => unreachable!(),
+    }
+}
+
+fn collection_mut(kind: &mut NodeKind) -> &mut Collection {
+    match kind {
+        NodeKind::List(c)
+        | NodeKind::Vector(c)
+        | NodeKind::Set(c)
+        | NodeKind::Map(c)
+        | NodeKind::NamespacedMap { map: c, .. } => c,
+        NodeKind::Atom(_) | NodeKind::Tagged { .. } => unreachable!(),
+    }
+}
+
+/// Index in the children of `kind` of the element at `key`: the value of a map entry or the
+/// element of a vector or list.
+fn find(kind: &NodeKind, key: &Edn) -> Result<Option<usize>, Error> {
+    let (map, namespace) = match kind {
+        NodeKind::Map(map) => (map, None),
+        NodeKind::NamespacedMap { prefix, map } => (map, Some(namespace(prefix))),
+        NodeKind::Vector(c) | NodeKind::List(c) => {
+            let Some(i) = key.to_uint() else {
+                return Err(Error::Edit(format!(
+                    "{key} is not an index of a {}",
+                    name(kind)
+                )));
+            };
+            return Ok(usize::try_from(i).ok().filter(|&i| i < c.children.len()));
+        }
+        kind => {
+            return Err(Error::Edit(format!(
+                "cannot look up {key} in a {}",
+                name(kind)
+            )));
+        }
+    };
+    for (i, entry) in map.children.chunks_exact(2).enumerate() {
+        let mut k = EdnRef::from(entry[0].to_edn()?);
+        if let Some((namespace, auto_resolved)) = namespace {
+            k = parser::qualify_key(k, namespace, auto_resolved);
+        }
+        if k.into_owned() == *key {
+            return Ok(Some(2 * i + 1));
+        }
+    }
+    Ok(None)
+}
+
+/// The namespace of a `#:ns{}` prefix, and whether it is auto-resolved.
+fn namespace(prefix: &str) -> (&str, bool) {
+    let namespace = prefix[2..].trim_end_matches(|c: char| c.is_whitespace() || c == ',');
+    namespace
+        .strip_prefix(':')
+        .map_or((namespace, false), |namespace| (namespace, true))
+}
+
+/// The key written in a `#:ns{}` map that reads back as `key`: `:user/id` is `:id` in
+/// `#:user{}`, and `:id` is `:_/id`.
+fn unqualify_key(key: &Edn, namespace: &str, auto_resolved: bool) -> Result<Edn, Error> {
+    let candidates = match key {
+        Edn::Key(k) => Keyword::parse(k).map_or_else(
+            |_| vec![key.clone()],
+            |k| {
+                let name = k.name();
+                vec![
+                    Edn::Key(format!(":{name}")),
+                    key.clone(),
+                    Edn::Key(format!(":_/{name}")),
+                ]
+            },
+        ),
+        Edn::Symbol(s) => Symbol::parse(s).map_or_else(
+            |_| vec![key.clone()],
+            |s| {
+                let name = s.name();
+                vec![
+                    Edn::Symbol(name.to_string()),
+                    key.clone(),
+                    Edn::Symbol(format!("_/{name}")),
+                ]
+            },
+        ),
+        key => vec![key.clone()],
+    };
+    candidates
+        .into_iter()
+        .find(|candidate| {
+            parser::qualify_key(EdnRef::from(candidate.clone()), namespace, auto_resolved)
+                .into_owned()
+                == *key
+        })
+        .ok_or_else(|| Error::Edit(format!("{key} cannot be written in a namespaced map")))
+}
+
+fn node_at_mut<'a>(mut node: &'a mut Node, keys: &[Edn]) -> Result<Option<&'a mut Node>, Error> {
+    for key in keys {
+        let Some(i) = find(&node.kind, key)? else {
+            return Ok(None);
+        };
+        node = &mut collection_mut(&mut node.kind).children[i];
+    }
+    Ok(Some(node))
+}
+
+fn assoc(node: &mut Node, keys: &[Edn], value: NodeKind) -> Result<(), Error> {
+    let Some((key, rest)) = keys.split_first() else {
+        node.kind = value;
+        return Ok(());
+    };
+    if let Some(i) = find(&node.kind, key)? {
+        return assoc(&mut collection_mut(&mut node.kind).children[i], rest, value);
+    }
+
+    // Like `assoc-in`, missing keys are added with maps
+    let value = if rest.is_empty() {
+        value
+    } else {
+        let value = Node {
+            leading: Vec::new(),
+            kind: value,
+        };
+        let nested = rest
+            .iter()
+            .rev()
+            .fold(value.to_string(), |value, key| format!("{{{key} {value}}}"));
+        element(&nested)?
+    };
+    match &mut node.kind {
+        NodeKind::Map(map) => append(map, vec![element(&key.to_string())?, value]),
+        NodeKind::NamespacedMap { prefix, map } => {
+            let (namespace, auto_resolved) = namespace(prefix);
+            let key = unqualify_key(key, namespace, auto_resolved)?;
+            append(map, vec![element(&key.to_string())?, value]);
+        }
+        NodeKind::Vector(c) | NodeKind::List(c) => {
+            let len = c.children.len();
+            if key.to_uint() != u64::try_from(len).ok() {
+                return Err(Error::Edit(format!(
+                    "index {key} is out of bounds of {len} elements"
+                )));
+            }
+            append(c, vec![value]);
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
+/// Whitespace separating a new node from the previous one, copied from `leading`: a newline
+/// and the same indentation, or the same spaces and commas on a single line.
+fn separator(leading: Option<&[Trivia]>) -> Trivia {
+    let whitespace = leading.and_then(|leading| {
+        leading.iter().rev().find_map(|t| match t {
+            Trivia::Whitespace(s) => Some(s.as_str()),
+            _ => None,
+        })
+    });
+    Trivia::Whitespace(whitespace.map_or_else(
+        || String::from(" "),
+        |s| String::from(s.rfind('\n').map_or(s, |i| &s[i..])),
+    ))
+}
+
+/// Trivia before the first newline, what follows the last element on its line.
+fn same_line(trivia: &mut Vec<Trivia>) -> Vec<Trivia> {
+    let end = trivia
+        .iter()
+        .position(|t| matches!(t, Trivia::Whitespace(s) if s.contains('\n')))
+        .unwrap_or(trivia.len());
+    trivia.drain(..end).collect()
+}
+
+/// Appends an entry of `kinds` to `collection`, laid out like its last entry.
+fn append(collection: &mut Collection, kinds: Vec<NodeKind>) {
+    let stride = kinds.len();
+    let len = collection.children.len();
+    // A comment after the last entry stays on its line
+    let mut comment = if collection
+        .trailing
+        .iter()
+        .take_while(|t| !matches!(t, Trivia::Whitespace(s) if s.contains('\n')))
+        .any(|t| matches!(t, Trivia::Comment(_)))
+    {
+        same_line(&mut collection.trailing)
+    } else {
+        Vec::new()
+    };
+
+    for (i, kind) in kinds.into_iter().enumerate() {
+        let leading = if len == 0 && i == 0 {
+            Vec::new()
+        } else {
+            // The first node of the entry follows the first node of the last entry, the
+            // others follow their predecessor
+            let sibling = if i == 0 {
+                len.checked_sub(stride)
+            } else {
+                len.checked_sub(1)
+            };
+            let sibling = sibling
+                .map(|s| collection.children[s].leading.as_slice())
+                .filter(|leading| !leading.is_empty());
+            let mut leading = if i == 0 {
+                mem::take(&mut comment)
+            } else {
+                Vec::new()
+            };
+            leading.push(separator(sibling));
+            leading
+        };
+        collection.children.push(Node { leading, kind });
+    }
+}
+
+fn prepend(collection: &mut Collection, kind: NodeKind) {
+    if collection.children.is_empty() {
+        return append(collection, vec![kind]);
+    }
+    let sibling = collection
+        .children
+        .get(1)
+        .map(|second| second.leading.as_slice());
+    let separator = separator(sibling);
+    let first = &mut collection.children[0];
+    let leading = mem::replace(&mut first.leading, vec![separator]);
+    collection.children.insert(0, Node { leading, kind });
+}
+
+/// Removes `count` children from `start`, with the comments on the line of the last one. The
+/// following node takes the place of the removed ones.
+fn remove(collection: &mut Collection, start: usize, count: usize) {
+    let leading = collection
+        .children
+        .drain(start..start + count)
+        .next()
+        .map(|node| node.leading)
+        .unwrap_or_default();
+
+    if let Some(next) = collection.children.get_mut(start) {
+        same_line(&mut next.leading);
+        let mut rest = mem::take(&mut next.leading).into_iter();
+        // The newline and indentation of the removed node replace those of the next one
+        if rest.len() > 0 {
+            rest.next();
+        }
+        next.leading = leading.into_iter().chain(rest).collect();
+    } else {
+        // Comments before the removed node stay, only its separator goes
+        let mut leading = leading;
+        if matches!(leading.last(), Some(Trivia::Whitespace(_))) {
+            leading.pop();
+        }
+        same_line(&mut collection.trailing);
+        leading.append(&mut collection.trailing);
+        collection.trailing = leading;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn edit(source: &str, f: impl FnOnce(&mut Document)) -> String {
+        let mut doc = Document::from_str(source).unwrap();
+        f(&mut doc);
+        doc.to_string()
+    }
+
+    #[test]
+    fn appends_like_the_last_entry() {
+        assert_eq!(edit("{}", |doc| doc.assoc_in([":a"], 1).unwrap()), "{:a 1}");
+        assert_eq!(
+            edit("{:a 1, :b 2}", |doc| doc.assoc_in([":c"], 3).unwrap()),
+            "{:a 1, :b 2, :c 3}"
+        );
+        assert_eq!(
+            edit("{\n  :a 1 ; one\n}", |doc| doc.assoc_in([":b"], 2).unwrap()),
+            "{\n  :a 1 ; one\n  :b 2\n}"
+        );
+        assert_eq!(
+            edit("[1\n 2]", |doc| doc.conj(Vec::<&str>::new(), 3).unwrap()),
+            "[1\n 2\n 3]"
+        );
+    }
+
+    #[test]
+    fn removes_comments_on_the_line() {
+        assert_eq!(
+            edit("{:a 1\n :b 2 ; two\n ;; c\n :c 3}", |doc| {
+                assert!(doc.dissoc([":b"]).unwrap());
+            }),
+            "{:a 1\n ;; c\n :c 3}"
+        );
+        assert_eq!(
+            edit("{:a 1 :b 2 ; two\n}", |doc| {
+                assert!(doc.dissoc([":b"]).unwrap());
+            }),
+            "{:a 1\n}"
+        );
+        assert_eq!(
+            edit("{:a 1 :b 2}", |doc| {
+                assert!(doc.dissoc([":a"]).unwrap());
+                assert!(!doc.dissoc([":a"]).unwrap());
+            }),
+            "{:b 2}"
+        );
+    }
+}
@@ -0,0 +1,595 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::deserialize::options::{DEFAULT_OPTIONS, ReaderOptions};
+use crate::deserialize::parse::{self, DELIMITERS};
+use crate::edn::{Edn, EdnRef, Error, ParseError, ParseErrorKind, Symbol};
+
+mod edit;
+
+/// `Document` is the concrete syntax tree of an EDN source: its top-level forms, each with the
+/// trivia preceding it, and the trivia after the last one.
+///
+/// Its `Display` writes back the source it was parsed from byte for byte, and
+/// [`Document::lower`] reads its forms as `Edn`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Document {
+    pub forms: Vec<Node>,
+    pub trailing: Vec<Trivia>,
+}
+
+/// An element of a [`Document`], with the trivia written before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub leading: Vec<Trivia>,
+    pub kind: NodeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeKind {
+    /// A string, char, number, keyword, symbol, `nil`, boolean or `##Inf` as written
+    Atom(String),
+    List(Collection),
+    Vector(Collection),
+    Set(Collection),
+    /// Keys and values, in source order
+    Map(Collection),
+    /// `#:ns{...}` with its prefix as written up to the `{`, including the whitespace
+    /// separating them
+    NamespacedMap {
+        prefix: String,
+        map: Collection,
+    },
+    /// A tagged element, `tag` is written without its leading `#`
+    Tagged {
+        tag: String,
+        element: Box<Node>,
+    },
+}
+
+/// The elements between the delimiters of a collection, and the trivia before its closing one.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Collection {
+    pub children: Vec<Node>,
+    pub trailing: Vec<Trivia>,
+}
+
+/// What the reader skips between elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Trivia {
+    /// A run of whitespace and commas
+    Whitespace(String),
+    /// A `;` comment, without the newline ending it
+    Comment(String),
+    /// `#_` and the element it discards
+    Discard(Box<Node>),
+}
+
+/// `parse` reads an EDN source into a [`Document`] that keeps its comments, discarded
+/// elements, commas and whitespace.
+///
+/// # Errors
+///
+/// `EdnError::Parse` when the source is not valid EDN, with the same kind and position as
+/// `Edn::from_str`. Tagged elements and sets are only read by [`Document::lower`].
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::Edn;
+/// use edn_rs::cst::{NodeKind, Trivia};
+///
+/// let source = "{:port 8080 ; default\n #_:debug :env :dev}";
+/// let doc = edn_rs::cst::parse(source).unwrap();
+///
+/// let NodeKind::Map(map) = &doc.forms[0].kind else { panic!() };
+/// assert_eq!(map.children[2].leading[1], Trivia::Comment("; default".to_string()));
+/// assert_eq!(doc.to_string(), source);
+/// assert_eq!(doc.lower().unwrap(), [Edn::from_str("{:port 8080 :env :dev}").unwrap()]);
+/// ```
+pub fn parse(source: &str) -> Result<Document, Error> {
+    let mut cursor = Cursor { source, offset: 0 };
+    let mut forms = Vec::new();
+    loop {
+        let leading = read_trivia(&mut cursor)?;
+        if cursor.peek().is_none() {
+            return Ok(Document {
+                forms,
+                trailing: leading,
+            });
+        }
+        forms.push(read_node(leading, &mut cursor)?);
+    }
+}
+
+impl Document {
+    /// Reads each top-level form as an `Edn`, the same way `Edn::from_str` reads one.
+    ///
+    /// # Errors
+    ///
+    /// `EdnError::Parse` for an element `Edn::from_str` would reject, like `#inst "yesterday"`
+    /// or a set without feature `sets`.
+    pub fn lower(&self) -> Result<Vec<Edn>, Error> {
+        self.lower_with(&DEFAULT_OPTIONS)
+    }
+
+    /// Same as [`Document::lower`], reading tagged elements and maps with `options`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Document::lower`], and `ParseErrorKind::InvalidTagged` when a tag handler fails.
+    pub fn lower_with(&self, options: &ReaderOptions) -> Result<Vec<Edn>, Error> {
+        let mut lowering = Lowering {
+            source: self.to_string(),
+            offset: 0,
+            options,
+        };
+        self.forms
+            .iter()
+            .map(|node| lowering.node(node).map(EdnRef::into_owned))
+            .collect()
+    }
+}
+
+impl Node {
+    /// Reads this node as an `Edn`, see [`Document::lower`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Document::lower`].
+    pub fn to_edn(&self) -> Result<Edn, Error> {
+        let mut lowering = Lowering {
+            source: self.to_string(),
+            offset: 0,
+            options: &DEFAULT_OPTIONS,
+        };
+        lowering.node(self).map(EdnRef::into_owned)
+    }
+}
+
+impl FromStr for Document {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+struct Cursor<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.rest().chars().nth(1)
+    }
+
+    fn bump(&mut self) {
+        self.offset += self.peek().map_or(0, char::len_utf8);
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.offset;
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.offset += len;
+        &self.source[start..self.offset]
+    }
+
+    fn error(&self, offset: usize, kind: ParseErrorKind) -> Error {
+        Error::Parse(ParseError::new(self.source, offset, kind))
+    }
+}
+
+fn is_token_char(c: char) -> bool {
+    !c.is_whitespace() && !DELIMITERS.contains(&c)
+}
+
+fn read_trivia(cursor: &mut Cursor<'_>) -> Result<Vec<Trivia>, Error> {
+    let mut trivia = Vec::new();
+    loop {
+        match cursor.peek() {
+            Some(c) if c.is_whitespace() || c == ',' => {
+                let space = cursor.take_while(|c| c.is_whitespace() || c == ',');
+                trivia.push(Trivia::Whitespace(String::from(space)));
+            }
+            Some(';') => {
+                let comment = cursor.take_while(|c| c != '\n');
+                trivia.push(Trivia::Comment(String::from(comment)));
+            }
+            Some('#') if cursor.peek_second() == Some('_') => {
+                let start = cursor.offset;
+                cursor.offset += 2;
+                let leading = read_trivia(cursor)?;
+                if cursor.peek().is_none() {
+                    return Err(cursor.error(start, ParseErrorKind::DiscardWithoutElement));
+                }
+                trivia.push(Trivia::Discard(Box::new(read_node(leading, cursor)?)));
+            }
+            _ => return Ok(trivia),
+        }
+    }
+}
+
+fn read_node(leading: Vec<Trivia>, cursor: &mut Cursor<'_>) -> Result<Node, Error> {
+    let start = cursor.offset;
+    let kind = match (cursor.peek(), cursor.peek_second()) {
+        (Some('('), _) => NodeKind::List(read_collection(1, ')', "(", cursor)?),
+        (Some('['), _) => NodeKind::Vector(read_collection(1, ']', "[", cursor)?),
+        (Some('{'), _) => NodeKind::Map(read_collection(1, '}', "{", cursor)?),
+        (Some('#'), Some('{')) => NodeKind::Set(read_collection(2, '}', "#{", cursor)?),
+        (Some('#'), Some(':')) => read_namespaced_map(cursor)?,
+        (Some('#'), Some('#')) => {
+            cursor.take_while(|c| c == '#' || is_token_char(c));
+            read_atom(start, cursor)?
+        }
+        (Some('#'), _) => {
+            cursor.bump();
+            let tag = cursor.take_while(|c| !c.is_whitespace() && c != ',');
+            let leading = read_trivia(cursor)?;
+            NodeKind::Tagged {
+                tag: String::from(tag),
+                element: Box::new(read_node(leading, cursor)?),
+            }
+        }
+        (Some('"'), _) => {
+            read_string(cursor)?;
+            read_atom(start, cursor)?
+        }
+        (Some('\\'), _) => {
+            cursor.bump();
+            cursor.offset += char_len(cursor.rest());
+            read_atom(start, cursor)?
+        }
+        (Some(d @ (']' | ')' | '}')), _) => {
+            return Err(cursor.error(start, ParseErrorKind::UnexpectedDelimiter(d)));
+        }
+        (Some(_), _) => {
+            cursor.take_while(is_token_char);
+            read_atom(start, cursor)?
+        }
+        (None, _) => return Err(cursor.error(start, ParseErrorKind::UnexpectedEof)),
+    };
+    Ok(Node { leading, kind })
+}
+
+/// Reads the atom from `start` to the cursor with the parser, so that it is checked the same
+/// way as in `Edn::from_str`.
+fn read_atom(start: usize, cursor: &Cursor<'_>) -> Result<NodeKind, Error> {
+    let atom = &cursor.source[start..cursor.offset];
+    parse::parse_with(atom, &DEFAULT_OPTIONS).map_err(|e| relocate(e, cursor.source, start))?;
+    Ok(NodeKind::Atom(String::from(atom)))
+}
+
+fn relocate(err: Error, source: &str, start: usize) -> Error {
+    match err {
+        Error::Parse(err) => Error::Parse(ParseError::new(source, start + err.offset, err.kind)),
+        err => err,
+    }
+}
+
+fn read_string(cursor: &mut Cursor<'_>) -> Result<(), Error> {
+    let start = cursor.offset;
+    cursor.bump();
+    loop {
+        match cursor.peek() {
+            Some('"') => {
+                cursor.bump();
+                return Ok(());
+            }
+            Some('\\') => {
+                cursor.bump();
+                cursor.bump();
+            }
+            Some(_) => cursor.bump(),
+            None => return Err(cursor.error(start, ParseErrorKind::UnterminatedString)),
+        }
+    }
+}
+
+/// Length of a char literal after its `\`, following what the parser consumes: a name like
+/// `newline`, a `\u` or `\o` escape, or a single char.
+fn char_len(rest: &str) -> usize {
+    let names = ["newline", "return", "tab", "space", "formfeed", "backspace"];
+    if let Some(name) = names.iter().find(|name| rest.starts_with(*name)) {
+        return name.len();
+    }
+    let Some(first) = rest.chars().next().filter(|c| !c.is_whitespace()) else {
+        return 0;
+    };
+    if !matches!(first, 'u' | 'o') {
+        return first.len_utf8();
+    }
+    let digits = rest[1..]
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(rest.len() - 1);
+    1 + digits
+}
+
+fn read_collection(
+    open: usize,
+    close: char,
+    delimiter: &'static str,
+    cursor: &mut Cursor<'_>,
+) -> Result<Collection, Error> {
+    let start = cursor.offset;
+    cursor.offset += open;
+    let mut children = Vec::new();
+    loop {
+        let leading = read_trivia(cursor)?;
+        match cursor.peek() {
+            Some(c) if c == close => {
+                cursor.bump();
+                return Ok(Collection {
+                    children,
+                    trailing: leading,
+                });
+            }
+            Some(_) => children.push(read_node(leading, cursor)?),
+            None => return Err(cursor.error(start, ParseErrorKind::Unclosed(delimiter))),
+        }
+    }
+}
+
+fn read_namespaced_map(cursor: &mut Cursor<'_>) -> Result<NodeKind, Error> {
+    let start = cursor.offset;
+    cursor.offset += 2;
+    let (namespace, auto_resolved) = {
+        let auto_resolved = cursor.peek() == Some(':');
+        if auto_resolved {
+            cursor.bump();
+        }
+        (cursor.take_while(is_token_char), auto_resolved)
+    };
+    let prefix_end = cursor.offset;
+    let valid = if namespace.is_empty() {
+        auto_resolved
+    } else {
+        Symbol::parse(namespace).is_ok_and(|s| s.namespace().is_none() && s.name() != "/")
+    };
+
+    cursor.take_while(|c| c.is_whitespace() || c == ',');
+    if !valid || cursor.peek() != Some('{') {
+        let prefix = String::from(&cursor.source[start..prefix_end]);
+        return Err(cursor.error(start, ParseErrorKind::InvalidNamespacedMap(prefix)));
+    }
+    let prefix = String::from(&cursor.source[start..cursor.offset]);
+    Ok(NodeKind::NamespacedMap {
+        prefix,
+        map: read_collection(1, '}', "{", cursor)?,
+    })
+}
+
+/// Walks a document in source order, keeping track of the offset of each node in its
+/// rendering so that errors point at it.
+struct Lowering<'o> {
+    source: String,
+    offset: usize,
+    options: &'o ReaderOptions,
+}
+
+impl Lowering<'_> {
+    fn skip(&mut self, trivia: &[Trivia]) {
+        for t in trivia {
+            self.offset += match t {
+                Trivia::Whitespace(s) | Trivia::Comment(s) => s.len(),
+                Trivia::Discard(node) => 2 + node.to_string().len(),
+            };
+        }
+    }
+
+    fn error(&self, offset: usize, kind: ParseErrorKind) -> Error {
+        Error::Parse(ParseError::new(&self.source, offset, kind))
+    }
+
+    fn node<'a>(&mut self, node: &'a Node) -> Result<EdnRef<'a>, Error> {
+        self.skip(&node.leading);
+        let start = self.offset;
+        match &node.kind {
+            NodeKind::Atom(atom) => {
+                self.offset += atom.len();
+                parse::parse_with(atom, &DEFAULT_OPTIONS)
+                    .map_err(|e| relocate(e, &self.source, start))
+            }
+            NodeKind::List(list) => self.children(1, list).map(EdnRef::List),
+            NodeKind::Vector(vector) => self.children(1, vector).map(EdnRef::Vector),
+            #[cfg(feature = "sets")]
+            NodeKind::Set(set) => self.children(2, set).map(EdnRef::Set),
+            #[cfg(not(feature = "sets"))]
+            NodeKind::Set(_) => Err(self.error(start, ParseErrorKind::SetsDisabled)),
+            NodeKind::Map(map) => self.map(1, map, |entries| entries),
+            NodeKind::NamespacedMap { prefix, map } => {
+                let namespace =
+                    prefix[2..].trim_end_matches(|c: char| c.is_whitespace() || c == ',');
+                let (namespace, auto_resolved) = namespace
+                    .strip_prefix(':')
+                    .map_or((namespace, false), |namespace| (namespace, true));
+                self.map(prefix.len(), map, |entries| {
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| {
+                            (parse::qualify_key(key, namespace, auto_resolved), value)
+                        })
+                        .collect()
+                })
+            }
+            NodeKind::Tagged { tag, element } => {
+                self.offset += 1 + tag.len();
+                let edn = self.node(element)?;
+                if !self.options.tags.contains(tag) {
+                    return Ok(EdnRef::Tagged(Cow::Borrowed(tag), Box::new(edn)));
+                }
+                match self.options.tags.read(tag, edn.into_owned()) {
+                    Ok(edn) => Ok(EdnRef::from(edn)),
+                    Err(message) => Err(self.error(
+                        start,
+                        ParseErrorKind::InvalidTagged {
+                            tag: tag.clone(),
+                            message,
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
+    fn children<'a>(
+        &mut self,
+        open: usize,
+        collection: &'a Collection,
+    ) -> Result<Vec<EdnRef<'a>>, Error> {
+        self.offset += open;
+        let children = collection
+            .children
+            .iter()
+            .map(|node| self.node(node))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.skip(&collection.trailing);
+        self.offset += 1;
+        Ok(children)
+    }
+
+    // Like the parser, a key without a value is dropped
+    fn map<'a>(
+        &mut self,
+        open: usize,
+        map: &'a Collection,
+        keys: impl FnOnce(Vec<(EdnRef<'a>, EdnRef<'a>)>) -> Vec<(EdnRef<'a>, EdnRef<'a>)>,
+    ) -> Result<EdnRef<'a>, Error> {
+        let mut children = self.children(open, map)?.into_iter();
+        let mut entries = Vec::new();
+        while let (Some(key), Some(value)) = (children.next(), children.next()) {
+            entries.push((key, value));
+        }
+        let entries = keys(entries);
+        Ok(if self.options.ordered_maps {
+            EdnRef::OrderedMap(entries)
+        } else {
+            EdnRef::Map(entries)
+        })
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for node in &self.forms {
+            write!(f, "{node}")?;
+        }
+        write_trivia(f, &self.trailing)
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_trivia(f, &self.leading)?;
+        match &self.kind {
+            NodeKind::Atom(atom) => write!(f, "{atom}"),
+            NodeKind::List(list) => write_collection(f, "(", list, ")"),
+            NodeKind::Vector(vector) => write_collection(f, "[", vector, "]"),
+            NodeKind::Set(set) => write_collection(f, "#{", set, "}"),
+            NodeKind::Map(map) => write_collection(f, "{", map, "}"),
+            NodeKind::NamespacedMap { prefix, map } => {
+                write!(f, "{prefix}")?;
+                write_collection(f, "{", map, "}")
+            }
+            NodeKind::Tagged { tag, element } => write!(f, "#{tag}{element}"),
+        }
+    }
+}
+
+impl fmt::Display for Trivia {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Whitespace(s) | Self::Comment(s) => write!(f, "{s}"),
+            Self::Discard(node) => write!(f, "#_{node}"),
+        }
+    }
+}
+
+fn write_trivia(f: &mut fmt::Formatter<'_>, trivia: &[Trivia]) -> fmt::Result {
+    trivia.iter().try_for_each(|t| write!(f, "{t}"))
+}
+
+fn write_collection(
+    f: &mut fmt::Formatter<'_>,
+    open: &str,
+    collection: &Collection,
+    close: &str,
+) -> fmt::Result {
+    write!(f, "{open}")?;
+    for node in &collection.children {
+        write!(f, "{node}")?;
+    }
+    write_trivia(f, &collection.trailing)?;
+    write!(f, "{close}")
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use super::*;
+
+    fn ws(s: &str) -> Trivia {
+        Trivia::Whitespace(s.to_string())
+    }
+
+    fn atom(leading: Vec<Trivia>, atom: &str) -> Node {
+        Node {
+            leading,
+            kind: NodeKind::Atom(atom.to_string()),
+        }
+    }
+
+    #[test]
+    fn trivia_is_attached_to_the_next_node() {
+        let doc = parse("; config\n[1, #_ 2 3] ; end").unwrap();
+
+        assert_eq!(
+            doc,
+            Document {
+                forms: vec![Node {
+                    leading: vec![Trivia::Comment("; config".to_string()), ws("\n")],
+                    kind: NodeKind::Vector(Collection {
+                        children: vec![
+                            atom(vec![], "1"),
+                            atom(
+                                vec![
+                                    ws(", "),
+                                    Trivia::Discard(Box::new(atom(vec![ws(" ")], "2"))),
+                                    ws(" "),
+                                ],
+                                "3"
+                            ),
+                        ],
+                        trailing: vec![],
+                    }),
+                }],
+                trailing: vec![ws(" "), Trivia::Comment("; end".to_string())],
+            }
+        );
+    }
+
+    #[test]
+    fn char_literals() {
+        assert_eq!(char_len("newline]"), 7);
+        assert_eq!(char_len("u00e9 "), 5);
+        assert_eq!(char_len("o101"), 4);
+        assert_eq!(char_len("u"), 1);
+        assert_eq!(char_len("(a"), 1);
+        assert_eq!(char_len("é"), 2);
+        assert_eq!(char_len(" "), 0);
+    }
+}
@@ -0,0 +1,105 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, ready};
+use std::io;
+
+use futures_core::Stream;
+use tokio::io::{AsyncBufRead, AsyncRead, BufReader};
+
+use crate::deserialize::framer::Framer;
+use crate::deserialize::options::ReaderOptions;
+use crate::edn::{Edn, Error};
+
+/// `AsyncEdnReader` is a [`Stream`] of the top-level forms of an EDN byte stream read from a
+/// `tokio::io::AsyncRead`, like a `TcpStream` or a `File`. Requires feature `async`.
+///
+/// It frames forms the same way as [`crate::EdnReader`], keeping only the current form in
+/// memory, and never blocks the runtime while waiting for the rest of a form.
+///
+/// ```rust
+/// use edn_rs::{AsyncEdnReader, Edn};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let (client, mut server) = tokio::io::duplex(16);
+/// tokio::spawn(async move {
+///     use tokio::io::AsyncWriteExt;
+///     server.write_all(b"{:op :ping} [1 2").await.unwrap();
+///     server.write_all(b" 3] :bye").await.unwrap();
+/// });
+///
+/// let mut reader = AsyncEdnReader::new(client);
+/// assert_eq!(reader.next_form().await.unwrap().unwrap()[":op"], Edn::Key(":ping".to_string()));
+/// assert_eq!(reader.next_form().await.unwrap().unwrap()[2], Edn::UInt(3));
+/// assert_eq!(reader.next_form().await, Some(Ok(Edn::Key(":bye".to_string()))));
+/// assert_eq!(reader.next_form().await, None);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncEdnReader<R> {
+    reader: BufReader<R>,
+    framer: Framer,
+    options: ReaderOptions,
+    done: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncEdnReader<R> {
+    /// Wraps `reader` in a [`BufReader`], there is no need to buffer it beforehand.
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(8 * 1024, reader)
+    }
+
+    /// Same as [`AsyncEdnReader::new`] with a read buffer of `capacity` bytes.
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        Self {
+            reader: BufReader::with_capacity(capacity, reader),
+            framer: Framer::new(),
+            options: ReaderOptions::new(),
+            done: false,
+        }
+    }
+
+    /// Reads the next form, same as `StreamExt::next` without needing a `Stream` extension
+    /// trait in scope.
+    pub async fn next_form(&mut self) -> Option<Result<Edn, Error>> {
+        core::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    /// Reads forms with `options`, like tag handlers, see [`ReaderOptions`].
+    #[must_use]
+    pub fn options(mut self, options: ReaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Unwraps this `AsyncEdnReader`, returning the underlying reader. Bytes buffered but not
+    /// yet read into a form are lost.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for AsyncEdnReader<R> {
+    type Item = Result<Edn, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.done {
+            let (used, ready) = match ready!(Pin::new(&mut this.reader).poll_fill_buf(cx)) {
+                Ok([]) => {
+                    this.done = true;
+                    return Poll::Ready(this.framer.finish(&this.options));
+                }
+                Ok(buf) => this.framer.feed(buf, this.options.max_form_len),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+            };
+            Pin::new(&mut this.reader).consume(used);
+            if ready && let Some(form) = this.framer.take(&this.options) {
+                return Poll::Ready(Some(form));
+            }
+        }
+        Poll::Ready(None)
+    }
+}
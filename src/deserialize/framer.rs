@@ -0,0 +1,348 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::deserialize::options::ReaderOptions;
+use crate::edn::{Edn, Error, ParseError, ParseErrorKind};
+
+/// `Framer` splits a stream of bytes into top-level EDN forms without parsing them.
+///
+/// Bytes are pushed with [`Framer::feed`] and only the form currently being read is kept,
+/// whitespace and comments between forms are dropped. Once a form is complete it is parsed
+/// with [`Framer::take`]. Positions of parse errors are relative to the whole stream.
+///
+/// A form longer than `max_form_len` is reported as soon as it goes past it, the rest of it is
+/// then skipped without being kept.
+#[derive(Debug)]
+pub struct Framer {
+    form: Vec<u8>,
+    started: bool,
+    depth: usize,
+    token: Token,
+    lexer: Lexer,
+    // Tags and discards at depth 0 still waiting for their element
+    prefixes: Vec<Prefix>,
+    max_form_len: usize,
+    // The form went past `max_form_len` before it was complete, the rest of it is dropped
+    skipping: bool,
+    // Position of the next byte in the stream, `line` and `column` are 1-based
+    offset: usize,
+    line: usize,
+    column: usize,
+    // Position of the first byte of `form`
+    start: (usize, usize, usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lexer {
+    Code,
+    Str,
+    // Right after a `\` inside a string
+    Escape,
+    Comment,
+    // Right after the `\` of a char literal, the next byte is never a delimiter
+    Char,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    None,
+    Atom,
+    // `#tag` or `#_`, which need a following element to be complete
+    Prefix,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Prefix {
+    // `#tag`, and `#{`, `#"` or `#:ns{` in front of their element
+    Tag,
+    Discard,
+}
+
+const fn is_delimiter(b: u8) -> bool {
+    b.is_ascii_whitespace()
+        || matches!(
+            b,
+            b',' | b'(' | b')' | b'[' | b']' | b'{' | b'}' | b'"' | b';'
+        )
+}
+
+impl Framer {
+    pub const fn new() -> Self {
+        Self {
+            form: Vec::new(),
+            started: false,
+            depth: 0,
+            token: Token::None,
+            lexer: Lexer::Code,
+            prefixes: Vec::new(),
+            max_form_len: usize::MAX,
+            skipping: false,
+            offset: 0,
+            line: 1,
+            column: 1,
+            start: (0, 1, 1),
+        }
+    }
+
+    /// Consumes bytes from `input` until a form is complete, or longer than `max_form_len`.
+    /// Returns how many bytes were consumed and whether a form is ready to [`Framer::take`].
+    pub fn feed(&mut self, input: &[u8], max_form_len: usize) -> (usize, bool) {
+        self.max_form_len = max_form_len;
+        for (i, &b) in input.iter().enumerate() {
+            if self.form.len() > max_form_len && !self.skipping {
+                self.skipping = true;
+                return (i, true);
+            }
+            match (self.lexer, b) {
+                (Lexer::Code, _) => {}
+                (Lexer::Str, b'"') => {
+                    self.lexer = Lexer::Code;
+                    self.push(b);
+                    if self.depth == 0 && self.complete() {
+                        return (i + 1, true);
+                    }
+                    continue;
+                }
+                (Lexer::Str, b'\\') => {
+                    self.lexer = Lexer::Escape;
+                    self.push(b);
+                    continue;
+                }
+                (Lexer::Comment, b'\n') | (Lexer::Escape | Lexer::Char, _) => {
+                    self.lexer = if self.lexer == Lexer::Escape {
+                        Lexer::Str
+                    } else {
+                        Lexer::Code
+                    };
+                    self.push(b);
+                    continue;
+                }
+                (Lexer::Str | Lexer::Comment, _) => {
+                    self.push(b);
+                    continue;
+                }
+            }
+
+            if self.token != Token::None && is_delimiter(b) {
+                let token = mem::replace(&mut self.token, Token::None);
+                if self.depth == 0 {
+                    match token {
+                        Token::Atom if self.complete() => return (i, true),
+                        Token::Prefix => self.prefixes.push(Prefix::Tag),
+                        _ => {}
+                    }
+                }
+            }
+
+            match b {
+                b if b.is_ascii_whitespace() || b == b',' => {}
+                b';' => self.lexer = Lexer::Comment,
+                b'"' => self.lexer = Lexer::Str,
+                b'\\' => {
+                    self.lexer = Lexer::Char;
+                    self.token = Token::Atom;
+                }
+                b'(' | b'[' | b'{' => self.depth += 1,
+                b')' | b']' | b'}' => {
+                    self.start_form();
+                    self.push(b);
+                    // An unmatched delimiter is a form of its own, the parser reports it
+                    if self.depth <= 1 {
+                        self.depth = 0;
+                        if self.complete() {
+                            return (i + 1, true);
+                        }
+                    } else {
+                        self.depth -= 1;
+                    }
+                    continue;
+                }
+                b'#' if self.token == Token::None => self.token = Token::Prefix,
+                // `##Inf` is an element of its own, not a tag
+                b'#' if self.token == Token::Prefix && self.form.last() == Some(&b'#') => {
+                    self.token = Token::Atom;
+                }
+                b'_' if self.token == Token::Prefix && self.form.last() == Some(&b'#') => {
+                    self.token = Token::None;
+                    if self.depth == 0 {
+                        self.prefixes.push(Prefix::Discard);
+                    }
+                }
+                _ if self.token == Token::None => self.token = Token::Atom,
+                _ => {}
+            }
+
+            if !b.is_ascii_whitespace() && b != b',' && b != b';' {
+                self.start_form();
+            }
+            self.push(b);
+        }
+        (input.len(), false)
+    }
+
+    /// Parses the buffered form, returns `None` when it holds no element, like `#_ :a`, or
+    /// when it was too long and has already been reported.
+    pub fn take(&mut self, options: &ReaderOptions) -> Option<Result<Edn, Error>> {
+        if self.skipping && !self.form.is_empty() {
+            // The form is not complete, the framer keeps its state to skip the rest of it
+            let form = mem::take(&mut self.form);
+            return Some(Err(self.too_long(&form)));
+        }
+        let form = mem::take(&mut self.form);
+        let skipped = mem::replace(&mut self.skipping, false);
+        self.reset();
+        if skipped {
+            return None;
+        }
+        if form.len() > self.max_form_len {
+            return Some(Err(self.too_long(&form)));
+        }
+
+        let result = match core::str::from_utf8(&form) {
+            Ok(source) => options.parse(source),
+            Err(e) => Err(Error::Parse(ParseError::new(
+                &String::from_utf8_lossy(&form),
+                e.valid_up_to(),
+                ParseErrorKind::InvalidUtf8,
+            ))),
+        };
+        match result {
+            Ok(Edn::Empty) => None,
+            Err(Error::Parse(e)) => Some(Err(Error::Parse(self.locate(e)))),
+            result => Some(result),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.started = false;
+        self.depth = 0;
+        self.token = Token::None;
+        self.lexer = Lexer::Code;
+        self.prefixes.clear();
+    }
+
+    // Only the start of the form makes the snippet, it could be as long as `max_form_len`
+    fn too_long(&self, form: &[u8]) -> Error {
+        let start = String::from_utf8_lossy(&form[..form.len().min(80)]);
+        let kind = ParseErrorKind::FormTooLong(self.max_form_len);
+        Error::Parse(self.locate(ParseError::new(&start, 0, kind)))
+    }
+
+    /// Called at the end of the stream, parses what is left of an incomplete form.
+    pub fn finish(&mut self, options: &ReaderOptions) -> Option<Result<Edn, Error>> {
+        if self.started {
+            self.take(options)
+        } else {
+            None
+        }
+    }
+
+    // An element ended at depth 0, returns whether the form is complete: pending tags take the
+    // element and end with it, a discard drops it and the form goes on when more prefixes wait
+    fn complete(&mut self) -> bool {
+        while let Some(prefix) = self.prefixes.pop() {
+            if prefix == Prefix::Discard {
+                return self.prefixes.is_empty();
+            }
+        }
+        true
+    }
+
+    const fn start_form(&mut self) {
+        if !self.started {
+            self.started = true;
+            self.start = (self.offset, self.line, self.column);
+        }
+    }
+
+    fn push(&mut self, b: u8) {
+        if self.started && !self.skipping {
+            self.form.push(b);
+        }
+        self.offset += 1;
+        if b == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else if b & 0xC0 != 0x80 {
+            self.column += 1;
+        }
+    }
+
+    // Moves a position relative to the form to the stream. What came before the form on its
+    // first line is no longer buffered, so the snippet is padded to keep the caret aligned.
+    fn locate(&self, mut err: ParseError) -> ParseError {
+        let (offset, line, column) = self.start;
+        if err.line == 1 {
+            err.column += column - 1;
+            err.snippet.insert_str(0, &" ".repeat(column - 1));
+        }
+        err.line += line - 1;
+        err.offset += offset;
+        err
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+    use crate::deserialize::parse::parse;
+
+    fn frames(input: &str) -> Vec<Result<Edn, Error>> {
+        let mut framer = Framer::new();
+        let mut input = input.as_bytes();
+        let mut forms = Vec::new();
+        loop {
+            let (used, ready) = framer.feed(input, usize::MAX);
+            input = &input[used..];
+            if ready {
+                forms.extend(framer.take(&ReaderOptions::new()));
+            } else {
+                forms.extend(framer.finish(&ReaderOptions::new()));
+                return forms;
+            }
+        }
+    }
+
+    #[test]
+    fn splits_top_level_forms() {
+        assert_eq!(
+            frames("1 :a, \"b c\"[1 2]{:d (3)}(4) #tag 5 \\) sym"),
+            vec![
+                Ok(Edn::UInt(1)),
+                Ok(Edn::Key(":a".to_string())),
+                Ok(Edn::Str("b c".to_string())),
+                parse("[1 2]"),
+                parse("{:d (3)}"),
+                parse("(4)"),
+                parse("#tag 5"),
+                Ok(Edn::Char(')')),
+                Ok(Edn::Symbol("sym".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_comments_and_discards() {
+        assert_eq!(
+            frames("; header\n#_ [1 2] ; one\n[3 \"]\" \\] ; ]\n]\n#_:b"),
+            vec![parse("[3 \"]\" \\]]")]
+        );
+    }
+
+    #[test]
+    fn errors_point_into_the_stream() {
+        let forms = frames("[1 2]\n:ok [3 \"\\q\"]");
+        let Err(Error::Parse(err)) = &forms[2] else {
+            panic!("{forms:?}")
+        };
+
+        assert_eq!(forms[1], Ok(Edn::Key(":ok".to_string())));
+        assert_eq!(err.kind, ParseErrorKind::InvalidEscape('q'));
+        assert_eq!((err.line, err.column, err.offset), (2, 9, 14));
+        assert_eq!(err.snippet, "    [3 \"\\q\"]");
+    }
+}
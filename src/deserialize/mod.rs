@@ -13,9 +13,16 @@ use std::collections::HashMap;
 #[cfg(all(feature = "sets", feature = "std"))]
 use std::collections::HashSet;
 
-use crate::edn::{Edn, Error};
+use crate::edn::{Edn, Error, Inst, Keyword, Symbol, Uuid};
 
+#[cfg(feature = "async")]
+pub mod async_reader;
+#[cfg(feature = "std")]
+mod framer;
+pub mod options;
 pub mod parse;
+#[cfg(feature = "std")]
+pub mod reader;
 
 #[cfg(feature = "sets")]
 use ordered_float::OrderedFloat;
@@ -86,6 +93,12 @@ impl Deserialize for () {
     }
 }
 
+impl Deserialize for Edn {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        Ok(edn.clone())
+    }
+}
+
 #[cfg(feature = "sets")]
 impl Deserialize for OrderedFloat<f64> {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
@@ -153,11 +166,103 @@ impl Deserialize for String {
                     Ok(s.clone())
                 }
             }
+            Edn::BigInt(n) | Edn::Decimal(n) => Ok(n.clone()),
             e => Ok(e.to_string()),
         }
     }
 }
 
+impl Deserialize for Keyword {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        edn.to_keyword()
+            .ok_or_else(|| build_deserialize_error(edn, "edn_rs::Keyword"))
+    }
+}
+
+impl Deserialize for Symbol {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        edn.to_symbol()
+            .ok_or_else(|| build_deserialize_error(edn, "edn_rs::Symbol"))
+    }
+}
+
+impl Deserialize for Inst {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        edn.to_inst()
+            .ok_or_else(|| build_deserialize_error(edn, "edn_rs::Inst"))
+    }
+}
+
+impl Deserialize for Uuid {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        edn.to_uuid()
+            .ok_or_else(|| build_deserialize_error(edn, "edn_rs::Uuid"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Deserialize for chrono::DateTime<chrono::FixedOffset> {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        Self::try_from(Inst::deserialize(edn)?)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Deserialize for chrono::DateTime<chrono::Utc> {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        chrono::DateTime::<chrono::FixedOffset>::deserialize(edn).map(|date| date.to_utc())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Deserialize for time::OffsetDateTime {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        Self::try_from(Inst::deserialize(edn)?)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl Deserialize for uuid::Uuid {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        Uuid::deserialize(edn).map(Into::into)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl Deserialize for num_bigint::BigInt {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        match edn {
+            Edn::BigInt(n) => n.parse().ok(),
+            Edn::Int(i) => Some(Self::from(*i)),
+            Edn::UInt(u) => Some(Self::from(*u)),
+            _ => None,
+        }
+        .ok_or_else(|| build_deserialize_error(edn, "num_bigint::BigInt"))
+    }
+}
+
+/// Fails instead of rounding when the value does not fit in a `Decimal`.
+#[cfg(feature = "rust_decimal")]
+impl Deserialize for rust_decimal::Decimal {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        let exact = |s: &str| {
+            if s.contains(['e', 'E']) {
+                Self::from_scientific(s).ok()
+            } else {
+                Self::from_str_exact(s).ok()
+            }
+        };
+        match edn {
+            Edn::Decimal(n) | Edn::BigInt(n) => exact(n),
+            Edn::Int(i) => Some(Self::from(*i)),
+            Edn::UInt(u) => Some(Self::from(*u)),
+            Edn::Double(d) => exact(&d.to_string()),
+            _ => None,
+        }
+        .ok_or_else(|| build_deserialize_error(edn, "rust_decimal::Decimal"))
+    }
+}
+
 impl Deserialize for char {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
         edn.to_char()
@@ -193,19 +298,21 @@ where
 }
 
 #[cfg(feature = "std")]
-impl<T, H> Deserialize for HashMap<String, T, H>
+impl<K, T, H> Deserialize for HashMap<K, T, H>
 where
+    K: Deserialize + Eq + std::hash::Hash,
     T: Deserialize,
     H: std::hash::BuildHasher + std::default::Default,
 {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
         match edn {
-            Edn::Map(_) => edn
-                .map_iter()
+            Edn::Map(_) | Edn::OrderedMap(_) => edn
+                .map_entries()
                 .ok_or_else(|| Error::Iter(format!("Could not create iter from {edn:?}")))?
+                .into_iter()
                 .map(|(key, e)| {
                     Ok((
-                        key.clone(),
+                        Deserialize::deserialize(key)?,
                         Deserialize::deserialize(e).map_err(|_| {
                             Error::Deserialize(format!(
                                 "Cannot safely deserialize {:?} to {}",
@@ -220,18 +327,20 @@ where
     }
 }
 
-impl<T> Deserialize for BTreeMap<String, T>
+impl<K, T> Deserialize for BTreeMap<K, T>
 where
+    K: Deserialize + Ord,
     T: Deserialize,
 {
     fn deserialize(edn: &Edn) -> Result<Self, Error> {
         match edn {
-            Edn::Map(_) => edn
-                .map_iter()
+            Edn::Map(_) | Edn::OrderedMap(_) => edn
+                .map_entries()
                 .ok_or_else(|| Error::Iter(format!("Could not create iter from {edn:?}")))?
+                .into_iter()
                 .map(|(key, e)| {
                     Ok((
-                        key.clone(),
+                        Deserialize::deserialize(key)?,
                         Deserialize::deserialize(e).map_err(|_| {
                             Error::Deserialize(format!(
                                 "Cannot safely deserialize {:?} to {}",
@@ -385,8 +494,8 @@ pub fn from_str<T: Deserialize>(s: &str) -> Result<T, Error> {
 /// }
 ///
 /// let edn = Edn::Map(Map::new(map! {
-///     ":name".to_string() => Edn::Str("rose".to_string()),
-///     ":age".to_string() => Edn::UInt(66)
+///     Edn::Key(":name".to_string()) => Edn::Str("rose".to_string()),
+///     Edn::Key(":age".to_string()) => Edn::UInt(66)
 /// }));
 /// let person: Person = edn_rs::from_edn(&edn).unwrap();
 ///
@@ -402,8 +511,8 @@ pub fn from_str<T: Deserialize>(s: &str) -> Result<T, Error> {
 /// );
 ///
 /// let bad_edn = Edn::Map(Map::new(map! {
-///     ":name".to_string() => Edn::Str("rose".to_string()),
-///     ":age".to_string() => Edn::Str("some text".to_string())
+///     Edn::Key(":name".to_string()) => Edn::Str("rose".to_string()),
+///     Edn::Key(":age".to_string()) => Edn::Str("some text".to_string())
 /// }));
 /// let person: Result<Person, EdnError> = edn_rs::from_edn(&bad_edn);
 ///
@@ -0,0 +1,221 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::deserialize::parse;
+use crate::edn::{Edn, EdnRef, Error, Inst, Uuid};
+
+type Handler = dyn Fn(Edn) -> Result<Edn, String> + Send + Sync;
+type DefaultHandler = dyn Fn(&str, Edn) -> Result<Edn, String> + Send + Sync;
+
+pub static DEFAULT_OPTIONS: ReaderOptions = ReaderOptions::new();
+
+// Default of `ReaderOptions::max_form_len`, 16 MiB
+const DEFAULT_MAX_FORM_LEN: usize = 16 * 1024 * 1024;
+
+/// `ReaderOptions` configures how EDN is read, like `clojure.edn/read`'s opts map.
+///
+/// ```rust
+/// use edn_rs::{Edn, ReaderOptions, TagRegistry};
+///
+/// let options = ReaderOptions::new().tags(
+///     TagRegistry::new().register("myapp/money", |edn: Edn| match edn.to_vec() {
+///         Some(v) if v.len() == 2 => Ok(Edn::Str(v.join(" "))),
+///         _ => Err(format!("expected [amount currency], got {edn}")),
+///     }),
+/// );
+///
+/// assert_eq!(
+///     options.parse("{:price #myapp/money [10 \"EUR\"]}").unwrap()[":price"],
+///     Edn::Str("10 EUR".to_string())
+/// );
+/// assert!(options.parse("#myapp/money 10").is_err());
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ReaderOptions {
+    pub tags: TagRegistry,
+    /// Reads maps as `Edn::OrderedMap`, keeping their entries in source order
+    pub ordered_maps: bool,
+    /// Longest form in bytes that [`crate::EdnReader`] and [`crate::AsyncEdnReader`] keep in
+    /// memory, 16 MiB by default
+    pub max_form_len: usize,
+}
+
+impl Default for ReaderOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReaderOptions {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            tags: TagRegistry::new(),
+            ordered_maps: false,
+            max_form_len: DEFAULT_MAX_FORM_LEN,
+        }
+    }
+
+    /// Replaces the handlers of tagged elements.
+    #[must_use]
+    pub fn tags(mut self, tags: TagRegistry) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Reads maps as [`crate::OrderedMap`] instead of [`crate::Map`], so that writing them
+    /// back keeps the order of their keys in the source.
+    #[must_use]
+    pub const fn ordered_maps(mut self, ordered_maps: bool) -> Self {
+        self.ordered_maps = ordered_maps;
+        self
+    }
+
+    /// Sets the longest form a streaming reader keeps in memory. A longer form, like an
+    /// unterminated string, is reported with `ParseErrorKind::FormTooLong` as soon as it goes
+    /// past `max_form_len` bytes, and the reader skips the rest of it. Parsing a `&str` is not
+    /// limited.
+    #[must_use]
+    pub const fn max_form_len(mut self, max_form_len: usize) -> Self {
+        self.max_form_len = max_form_len;
+        self
+    }
+
+    /// Parses an EDN `&str` into an `Edn` using these options.
+    ///
+    /// # Errors
+    ///
+    /// Same as `Edn::from_str`, and `ParseErrorKind::InvalidTagged` when a tag handler fails.
+    pub fn parse(&self, edn: &str) -> Result<Edn, Error> {
+        self.parse_borrowed(edn).map(EdnRef::into_owned)
+    }
+
+    /// Same as [`ReaderOptions::parse`] producing an [`EdnRef`], see [`crate::parse_borrowed`].
+    /// Elements returned by tag handlers are owned.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ReaderOptions::parse`].
+    pub fn parse_borrowed<'a>(&self, edn: &'a str) -> Result<EdnRef<'a>, Error> {
+        parse::parse_with(edn, self)
+    }
+}
+
+/// `TagRegistry` holds the functions that read tagged elements, like Clojure's `*data-readers*`.
+///
+/// A handler receives the element following its tag and returns the `Edn` that replaces the
+/// whole tagged element, or an error message that becomes a
+/// `ParseErrorKind::InvalidTagged` pointing at the tag. Without a registered handler, the
+/// built-in `#inst` and `#uuid` elements are validated and kept tagged, see [`crate::Inst`] and
+/// [`crate::Uuid`]. Other tags go to the default handler, or are kept as `Edn::Tagged` when
+/// there is none.
+///
+/// ```rust
+/// use edn_rs::{Edn, EdnError, ParseErrorKind, ReaderOptions, TagRegistry};
+///
+/// let options = ReaderOptions::new().tags(
+///     TagRegistry::new()
+///         .register("double", |edn: Edn| {
+///             edn.to_uint().map(|n| Edn::UInt(n * 2)).ok_or("not a number")
+///         })
+///         .default_handler(|tag: &str, _| Err(format!("unknown tag #{tag}"))),
+/// );
+///
+/// assert_eq!(options.parse("[#double 21]").unwrap()[0], Edn::UInt(42));
+///
+/// let Err(EdnError::Parse(err)) = options.parse("[1 #other 2]") else { panic!() };
+/// assert_eq!(
+///     err.kind,
+///     ParseErrorKind::InvalidTagged {
+///         tag: "other".to_string(),
+///         message: "unknown tag #other".to_string(),
+///     }
+/// );
+/// assert_eq!(err.column, 4);
+/// ```
+#[derive(Default)]
+pub struct TagRegistry {
+    handlers: BTreeMap<String, Box<Handler>>,
+    default: Option<Box<DefaultHandler>>,
+}
+
+impl TagRegistry {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+            default: None,
+        }
+    }
+
+    /// Registers `handler` for elements tagged with `tag`, without the leading `#`,
+    /// replacing any previous handler of `tag`.
+    #[must_use]
+    pub fn register<F, E>(mut self, tag: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Edn) -> Result<Edn, E> + Send + Sync + 'static,
+        E: fmt::Display,
+    {
+        self.handlers.insert(
+            tag.into(),
+            Box::new(move |edn| handler(edn).map_err(|e| e.to_string())),
+        );
+        self
+    }
+
+    /// Sets the handler of tags without a registered handler, it also receives the tag.
+    #[must_use]
+    pub fn default_handler<F, E>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str, Edn) -> Result<Edn, E> + Send + Sync + 'static,
+        E: fmt::Display,
+    {
+        self.default = Some(Box::new(move |tag, edn| {
+            handler(tag, edn).map_err(|e| e.to_string())
+        }));
+        self
+    }
+
+    /// Whether `tag` has a handler, including the default and built-in ones.
+    #[must_use]
+    pub fn contains(&self, tag: &str) -> bool {
+        self.default.is_some() || matches!(tag, "inst" | "uuid") || self.handlers.contains_key(tag)
+    }
+
+    /// Reads `edn` with the handler of `tag`, keeps it tagged when there is none.
+    ///
+    /// # Errors
+    ///
+    /// The message of the handler rejecting `edn`.
+    pub fn read(&self, tag: &str, edn: Edn) -> Result<Edn, String> {
+        match (self.handlers.get(tag), tag, &self.default) {
+            (Some(handler), _, _) => handler(edn),
+            (None, "inst", _) => validate(tag, edn, Inst::parse),
+            (None, "uuid", _) => validate(tag, edn, Uuid::parse),
+            (None, _, Some(handler)) => handler(tag, edn),
+            (None, _, None) => Ok(Edn::Tagged(String::from(tag), Box::new(edn))),
+        }
+    }
+}
+
+// Built-in tags keep their element as is once it is known to be valid
+fn validate<T>(tag: &str, edn: Edn, parse: fn(&str) -> Result<T, String>) -> Result<Edn, String> {
+    match &edn {
+        Edn::Str(s) => parse(s)?,
+        _ => return Err(format!("expected a string, got {edn}")),
+    };
+    Ok(Edn::Tagged(String::from(tag), Box::new(edn)))
+}
+
+impl fmt::Debug for TagRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TagRegistry")
+            .field("tags", &self.handlers.keys())
+            .field("default", &self.default.is_some())
+            .finish()
+    }
+}
@@ -1,36 +1,114 @@
+use alloc::borrow::Cow;
 use alloc::boxed::Box;
-use alloc::collections::BTreeMap;
-#[cfg(feature = "sets")]
-use alloc::collections::BTreeSet;
-use alloc::string::{String, ToString};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
-use alloc::{format, vec};
-use core::iter;
 use core::primitive::str;
+use core::str::CharIndices;
 
-#[cfg(feature = "sets")]
-use crate::edn::Set;
-use crate::edn::{Edn, Error, List, Map, Vector};
+use crate::deserialize::options::{DEFAULT_OPTIONS, ReaderOptions};
+use crate::edn::{Edn, EdnRef, Error, Keyword, ParseError, ParseErrorKind, Symbol};
+
+pub const DELIMITERS: [char; 8] = [',', ']', '}', ')', ';', '(', '[', '{'];
+
+/// Chars of the EDN source together with their byte offsets.
+/// Keeping the source around lets tokens be sliced out of it instead of copied.
+#[derive(Clone)]
+struct SourceChars<'a, 'o> {
+    source: &'a str,
+    chars: CharIndices<'a>,
+    options: &'o ReaderOptions,
+}
+
+impl<'a, 'o> SourceChars<'a, 'o> {
+    fn new(source: &'a str, options: &'o ReaderOptions) -> Self {
+        Self {
+            source,
+            chars: source.char_indices(),
+            options,
+        }
+    }
+
+    /// Byte offset of the next char to be read.
+    fn offset(&self) -> usize {
+        self.chars.offset()
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        &self.source[start..end]
+    }
+
+    fn consume(&mut self, n: usize) {
+        for _ in 0..n {
+            self.chars.next();
+        }
+    }
+
+    fn error(&self, offset: usize, kind: ParseErrorKind) -> Error {
+        Error::Parse(ParseError::new(self.source, offset, kind))
+    }
+}
 
-const DELIMITERS: [char; 8] = [',', ']', '}', ')', ';', '(', '[', '{'];
+impl Iterator for SourceChars<'_, '_> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chars.next()
+    }
+}
 
 pub fn parse(edn: &str) -> Result<Edn, Error> {
-    let owned = String::from(edn);
-    let mut tokens = owned.chars().enumerate();
-    (parse_internal(tokens.next(), &mut tokens)?).map_or_else(|| Ok(Edn::Empty), Ok)
+    parse_borrowed(edn).map(EdnRef::into_owned)
+}
+
+/// `parse_borrowed` parses an EDN `&str` into an [`EdnRef`] that borrows its strings, keywords,
+/// symbols and tags from the input. Only strings containing escape sequences are allocated.
+///
+/// # Errors
+///
+/// Same as `Edn::from_str`, `EdnError::Parse` when the input is not valid EDN.
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use edn_rs::EdnRef;
+///
+/// let edn = edn_rs::parse_borrowed("{:name \"rose\" :bio \"likes\\nflowers\"}").unwrap();
+///
+/// if let EdnRef::Map(entries) = &edn {
+///     assert!(matches!(entries[0].1, EdnRef::Str(Cow::Borrowed("rose"))));
+///     assert!(matches!(&entries[1].1, EdnRef::Str(Cow::Owned(s)) if s == "likes\nflowers"));
+/// }
+/// ```
+pub fn parse_borrowed(edn: &str) -> Result<EdnRef<'_>, Error> {
+    parse_with(edn, &DEFAULT_OPTIONS)
+}
+
+pub fn parse_with<'a>(edn: &'a str, options: &ReaderOptions) -> Result<EdnRef<'a>, Error> {
+    let mut tokens = SourceChars::new(edn, options);
+    (parse_internal(tokens.next(), &mut tokens)?).map_or_else(|| Ok(EdnRef::Empty), Ok)
+}
+
+/// `edn` when it holds exactly one element, with nothing but whitespace after it.
+pub fn parse_single(edn: &str) -> Option<Edn> {
+    let mut chars = SourceChars::new(edn, &DEFAULT_OPTIONS);
+    let element = parse_internal(chars.next(), &mut chars).ok()??;
+    chars
+        .all(|(_, c)| c.is_whitespace() || c == ',')
+        .then(|| element.into_owned())
 }
 
-fn parse_consuming(
+fn parse_consuming<'a>(
     c: Option<(usize, char)>,
-    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
-) -> Result<Edn, Error> {
-    (parse_internal(c, chars)?).map_or_else(|| Ok(Edn::Empty), Ok)
+    chars: &mut SourceChars<'a, '_>,
+) -> Result<EdnRef<'a>, Error> {
+    (parse_internal(c, chars)?).map_or_else(|| Ok(EdnRef::Empty), Ok)
 }
 
-fn parse_internal(
+fn parse_internal<'a>(
     c: Option<(usize, char)>,
-    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
-) -> Result<Option<Edn>, Error> {
+    chars: &mut SourceChars<'a, '_>,
+) -> Result<Option<EdnRef<'a>>, Error> {
     Ok(match c {
         Some((_, '[')) => Some(read_vec(chars)?),
         Some((_, '(')) => Some(read_list(chars)?),
@@ -47,13 +125,13 @@ fn parse_internal(
     })
 }
 
-fn edn_element(
+fn edn_element<'a>(
     c: Option<(usize, char)>,
-    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
-) -> Result<Edn, Error> {
+    chars: &mut SourceChars<'a, '_>,
+) -> Result<EdnRef<'a>, Error> {
     match c {
         Some((_, '\"')) => read_str(chars),
-        Some((_, ':')) => Ok(read_key(chars)),
+        Some((_, ':')) => read_key(chars),
         Some((_, n)) if n.is_numeric() => Ok(read_number(n, chars)?),
         Some((_, n))
             if (n == '-' || n == '+')
@@ -66,128 +144,310 @@ fn edn_element(
             Ok(read_number(n, chars)?)
         }
         Some((_, '\\')) => Ok(read_char(chars)?),
-        Some((_, b)) if b == 't' || b == 'f' || b == 'n' => Ok(read_bool_or_nil(b, chars)?),
-        Some((_, a)) => Ok(read_symbol(a, chars)?),
-        None => Err(Error::ParseEdn("Edn could not be parsed".to_string())),
+        Some((_, b)) if b == 't' || b == 'f' || b == 'n' => read_bool_or_nil(b, chars),
+        Some((i, d)) if d == ']' || d == ')' || d == '}' => {
+            Err(chars.error(i, ParseErrorKind::UnexpectedDelimiter(d)))
+        }
+        Some((_, a)) => read_symbol(a, chars),
+        None => Err(chars.error(chars.offset(), ParseErrorKind::UnexpectedEof)),
     }
 }
 
-fn tagged_or_set_or_discard(
-    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
-) -> Result<Option<Edn>, Error> {
+fn tagged_or_set_or_discard<'a>(
+    chars: &mut SourceChars<'a, '_>,
+) -> Result<Option<EdnRef<'a>>, Error> {
     match chars.clone().next() {
         Some((_, '{')) => read_set(chars).map(Some),
         Some((_, '_')) => read_discard(chars),
+        Some((_, '#')) => read_symbolic_value(chars).map(Some),
+        Some((_, ':')) => read_namespaced_map(chars).map(Some),
         _ => read_tagged(chars).map(Some),
     }
 }
 
-fn read_key(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Edn {
-    let key_chars = chars
+fn read_key<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    // The leading ':' was already consumed and is part of the key
+    let start = chars.offset() - 1;
+    let c_len = chars
         .clone()
-        .take_while(|c| !c.1.is_whitespace() && !DELIMITERS.contains(&c.1));
-    let c_len = key_chars.clone().count();
-
-    let mut key = String::from(":");
-    let key_chars = chars.take(c_len).map(|c| c.1).collect::<String>();
-    key.push_str(&key_chars);
-    Edn::Key(key)
-}
-
-fn read_str(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
-    let result = chars.try_fold(
-        (false, String::new()),
-        |(last_was_escape, mut s), (_, c)| {
-            if last_was_escape {
-                // Supported escape characters, per https://github.com/edn-format/edn#strings
-                match c {
-                    't' => s.push('\t'),
-                    'r' => s.push('\r'),
-                    'n' => s.push('\n'),
-                    '\\' => s.push('\\'),
-                    '\"' => s.push('\"'),
-                    _ => {
-                        return Err(Err(Error::ParseEdn(format!(
-                            "Invalid escape sequence \\{c}"
-                        ))));
-                    }
-                }
+        .take_while(|c| !c.1.is_whitespace() && !DELIMITERS.contains(&c.1))
+        .count();
+    chars.consume(c_len);
+
+    let key = chars.slice(start, chars.offset());
+    if Keyword::parse(key).is_err() {
+        return Err(chars.error(start, ParseErrorKind::InvalidKeyword(String::from(key))));
+    }
+    Ok(EdnRef::Key(Cow::Borrowed(key)))
+}
 
-                Ok((false, s))
-            } else if c == '\"' {
-                // Unescaped quote means we're done
-                Err(Ok(s))
-            } else if c == '\\' {
-                Ok((true, s))
-            } else {
-                s.push(c);
-                Ok((false, s))
+fn read_str<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    let start = chars.offset();
+    if let Some(len) = chars.slice(start, chars.source.len()).find(['\"', '\\'])
+        && chars.source[start + len..].starts_with('\"')
+    {
+        // No escape sequences, so the string can be borrowed as is
+        let end = start + len;
+        chars.find(|&(i, _)| i == end);
+        return Ok(EdnRef::Str(Cow::Borrowed(chars.slice(start, end))));
+    }
+
+    let mut string = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\"' => return Ok(EdnRef::Str(Cow::Owned(string))),
+            '\\' => string.push(read_escape(i, start, chars)?),
+            c => string.push(c),
+        }
+    }
+    Err(chars.error(start - 1, ParseErrorKind::UnterminatedString))
+}
+
+/// Reads the escape sequence after the backslash at `backslash` in the string opened at
+/// `start`, per <https://github.com/edn-format/edn#strings> plus the unicode and octal escapes
+/// Clojure emits.
+fn read_escape(
+    backslash: usize,
+    start: usize,
+    chars: &mut SourceChars<'_, '_>,
+) -> Result<char, Error> {
+    let Some((_, c)) = chars.next() else {
+        return Err(chars.error(start - 1, ParseErrorKind::UnterminatedString));
+    };
+    match c {
+        't' => Ok('\t'),
+        'r' => Ok('\r'),
+        'n' => Ok('\n'),
+        'b' => Ok('\u{8}'),
+        'f' => Ok('\u{c}'),
+        '\\' => Ok('\\'),
+        '\"' => Ok('\"'),
+        'u' => {
+            let high = read_unicode_escape(backslash, chars)?;
+            if !(0xD800..0xDC00).contains(&high) {
+                return code_point(high, backslash, chars);
             }
-        },
-    );
+            // A high surrogate must be followed by an escaped low surrogate
+            let mut next = chars.clone();
+            if next.next().map(|c| c.1) == Some('\\') && next.next().map(|c| c.1) == Some('u') {
+                let low = read_unicode_escape(backslash + 6, &mut next)?;
+                if (0xDC00..0xE000).contains(&low) {
+                    *chars = next;
+                    return code_point(
+                        0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+                        backslash,
+                        chars,
+                    );
+                }
+            }
+            Err(chars.error(backslash, ParseErrorKind::InvalidCodePoint(high)))
+        }
+        '0'..='7' => {
+            let octal = read_octal(c, chars);
+            char::from_u32(octal)
+                .filter(|_| octal <= 0o377)
+                .ok_or_else(|| chars.error(backslash, ParseErrorKind::InvalidEscape(c)))
+        }
+        _ => Err(chars.error(backslash, ParseErrorKind::InvalidEscape(c))),
+    }
+}
 
-    match result {
-        // An Ok means we actually finished parsing *without* seeing the end of the string, so that's
-        // an error.
-        Ok(_) => Err(Error::ParseEdn("Unterminated string".to_string())),
-        Err(Err(e)) => Err(e),
-        Err(Ok(string)) => Ok(Edn::Str(string)),
+/// The 4 hex digits of a `\u` escape, whose backslash is at `backslash`.
+fn read_unicode_escape(backslash: usize, chars: &mut SourceChars<'_, '_>) -> Result<u32, Error> {
+    let digits: String = chars.clone().take(4).map(|c| c.1).collect();
+    if digits.len() == 4 && is_hex(&digits) {
+        chars.consume(4);
+        Ok(u32::from_str_radix(&digits, 16)?)
+    } else {
+        Err(chars.error(
+            backslash,
+            ParseErrorKind::InvalidUnicodeEscape(format!("\\u{digits}")),
+        ))
     }
 }
 
-fn read_symbol(a: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn code_point(code_point: u32, offset: usize, chars: &SourceChars<'_, '_>) -> Result<char, Error> {
+    char::from_u32(code_point)
+        .ok_or_else(|| chars.error(offset, ParseErrorKind::InvalidCodePoint(code_point)))
+}
+
+/// Octal number starting with `first`, followed by at most 2 more octal digits.
+fn read_octal(first: char, chars: &mut SourceChars<'_, '_>) -> u32 {
+    let mut octal = first.to_digit(8).unwrap_or_default();
+    for _ in 0..2 {
+        match chars.clone().next().and_then(|c| c.1.to_digit(8)) {
+            Some(digit) => {
+                octal = octal * 8 + digit;
+                chars.next();
+            }
+            None => break,
+        }
+    }
+    octal
+}
+
+fn read_symbol<'a>(a: char, chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    // `a` was already consumed and is the first char of the symbol
+    let start = chars.offset() - a.len_utf8();
     let c_len = chars
         .clone()
-        .enumerate()
-        .take_while(|&(_, c)| !c.1.is_whitespace() && !DELIMITERS.contains(&c.1))
+        .take_while(|c| !c.1.is_whitespace() && !DELIMITERS.contains(&c.1))
         .count();
-    let i = chars
+    chars.consume(c_len);
+
+    let symbol = chars.slice(start, chars.offset());
+    if Symbol::parse(symbol).is_err() {
+        return Err(chars.error(start, ParseErrorKind::InvalidSymbol(String::from(symbol))));
+    }
+    Ok(EdnRef::Symbol(Cow::Borrowed(symbol)))
+}
+
+fn read_tagged<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    let start = chars.offset();
+    // The '#' was already consumed
+    let c_len = chars
         .clone()
-        .next()
-        .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
-        .0;
+        .take_while(|c| !c.1.is_whitespace() && c.1 != ',')
+        .count();
+    chars.consume(c_len);
+    let tag = chars.slice(start, chars.offset());
+    // Whitespace or comma after the tag
+    chars.next();
+
+    let edn = parse_consuming(chars.next(), chars)?;
 
-    if a.is_whitespace() {
-        return Err(Error::ParseEdn(format!(
-            "\"{a}\" could not be parsed at char count {i}"
-        )));
+    // Without a handler the tag and element stay borrowed
+    if !chars.options.tags.contains(tag) {
+        return Ok(EdnRef::Tagged(Cow::Borrowed(tag), Box::new(edn)));
     }
+    match chars.options.tags.read(tag, edn.into_owned()) {
+        Ok(edn) => Ok(EdnRef::from(edn)),
+        Err(message) => Err(chars.error(
+            start - 1,
+            ParseErrorKind::InvalidTagged {
+                tag: String::from(tag),
+                message,
+            },
+        )),
+    }
+}
 
-    let mut symbol = String::from(a);
-    let symbol_chars = chars.take(c_len).map(|c| c.1).collect::<String>();
-    symbol.push_str(&symbol_chars);
-    Ok(Edn::Symbol(symbol))
+fn read_symbolic_value<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    // Points to the first '#' of "##"
+    let start = chars.offset() - 1;
+    chars.next();
+    let value_start = chars.offset();
+    let c_len = chars
+        .clone()
+        .take_while(|c| !c.1.is_whitespace() && !DELIMITERS.contains(&c.1))
+        .count();
+    chars.consume(c_len);
+
+    match chars.slice(value_start, chars.offset()) {
+        "Inf" => Ok(EdnRef::Double(f64::INFINITY.into())),
+        "-Inf" => Ok(EdnRef::Double(f64::NEG_INFINITY.into())),
+        "NaN" => Ok(EdnRef::Double(f64::NAN.into())),
+        value => Err(chars.error(
+            start,
+            ParseErrorKind::InvalidSymbolicValue(String::from(value)),
+        )),
+    }
 }
 
-fn read_tagged(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
-    let tag = chars
-        .take_while(|c| !c.1.is_whitespace() && c.1 != ',')
-        .map(|c| c.1)
-        .collect::<String>();
+/// Reads `#:ns{...}` and `#::{...}`, Clojure's syntax for a map whose keys share a namespace,
+/// as a map of qualified keys. `#:user{:id 1 :_/tag 2}` is read as `{:user/id 1 :tag 2}` and
+/// `#::{:id 1}` as `{::id 1}`.
+fn read_namespaced_map<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    // Points to the '#' of "#:"
+    let start = chars.offset() - 1;
+    chars.next();
+    let auto_resolved = chars.clone().next().is_some_and(|c| c.1 == ':');
+    if auto_resolved {
+        chars.next();
+    }
+    let namespace_start = chars.offset();
+    let c_len = chars
+        .clone()
+        .take_while(|c| !c.1.is_whitespace() && !DELIMITERS.contains(&c.1))
+        .count();
+    chars.consume(c_len);
+    let prefix_end = chars.offset();
+    let namespace = chars.slice(namespace_start, prefix_end);
+    let valid = if namespace.is_empty() {
+        auto_resolved
+    } else {
+        Symbol::parse(namespace).is_ok_and(|s| s.namespace().is_none() && s.name() != "/")
+    };
 
-    Ok(Edn::Tagged(
-        tag,
-        Box::new(parse_consuming(chars.next(), chars)?),
-    ))
+    // Like in Clojure, the map can be separated from its prefix
+    let c_len = chars
+        .clone()
+        .take_while(|c| c.1.is_whitespace() || c.1 == ',')
+        .count();
+    chars.consume(c_len);
+    if !valid || chars.next().map(|c| c.1) != Some('{') {
+        let prefix = String::from(chars.slice(start, prefix_end));
+        return Err(chars.error(start, ParseErrorKind::InvalidNamespacedMap(prefix)));
+    }
+
+    let qualify = |entries: Vec<(EdnRef<'a>, EdnRef<'a>)>| {
+        entries
+            .into_iter()
+            .map(|(key, value)| (qualify_key(key, namespace, auto_resolved), value))
+            .collect()
+    };
+    Ok(match read_map(chars)? {
+        EdnRef::Map(entries) => EdnRef::Map(qualify(entries)),
+        EdnRef::OrderedMap(entries) => EdnRef::OrderedMap(qualify(entries)),
+        edn => edn,
+    })
 }
 
-fn read_discard(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Option<Edn>, Error> {
+// Keywords and symbols without a namespace take the one of the map, `_` removes it. Symbols are
+// left as they are in `#::{}`, as their namespace is not known.
+pub fn qualify_key<'a>(key: EdnRef<'a>, namespace: &str, auto_resolved: bool) -> EdnRef<'a> {
+    let separator = if namespace.is_empty() { "" } else { "/" };
+    match &key {
+        EdnRef::Key(k) => match Keyword::parse(k) {
+            Ok(keyword) if keyword.is_auto_resolved() => key,
+            Ok(keyword) if keyword.namespace() == Some("_") => {
+                EdnRef::Key(Cow::Owned(format!(":{}", keyword.name())))
+            }
+            Ok(keyword) if keyword.namespace().is_none() && keyword.name() != "/" => {
+                let colons = if auto_resolved { "::" } else { ":" };
+                let name = keyword.name();
+                EdnRef::Key(Cow::Owned(format!("{colons}{namespace}{separator}{name}")))
+            }
+            _ => key,
+        },
+        EdnRef::Symbol(s) => match Symbol::parse(s) {
+            Ok(symbol) if symbol.namespace() == Some("_") => {
+                EdnRef::Symbol(Cow::Owned(String::from(symbol.name())))
+            }
+            Ok(symbol)
+                if !auto_resolved && symbol.namespace().is_none() && symbol.name() != "/" =>
+            {
+                EdnRef::Symbol(Cow::Owned(format!("{namespace}/{}", symbol.name())))
+            }
+            _ => key,
+        },
+        _ => key,
+    }
+}
+
+fn read_discard<'a>(chars: &mut SourceChars<'a, '_>) -> Result<Option<EdnRef<'a>>, Error> {
+    // Points to the '#' of "#_"
+    let start = chars.offset() - 1;
     let _discard_underscore = chars.next();
-    let i = chars
-        .clone()
-        .next()
-        .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
-        .0;
     match parse_consuming(chars.next(), chars) {
         Err(e) => Err(e),
-        Ok(Edn::Empty) => Err(Error::ParseEdn(format!(
-            "Discard sequence must have a following element at char count {i}"
-        ))),
+        Ok(EdnRef::Empty) => Err(chars.error(start, ParseErrorKind::DiscardWithoutElement)),
         _ => read_if_not_container_end(chars),
     }
 }
 
-fn read_number(n: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn read_number<'a>(n: char, chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    let start = chars.offset() - n.len_utf8();
     let c_len = chars
         .clone()
         .take_while(|(_, c)| !c.is_whitespace() && !DELIMITERS.contains(c))
@@ -222,7 +482,7 @@ fn read_number(n: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Re
                 Ok(r) => {
                     // from_str_radix panics if radix is not in the range from 2 to 36
                     if !(2..=36).contains(&r) {
-                        return Err(Error::ParseEdn(format!("Radix of {r} is out of bounds")));
+                        return Err(chars.error(start, ParseErrorKind::RadixOutOfBounds(r)));
                     }
 
                     if negative {
@@ -236,10 +496,8 @@ fn read_number(n: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Re
                     }
                     (number, r)
                 }
-                Err(e) => {
-                    return Err(Error::ParseEdn(format!(
-                        "{e} while trying to parse radix from {number}"
-                    )));
+                Err(_) => {
+                    return Err(chars.error(start, ParseErrorKind::InvalidRadix(number)));
                 }
             }
         } else {
@@ -247,31 +505,105 @@ fn read_number(n: char, chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Re
         }
     };
 
+    // `N` is a digit from radix 24 onwards and `M` from radix 23
+    if radix <= 16
+        && let Some(digits) = number.strip_suffix('N')
+    {
+        return big_int_digits(digits, radix)
+            .map(|n| EdnRef::BigInt(Cow::Owned(n)))
+            .ok_or_else(|| chars.error(start, ParseErrorKind::InvalidNumber { number, radix }));
+    }
+    if radix == 10
+        && let Some(decimal) = number.strip_suffix('M')
+    {
+        return if is_decimal(decimal) {
+            Ok(EdnRef::Decimal(Cow::Owned(String::from(decimal))))
+        } else {
+            Err(chars.error(start, ParseErrorKind::InvalidNumber { number, radix }))
+        };
+    }
+
     match number {
         n if (n.contains('E') || n.contains('e')) && n.parse::<f64>().is_ok() => {
-            Ok(Edn::Double(n.parse::<f64>()?.into()))
+            Ok(EdnRef::Double(n.parse::<f64>()?.into()))
         }
         n if u64::from_str_radix(&n, radix).is_ok() => {
-            Ok(Edn::UInt(u64::from_str_radix(&n, radix)?))
+            Ok(EdnRef::UInt(u64::from_str_radix(&n, radix)?))
         }
         n if i64::from_str_radix(&n, radix).is_ok() => {
-            Ok(Edn::Int(i64::from_str_radix(&n, radix)?))
+            Ok(EdnRef::Int(i64::from_str_radix(&n, radix)?))
         }
-        n if n.parse::<f64>().is_ok() => Ok(Edn::Double(n.parse::<f64>()?.into())),
+        n if big_int_digits(&n, radix).is_some() => Ok(EdnRef::BigInt(Cow::Owned(
+            big_int_digits(&n, radix).unwrap_or_default(),
+        ))),
+        n if n.parse::<f64>().is_ok() => Ok(EdnRef::Double(n.parse::<f64>()?.into())),
         n if n.contains('/') && n.split('/').all(|d| d.parse::<f64>().is_ok()) => {
-            Ok(Edn::Rational(n))
+            Ok(EdnRef::Rational(Cow::Owned(n)))
         }
         n if n.to_uppercase().chars().filter(|c| c == &'E').count() > 1 => {
-            let mut n = n.chars();
-            read_symbol(n.next().unwrap_or(' '), &mut n.enumerate())
+            Ok(EdnRef::Symbol(Cow::Owned(n)))
         }
-        _ => Err(Error::ParseEdn(format!(
-            "{number} could not be parsed with radix {radix}"
-        ))),
+        _ => Err(chars.error(start, ParseErrorKind::InvalidNumber { number, radix })),
     }
 }
 
-fn read_char(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+/// Decimal digits of the integer `digits` written in `radix`, without leading zeros.
+fn big_int_digits(digits: &str, radix: u32) -> Option<String> {
+    const BASE: u64 = 1_000_000_000;
+
+    let (negative, digits) = digits
+        .strip_prefix('-')
+        .map_or((false, digits), |digits| (true, digits));
+    if digits.is_empty() {
+        return None;
+    }
+    // Base 10^9 limbs, least significant first
+    let mut limbs: Vec<u64> = Vec::new();
+    for c in digits.chars() {
+        let mut carry = u64::from(c.to_digit(radix)?);
+        for limb in &mut limbs {
+            let value = *limb * u64::from(radix) + carry;
+            *limb = value % BASE;
+            carry = value / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+
+    let Some((last, rest)) = limbs.split_last() else {
+        return Some(String::from("0"));
+    };
+    let sign = if negative { "-" } else { "" };
+    let rest = rest.iter().rev().map(|limb| format!("{limb:09}"));
+    Some(
+        core::iter::once(format!("{sign}{last}"))
+            .chain(rest)
+            .collect(),
+    )
+}
+
+/// Whether `n` is a decimal with an optional fraction and exponent, `-1`, `1.`, `1.5e-3`.
+fn is_decimal(n: &str) -> bool {
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    let n = n.strip_prefix('-').unwrap_or(n);
+    let (mantissa, exponent) = match n.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (n, None),
+    };
+    let (int, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    !int.is_empty()
+        && digits(int)
+        && digits(fraction)
+        && exponent.is_none_or(|e| {
+            let e = e.strip_prefix(['+', '-']).unwrap_or(e);
+            !e.is_empty() && digits(e)
+        })
+}
+
+fn read_char<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    // Points to the leading '\\'
+    let start = chars.offset() - 1;
     let element = chars
         .clone()
         .enumerate()
@@ -287,39 +619,72 @@ fn read_char(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, E
     match element {
         _ if element.starts_with("newline") => {
             consume_chars(7);
-            Ok(Edn::Char('\n'))
+            Ok(EdnRef::Char('\n'))
         }
         _ if element.starts_with("return") => {
             consume_chars(6);
-            Ok(Edn::Char('\r'))
+            Ok(EdnRef::Char('\r'))
         }
         _ if element.starts_with("tab") => {
             consume_chars(3);
-            Ok(Edn::Char('\t'))
+            Ok(EdnRef::Char('\t'))
         }
         _ if element.starts_with("space") => {
             consume_chars(5);
-            Ok(Edn::Char(' '))
+            Ok(EdnRef::Char(' '))
+        }
+        _ if element.starts_with("formfeed") => {
+            consume_chars(8);
+            Ok(EdnRef::Char('\u{c}'))
+        }
+        _ if element.starts_with("backspace") => {
+            consume_chars(9);
+            Ok(EdnRef::Char('\u{8}'))
+        }
+        // `\u` and `\o` alone are the chars themselves
+        _ if element.starts_with('u') && !escape_digits(&element).is_empty() => {
+            let digits = escape_digits(&element);
+            if digits.len() != 4 || !is_hex(digits) {
+                return Err(chars.error(
+                    start,
+                    ParseErrorKind::InvalidUnicodeEscape(format!("\\u{digits}")),
+                ));
+            }
+            consume_chars(5);
+            code_point(u32::from_str_radix(digits, 16)?, start, chars).map(EdnRef::Char)
+        }
+        _ if element.starts_with('o') && !escape_digits(&element).is_empty() => {
+            let digits = escape_digits(&element);
+            match u32::from_str_radix(digits, 8) {
+                Ok(octal) if digits.len() <= 3 && octal <= 0o377 => {
+                    consume_chars(1 + digits.len());
+                    code_point(octal, start, chars).map(EdnRef::Char)
+                }
+                _ => Err(chars.error(start, ParseErrorKind::InvalidChar(format!("o{digits}")))),
+            }
         }
         c if !c.is_empty() => {
             consume_chars(1);
-            Ok(Edn::Char(c.chars().next().unwrap()))
+            Ok(EdnRef::Char(c.chars().next().unwrap()))
         }
-        _ => Err(Error::ParseEdn(format!(
-            "{element:?} could not be parsed as a symbol"
-        ))),
+        _ => Err(chars.error(start, ParseErrorKind::InvalidChar(element))),
     }
 }
 
-fn read_bool_or_nil(
-    c: char,
-    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
-) -> Result<Edn, Error> {
-    let i = chars
-        .clone()
-        .next()
-        .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
-        .0;
+/// Letters and digits following the first char of a char literal, `00e9` for `\u00e9`.
+fn escape_digits(element: &str) -> &str {
+    let rest = &element[1..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(rest.len());
+    &rest[..end]
+}
+
+fn is_hex(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn read_bool_or_nil<'a>(c: char, chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
     match c {
         't' if {
             let val = chars
@@ -330,11 +695,8 @@ fn read_bool_or_nil(
             val.eq("rue")
         } =>
         {
-            let mut string = String::new();
-            let t = chars.take(3).map(|c| c.1).collect::<String>();
-            string.push(c);
-            string.push_str(&t);
-            Ok(Edn::Bool(string.parse::<bool>()?))
+            chars.consume(3);
+            Ok(EdnRef::Bool(true))
         }
         'f' if {
             let val = chars
@@ -345,11 +707,8 @@ fn read_bool_or_nil(
             val.eq("alse")
         } =>
         {
-            let mut string = String::new();
-            let f = chars.take(4).map(|c| c.1).collect::<String>();
-            string.push(c);
-            string.push_str(&f);
-            Ok(Edn::Bool(string.parse::<bool>()?))
+            chars.consume(4);
+            Ok(EdnRef::Bool(false))
         }
         'n' if {
             let val = chars
@@ -360,114 +719,78 @@ fn read_bool_or_nil(
             val.eq("il")
         } =>
         {
-            let mut string = String::new();
-            let n = chars.take(2).map(|c| c.1).collect::<String>();
-            string.push(c);
-            string.push_str(&n);
-            match &string[..] {
-                "nil" => Ok(Edn::Nil),
-                _ => Err(Error::ParseEdn(format!(
-                    "{string} could not be parsed at char count {i}"
-                ))),
-            }
+            chars.consume(2);
+            Ok(EdnRef::Nil)
         }
         _ => read_symbol(c, chars),
     }
 }
 
-fn read_vec(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
-    let i = chars
-        .clone()
-        .next()
-        .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
-        .0;
-    let mut res: Vec<Edn> = vec![];
+fn read_vec<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    let start = chars.offset() - 1;
+    let mut res: Vec<EdnRef<'a>> = vec![];
     loop {
         match chars.next() {
-            Some((_, ']')) => return Ok(Edn::Vector(Vector::new(res))),
+            Some((_, ']')) => return Ok(EdnRef::Vector(res)),
             Some(c) => {
                 if let Some(e) = parse_internal(Some(c), chars)? {
                     res.push(e);
                 }
             }
-            err => {
-                return Err(Error::ParseEdn(format!(
-                    "{err:?} could not be parsed at char count {i}"
-                )));
-            }
+            None => return Err(chars.error(start, ParseErrorKind::Unclosed("["))),
         }
     }
 }
 
-fn read_list(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
-    let i = chars
-        .clone()
-        .next()
-        .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
-        .0;
-    let mut res: Vec<Edn> = vec![];
+fn read_list<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    let start = chars.offset() - 1;
+    let mut res: Vec<EdnRef<'a>> = vec![];
     loop {
         match chars.next() {
-            Some((_, ')')) => return Ok(Edn::List(List::new(res))),
+            Some((_, ')')) => return Ok(EdnRef::List(res)),
             Some(c) => {
                 if let Some(e) = parse_internal(Some(c), chars)? {
                     res.push(e);
                 }
             }
-            err => {
-                return Err(Error::ParseEdn(format!(
-                    "{err:?} could not be parsed at char count {i}"
-                )));
-            }
+            None => return Err(chars.error(start, ParseErrorKind::Unclosed("("))),
         }
     }
 }
 
 #[cfg(feature = "sets")]
-fn read_set(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
+fn read_set<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    // Points to the '#' of "#{"
+    let start = chars.offset() - 1;
     let _discard_brackets = chars.next();
-    let i = chars
-        .clone()
-        .next()
-        .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
-        .0;
-    let mut res: BTreeSet<Edn> = BTreeSet::new();
+    let mut res: Vec<EdnRef<'a>> = vec![];
     loop {
         match chars.next() {
-            Some((_, '}')) => return Ok(Edn::Set(Set::new(res))),
+            Some((_, '}')) => return Ok(EdnRef::Set(res)),
             Some(c) => {
                 if let Some(e) = parse_internal(Some(c), chars)? {
-                    res.insert(e);
+                    res.push(e);
                 }
             }
-            err => {
-                return Err(Error::ParseEdn(format!(
-                    "{err:?} could not be parsed at char count {i}"
-                )));
-            }
+            None => return Err(chars.error(start, ParseErrorKind::Unclosed("#{"))),
         }
     }
 }
 
 #[cfg(not(feature = "sets"))]
-fn read_set(_chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
-    Err(Error::ParseEdn(
-        "Could not parse set due to feature not being enabled".to_string(),
-    ))
+fn read_set<'a>(chars: &SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    Err(chars.error(chars.offset() - 1, ParseErrorKind::SetsDisabled))
 }
 
-fn read_map(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Error> {
-    let i = chars
-        .clone()
-        .next()
-        .ok_or_else(|| Error::ParseEdn("Could not identify symbol index".to_string()))?
-        .0;
-    let mut res: BTreeMap<String, Edn> = BTreeMap::new();
-    let mut key: Option<Edn> = None;
-    let mut val: Option<Edn> = None;
+fn read_map<'a>(chars: &mut SourceChars<'a, '_>) -> Result<EdnRef<'a>, Error> {
+    let start = chars.offset() - 1;
+    let mut res: Vec<(EdnRef<'a>, EdnRef<'a>)> = vec![];
+    let mut key: Option<EdnRef<'a>> = None;
+    let mut val: Option<EdnRef<'a>> = None;
     loop {
         match chars.next() {
-            Some((_, '}')) => return Ok(Edn::Map(Map::new(res))),
+            Some((_, '}')) if chars.options.ordered_maps => return Ok(EdnRef::OrderedMap(res)),
+            Some((_, '}')) => return Ok(EdnRef::Map(res)),
             Some(c) => {
                 if key.is_some() {
                     val = Some(parse_consuming(Some(c), chars)?);
@@ -475,24 +798,20 @@ fn read_map(chars: &mut iter::Enumerate<core::str::Chars<'_>>) -> Result<Edn, Er
                     key = parse_internal(Some(c), chars)?;
                 }
             }
-            err => {
-                return Err(Error::ParseEdn(format!(
-                    "{err:?} could not be parsed at char count {i}"
-                )));
-            }
+            None => return Err(chars.error(start, ParseErrorKind::Unclosed("{"))),
         }
 
         if key.is_some() && val.is_some() {
-            res.insert(key.unwrap().to_string(), val.unwrap());
+            res.push((key.unwrap(), val.unwrap()));
             key = None;
             val = None;
         }
     }
 }
 
-fn read_if_not_container_end(
-    chars: &mut iter::Enumerate<core::str::Chars<'_>>,
-) -> Result<Option<Edn>, Error> {
+fn read_if_not_container_end<'a>(
+    chars: &mut SourceChars<'a, '_>,
+) -> Result<Option<EdnRef<'a>>, Error> {
     Ok(match chars.clone().next() {
         Some(c) if c.1 == ']' || c.1 == ')' || c.1 == '}' => None,
         Some(_) => parse_internal(chars.next(), chars)?,
@@ -0,0 +1,93 @@
+use std::io::{self, BufRead, BufReader, Read};
+
+use crate::deserialize::framer::Framer;
+use crate::deserialize::options::ReaderOptions;
+use crate::edn::{Edn, Error};
+
+/// `EdnReader` iterates over the top-level forms of an EDN byte stream, one `Edn` at a time.
+///
+/// Only the form currently being read is kept in memory, so streams larger than memory, like
+/// logs with one form per line or forms concatenated on a single line, can be processed
+/// incrementally. Whitespace, commas, comments and `#_` discarded forms between elements are
+/// skipped. Requires feature `std`.
+///
+/// A form that fails to parse yields an `Err` with its position in the whole stream and
+/// iteration continues with the next form. So does a form longer than
+/// [`ReaderOptions::max_form_len`], like an unterminated string, without being kept whole. An I/O error yields `Err(EdnError::Io(..))` and
+/// ends the iteration.
+///
+/// ```rust
+/// use edn_rs::{Edn, EdnReader};
+///
+/// let log = "{:tx 1 :op :add}\n{:tx 2 :op :retract} ; undo\n#_ {:tx 3} :eof";
+/// let forms = EdnReader::new(log.as_bytes())
+///     .collect::<Result<Vec<Edn>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(forms.len(), 3);
+/// assert_eq!(forms[1][":tx"], Edn::UInt(2));
+/// assert_eq!(forms[2], Edn::Key(":eof".to_string()));
+/// ```
+#[derive(Debug)]
+pub struct EdnReader<R> {
+    reader: BufReader<R>,
+    framer: Framer,
+    options: ReaderOptions,
+    done: bool,
+}
+
+impl<R: Read> EdnReader<R> {
+    /// Wraps `reader` in a [`BufReader`], there is no need to buffer it beforehand.
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(8 * 1024, reader)
+    }
+
+    /// Same as [`EdnReader::new`] with a read buffer of `capacity` bytes.
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        Self {
+            reader: BufReader::with_capacity(capacity, reader),
+            framer: Framer::new(),
+            options: ReaderOptions::new(),
+            done: false,
+        }
+    }
+
+    /// Reads forms with `options`, like tag handlers, see [`ReaderOptions`].
+    #[must_use]
+    pub fn options(mut self, options: ReaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Unwraps this `EdnReader`, returning the underlying reader. Bytes buffered but not yet
+    /// read into a form are lost.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R: Read> Iterator for EdnReader<R> {
+    type Item = Result<Edn, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let (used, ready) = match self.reader.fill_buf() {
+                Ok([]) => {
+                    self.done = true;
+                    return self.framer.finish(&self.options);
+                }
+                Ok(buf) => self.framer.feed(buf, self.options.max_form_len),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            };
+            self.reader.consume(used);
+            if ready && let Some(form) = self.framer.take(&self.options) {
+                return Some(form);
+            }
+        }
+        None
+    }
+}
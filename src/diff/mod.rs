@@ -0,0 +1,458 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::deserialize::Deserialize;
+use crate::edn::{Edn, Error, List, OrderedMap, Vector};
+use crate::serialize::Serialize;
+
+/// A change between two `Edn` values at `path`: the keys of maps, the indexes of vectors and
+/// lists as `Edn::UInt`, and the elements of sets, as in [`crate::query::Match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// `value` is only in the new value
+    Add { path: Vec<Edn>, value: Edn },
+    /// `value` is only in the old value
+    Remove { path: Vec<Edn>, value: Edn },
+    /// The element at `path` went from `old` to `new`
+    Replace { path: Vec<Edn>, old: Edn, new: Edn },
+}
+
+/// The changes turning one `Edn` into another, see [`diff`] and [`patch`].
+///
+/// Its EDN form is a vector of maps in the spirit of JSON Patch, which reads well in audit
+/// logs and is read back with `edn_rs::from_str`:
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, Serialize, diff};
+///
+/// let old = Edn::from_str("{:db {:port 5432} :debug true}").unwrap();
+/// let new = Edn::from_str("{:db {:port 5433} :replicas [:a]}").unwrap();
+/// let changes = diff(&old, &new);
+///
+/// assert_eq!(
+///     changes.serialize(),
+///     "[{:op :replace, :path [:db :port], :old 5432, :new 5433} \
+///       {:op :remove, :path [:debug], :value true} \
+///       {:op :add, :path [:replicas], :value [:a]}]"
+/// );
+/// assert_eq!(edn_rs::from_str(&changes.serialize()), Ok(changes));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Diff(pub Vec<Change>);
+
+/// The changes from `old` to `new`.
+///
+/// Maps and sets are compared by key and element, and vectors and lists index by index, so
+/// an element inserted in the middle of a vector replaces the ones after it. Values of
+/// different types, tagged elements and a `Map` against an `OrderedMap` included, are
+/// replaced whole.
+#[must_use]
+pub fn diff(old: &Edn, new: &Edn) -> Diff {
+    let mut changes = Vec::new();
+    diff_into(&mut Vec::new(), old, new, &mut changes);
+    Diff(changes)
+}
+
+/// Applies the changes of `diff` to `edn`, in order.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, diff, patch};
+///
+/// let old = Edn::from_str("{:hosts [\"a\" \"b\" \"c\"] :port 80}").unwrap();
+/// let new = Edn::from_str("{:hosts [\"a\" \"d\"] :port 443}").unwrap();
+///
+/// let mut synced = old.clone();
+/// patch(&mut synced, &diff(&old, &new)).unwrap();
+/// assert_eq!(synced, new);
+/// ```
+///
+/// # Errors
+///
+/// `EdnError::Edit` when a change does not apply, because the element it removes or replaces
+/// is not there or has another value, or the element it adds is already there. `edn` is then
+/// left unchanged.
+pub fn patch(edn: &mut Edn, diff: &Diff) -> Result<(), Error> {
+    let mut patched = edn.clone();
+    for change in &diff.0 {
+        change.apply(&mut patched)?;
+    }
+    *edn = patched;
+    Ok(())
+}
+
+fn diff_into(path: &mut Vec<Edn>, old: &Edn, new: &Edn, changes: &mut Vec<Change>) {
+    let mut child = |key: Edn, old: &Edn, new: &Edn| {
+        path.push(key);
+        diff_into(path, old, new, changes);
+        path.pop();
+    };
+    match (old, new) {
+        _ if old == new => {}
+        (Edn::Map(_), Edn::Map(_)) | (Edn::OrderedMap(_), Edn::OrderedMap(_)) => {
+            let old_entries = old.map_entries().unwrap_or_default();
+            let mut removed = Vec::new();
+            for &(key, value) in &old_entries {
+                match get(new, key) {
+                    Some(new_value) => child(key.clone(), value, new_value),
+                    None => removed.push((key, value)),
+                }
+            }
+            let added = new
+                .map_entries()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(key, _)| get(old, key).is_none());
+            changes.extend(removed.into_iter().map(|(key, value)| Change::Remove {
+                path: child_path(path, key),
+                value: value.clone(),
+            }));
+            changes.extend(added.map(|(key, value)| Change::Add {
+                path: child_path(path, key),
+                value: value.clone(),
+            }));
+        }
+        (Edn::Vector(Vector(old_seq)), Edn::Vector(Vector(new_seq)))
+        | (Edn::List(List(old_seq)), Edn::List(List(new_seq))) => {
+            for (i, (old, new)) in old_seq.iter().zip(new_seq).enumerate() {
+                child(Edn::UInt(i as u64), old, new);
+            }
+            changes.extend(
+                (new_seq.len()..old_seq.len())
+                    .rev()
+                    .map(|i| Change::Remove {
+                        path: child_path(path, &Edn::UInt(i as u64)),
+                        value: old_seq[i].clone(),
+                    }),
+            );
+            changes.extend((old_seq.len()..new_seq.len()).map(|i| Change::Add {
+                path: child_path(path, &Edn::UInt(i as u64)),
+                value: new_seq[i].clone(),
+            }));
+        }
+        #[cfg(feature = "sets")]
+        (Edn::Set(old_set), Edn::Set(new_set)) => {
+            changes.extend(old_set.0.difference(&new_set.0).map(|e| Change::Remove {
+                path: child_path(path, e),
+                value: e.clone(),
+            }));
+            changes.extend(new_set.0.difference(&old_set.0).map(|e| Change::Add {
+                path: child_path(path, e),
+                value: e.clone(),
+            }));
+        }
+        _ => changes.push(Change::Replace {
+            path: path.clone(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+fn get<'a>(map: &'a Edn, key: &Edn) -> Option<&'a Edn> {
+    match map {
+        Edn::Map(map) => map.0.get(key),
+        Edn::OrderedMap(map) => map.get(key),
+        _ => None,
+    }
+}
+
+fn child_path(path: &[Edn], key: &Edn) -> Vec<Edn> {
+    let mut path = path.to_vec();
+    path.push(key.clone());
+    path
+}
+
+fn index(key: &Edn) -> Option<usize> {
+    match *key {
+        Edn::UInt(i) => usize::try_from(i).ok(),
+        Edn::Int(i) => usize::try_from(i).ok(),
+        _ => None,
+    }
+}
+
+fn show(path: &[Edn]) -> String {
+    Vector::new(path.to_vec()).to_string()
+}
+
+fn child_mut<'a>(edn: &'a mut Edn, key: &Edn) -> Option<&'a mut Edn> {
+    match edn {
+        Edn::Map(map) => map.0.get_mut(key),
+        Edn::OrderedMap(map) => map.get_mut(key),
+        Edn::Vector(Vector(seq)) | Edn::List(List(seq)) => seq.get_mut(index(key)?),
+        _ => None,
+    }
+}
+
+impl Change {
+    /// The path of the changed element.
+    #[must_use]
+    pub fn path(&self) -> &[Edn] {
+        match self {
+            Self::Add { path, .. } | Self::Remove { path, .. } | Self::Replace { path, .. } => path,
+        }
+    }
+
+    fn apply(&self, edn: &mut Edn) -> Result<(), Error> {
+        let path = self.path();
+        let missing = || Error::Edit(format!("no element at {}", show(path)));
+        let Some((key, parent_path)) = path.split_last() else {
+            return match self {
+                Self::Replace { old, new, .. } if edn == old => {
+                    *edn = new.clone();
+                    Ok(())
+                }
+                Self::Replace { old, .. } => Err(conflict(path, edn, old)),
+                _ => Err(Error::Edit("cannot add or remove the root".to_string())),
+            };
+        };
+        let parent = parent_path
+            .iter()
+            .try_fold(edn, |edn, key| child_mut(edn, key))
+            .ok_or_else(|| Error::Edit(format!("no element at {}", show(parent_path))))?;
+
+        match self {
+            Self::Replace { old, new, .. } => {
+                let current = child_mut(parent, key).ok_or_else(missing)?;
+                if current != old {
+                    return Err(conflict(path, current, old));
+                }
+                *current = new.clone();
+            }
+            Self::Remove { value, .. } => {
+                let removed = match parent {
+                    Edn::Map(map) => map.0.remove(key),
+                    Edn::OrderedMap(map) => map.remove(key),
+                    Edn::Vector(Vector(seq)) | Edn::List(List(seq)) => {
+                        index(key).filter(|&i| i < seq.len()).map(|i| seq.remove(i))
+                    }
+                    #[cfg(feature = "sets")]
+                    Edn::Set(set) => set.0.take(key),
+                    _ => None,
+                }
+                .ok_or_else(missing)?;
+                if removed != *value {
+                    let err = conflict(path, &removed, value);
+                    return Err(err);
+                }
+            }
+            Self::Add { value, .. } => {
+                let exists = || Error::Edit(format!("{} already exists", show(path)));
+                match parent {
+                    Edn::Map(map) if map.0.contains_key(key) => return Err(exists()),
+                    Edn::Map(map) => {
+                        map.0.insert(key.clone(), value.clone());
+                    }
+                    Edn::OrderedMap(map) if map.get(key).is_some() => return Err(exists()),
+                    Edn::OrderedMap(map) => {
+                        map.insert(key.clone(), value.clone());
+                    }
+                    Edn::Vector(Vector(seq)) | Edn::List(List(seq)) => {
+                        let i = index(key).filter(|&i| i <= seq.len());
+                        let i = i.ok_or_else(|| {
+                            Error::Edit(format!(
+                                "index {key} is out of bounds of {} elements",
+                                seq.len()
+                            ))
+                        })?;
+                        seq.insert(i, value.clone());
+                    }
+                    #[cfg(feature = "sets")]
+                    Edn::Set(set) => {
+                        if !set.0.insert(value.clone()) {
+                            return Err(exists());
+                        }
+                    }
+                    _ => {
+                        return Err(Error::Edit(format!(
+                            "cannot add to {}, a scalar",
+                            show(parent_path)
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The EDN form of the change, `{:op :add, :path [...], :value ...}`,
+    /// `{:op :remove, :path [...], :value ...}` or `{:op :replace, :path [...], :old ..., :new ...}`.
+    #[must_use]
+    pub fn to_edn(&self) -> Edn {
+        let key = |k: &str| Edn::Key(k.to_string());
+        let (op, values) = match self {
+            Self::Add { value, .. } => (":add", vec![(key(":value"), value.clone())]),
+            Self::Remove { value, .. } => (":remove", vec![(key(":value"), value.clone())]),
+            Self::Replace { old, new, .. } => (
+                ":replace",
+                vec![(key(":old"), old.clone()), (key(":new"), new.clone())],
+            ),
+        };
+        let mut entries = vec![
+            (key(":op"), key(op)),
+            (key(":path"), Edn::Vector(Vector::new(self.path().to_vec()))),
+        ];
+        entries.extend(values);
+        Edn::OrderedMap(OrderedMap::new(entries))
+    }
+}
+
+fn conflict(path: &[Edn], found: &Edn, expected: &Edn) -> Error {
+    Error::Edit(format!("{} is {found}, not {expected}", show(path)))
+}
+
+impl Diff {
+    /// Whether there is no change.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The EDN form of the changes, a vector of [`Change::to_edn`].
+    #[must_use]
+    pub fn to_edn(&self) -> Edn {
+        Edn::Vector(Vector::new(self.0.iter().map(Change::to_edn).collect()))
+    }
+}
+
+impl Serialize for Diff {
+    fn serialize(&self) -> String {
+        self.to_edn().to_string()
+    }
+}
+
+impl Deserialize for Change {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        let invalid = || Error::Deserialize(format!("couldn't convert `{edn}` into `Change`"));
+        let field = |k: &str| edn.get(k).cloned().ok_or_else(invalid);
+        let path = match edn.get(":path") {
+            Some(path @ (Edn::Vector(_) | Edn::List(_))) => {
+                path.iter_some().into_iter().flatten().cloned().collect()
+            }
+            _ => return Err(invalid()),
+        };
+        let Some(Edn::Key(op)) = edn.get(":op") else {
+            return Err(invalid());
+        };
+        match op.as_str() {
+            ":add" => Ok(Self::Add {
+                path,
+                value: field(":value")?,
+            }),
+            ":remove" => Ok(Self::Remove {
+                path,
+                value: field(":value")?,
+            }),
+            ":replace" => Ok(Self::Replace {
+                path,
+                old: field(":old")?,
+                new: field(":new")?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl Deserialize for Diff {
+    fn deserialize(edn: &Edn) -> Result<Self, Error> {
+        edn.iter_some()
+            .ok_or_else(|| Error::Deserialize(format!("couldn't convert `{edn}` into `Diff`")))?
+            .map(Change::deserialize)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn diffs_sequences_by_index() {
+        assert_eq!(
+            diff(
+                &Edn::from_str("[1 2 3]").unwrap(),
+                &Edn::from_str("[1 4]").unwrap()
+            )
+            .0,
+            [
+                Change::Replace {
+                    path: vec![Edn::UInt(1)],
+                    old: Edn::UInt(2),
+                    new: Edn::UInt(4)
+                },
+                Change::Remove {
+                    path: vec![Edn::UInt(2)],
+                    value: Edn::UInt(3)
+                },
+            ]
+        );
+        assert_eq!(
+            diff(
+                &Edn::from_str("(1)").unwrap(),
+                &Edn::from_str("(1 2 3)").unwrap()
+            )
+            .0,
+            [
+                Change::Add {
+                    path: vec![Edn::UInt(1)],
+                    value: Edn::UInt(2)
+                },
+                Change::Add {
+                    path: vec![Edn::UInt(2)],
+                    value: Edn::UInt(3)
+                },
+            ]
+        );
+        assert_eq!(
+            diff(
+                &Edn::from_str("[1]").unwrap(),
+                &Edn::from_str("(1)").unwrap()
+            )
+            .0,
+            [Change::Replace {
+                path: vec![],
+                old: Edn::from_str("[1]").unwrap(),
+                new: Edn::from_str("(1)").unwrap()
+            }]
+        );
+    }
+
+    #[test]
+    fn patch_checks_the_old_values() {
+        let mut config = Edn::from_str("{:a 1 :b [1 2]}").unwrap();
+        let changes = diff(
+            &Edn::from_str("{:a 2}").unwrap(),
+            &Edn::from_str("{:a 3}").unwrap(),
+        );
+
+        assert_eq!(
+            patch(&mut config, &changes),
+            Err(Error::Edit("[:a] is 1, not 2".to_string()))
+        );
+        assert_eq!(
+            patch(
+                &mut config,
+                &Diff(vec![
+                    Change::Remove {
+                        path: vec![Edn::Key(":a".to_string())],
+                        value: Edn::UInt(1)
+                    },
+                    Change::Add {
+                        path: vec![Edn::Key(":b".to_string()), Edn::UInt(5)],
+                        value: Edn::Nil
+                    },
+                ])
+            ),
+            Err(Error::Edit(
+                "index 5 is out of bounds of 2 elements".to_string()
+            ))
+        );
+        assert_eq!(config, Edn::from_str("{:a 1 :b [1 2]}").unwrap());
+    }
+}
@@ -0,0 +1,187 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+#[cfg(feature = "sets")]
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+#[cfg(feature = "sets")]
+use crate::edn::Set;
+use crate::edn::{Double, Edn, List, Map, Vector};
+
+/// `EdnRef` is the borrowed counterpart of [`Edn`], produced by [`crate::parse_borrowed`].
+///
+/// Strings, keywords, symbols and tags point straight into the parsed input and are only
+/// allocated when they contain escape sequences. Collections keep their elements in source
+/// order, so two maps with the same entries in a different order are not equal.
+/// Use `into_owned` to get the equivalent `Edn`.
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, EdnRef};
+///
+/// let source = "[:name \"rose\"]";
+/// let edn = edn_rs::parse_borrowed(source).unwrap();
+///
+/// assert_eq!(
+///     edn,
+///     EdnRef::Vector(vec![
+///         EdnRef::Key(Cow::Borrowed(":name")),
+///         EdnRef::Str(Cow::Borrowed("rose")),
+///     ])
+/// );
+/// assert_eq!(edn.into_owned(), Edn::from_str(source).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum EdnRef<'a> {
+    Tagged(Cow<'a, str>, Box<Self>),
+    Vector(Vec<Self>),
+    #[cfg(feature = "sets")]
+    Set(Vec<Self>),
+    Map(Vec<(Self, Self)>),
+    /// Map read with `ReaderOptions::ordered_maps`, owned as an `Edn::OrderedMap`
+    OrderedMap(Vec<(Self, Self)>),
+    List(Vec<Self>),
+    Key(Cow<'a, str>),
+    Symbol(Cow<'a, str>),
+    Str(Cow<'a, str>),
+    Int(i64),
+    UInt(u64),
+    Double(Double),
+    Rational(Cow<'a, str>),
+    BigInt(Cow<'a, str>),
+    Decimal(Cow<'a, str>),
+    Char(char),
+    Bool(bool),
+    Nil,
+    Empty,
+}
+
+impl EdnRef<'_> {
+    /// `into_owned` converts an `EdnRef` into an owned `Edn`, copying any borrowed strings.
+    #[must_use]
+    pub fn into_owned(self) -> Edn {
+        match self {
+            Self::Tagged(tag, edn) => Edn::Tagged(tag.into_owned(), Box::new(edn.into_owned())),
+            Self::Vector(v) => {
+                Edn::Vector(Vector::new(v.into_iter().map(EdnRef::into_owned).collect()))
+            }
+            #[cfg(feature = "sets")]
+            Self::Set(s) => Edn::Set(Set::new(
+                s.into_iter()
+                    .map(EdnRef::into_owned)
+                    .collect::<BTreeSet<Edn>>(),
+            )),
+            Self::Map(m) => Edn::Map(Map::new(
+                m.into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect::<BTreeMap<_, _>>(),
+            )),
+            Self::OrderedMap(m) => Edn::OrderedMap(
+                m.into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect(),
+            ),
+            Self::List(l) => Edn::List(List::new(l.into_iter().map(EdnRef::into_owned).collect())),
+            Self::Key(k) => Edn::Key(k.into_owned()),
+            Self::Symbol(s) => Edn::Symbol(s.into_owned()),
+            Self::Str(s) => Edn::Str(s.into_owned()),
+            Self::Int(i) => Edn::Int(i),
+            Self::UInt(u) => Edn::UInt(u),
+            Self::Double(d) => Edn::Double(d),
+            Self::Rational(r) => Edn::Rational(r.into_owned()),
+            Self::BigInt(n) => Edn::BigInt(n.into_owned()),
+            Self::Decimal(d) => Edn::Decimal(d.into_owned()),
+            Self::Char(c) => Edn::Char(c),
+            Self::Bool(b) => Edn::Bool(b),
+            Self::Nil => Edn::Nil,
+            Self::Empty => Edn::Empty,
+        }
+    }
+}
+
+impl From<EdnRef<'_>> for Edn {
+    fn from(edn: EdnRef<'_>) -> Self {
+        edn.into_owned()
+    }
+}
+
+impl From<Edn> for EdnRef<'_> {
+    fn from(edn: Edn) -> Self {
+        match edn {
+            Edn::Tagged(tag, edn) => Self::Tagged(Cow::Owned(tag), Box::new(Self::from(*edn))),
+            Edn::Vector(v) => Self::Vector(v.to_vec().into_iter().map(Self::from).collect()),
+            #[cfg(feature = "sets")]
+            Edn::Set(s) => Self::Set(s.to_set().into_iter().map(Self::from).collect()),
+            Edn::Map(m) => Self::Map(
+                m.to_map()
+                    .into_iter()
+                    .map(|(k, v)| (Self::from(k), Self::from(v)))
+                    .collect(),
+            ),
+            Edn::OrderedMap(m) => Self::OrderedMap(
+                m.to_vec()
+                    .into_iter()
+                    .map(|(k, v)| (Self::from(k), Self::from(v)))
+                    .collect(),
+            ),
+            Edn::List(l) => Self::List(l.to_vec().into_iter().map(Self::from).collect()),
+            Edn::Key(k) => Self::Key(Cow::Owned(k)),
+            Edn::Symbol(s) => Self::Symbol(Cow::Owned(s)),
+            Edn::Str(s) => Self::Str(Cow::Owned(s)),
+            Edn::Int(i) => Self::Int(i),
+            Edn::UInt(u) => Self::UInt(u),
+            Edn::Double(d) => Self::Double(d),
+            Edn::Rational(r) => Self::Rational(Cow::Owned(r)),
+            Edn::BigInt(n) => Self::BigInt(Cow::Owned(n)),
+            Edn::Decimal(d) => Self::Decimal(Cow::Owned(d)),
+            Edn::Char(c) => Self::Char(c),
+            Edn::Bool(b) => Self::Bool(b),
+            Edn::Nil => Self::Nil,
+            Edn::Empty => Self::Empty,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::Cow;
+    use alloc::string::String;
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn into_owned_map_keeps_keys() {
+        let edn = EdnRef::Map(vec![
+            (EdnRef::Key(Cow::Borrowed(":a")), EdnRef::UInt(1)),
+            (EdnRef::Str(Cow::Borrowed("b")), EdnRef::Nil),
+        ]);
+
+        assert_eq!(
+            edn.into_owned(),
+            Edn::Map(Map::new(map! {
+                Edn::Key(String::from(":a")) => Edn::UInt(1),
+                Edn::Str(String::from("b")) => Edn::Nil
+            }))
+        );
+    }
+
+    #[test]
+    fn into_owned_tagged() {
+        let edn = EdnRef::Tagged(
+            Cow::Borrowed("inst"),
+            Box::new(EdnRef::Str(Cow::Borrowed("2020-07-16T21:53:14.628-00:00"))),
+        );
+
+        assert_eq!(
+            Edn::from(edn),
+            Edn::Tagged(
+                String::from("inst"),
+                Box::new(Edn::Str(String::from("2020-07-16T21:53:14.628-00:00")))
+            )
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::fmt;
+use alloc::string::{String, ToString};
+
+/// `ParseError` describes where and why parsing an EDN source failed.
+///
+/// `line` and `column` are 1-based, `column` counts chars, while `offset` is the byte offset
+/// into the source. `snippet` is the source line containing the error.
+/// `Display` renders the message followed by the line with a caret under the bad token:
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, EdnError, ParseErrorKind};
+///
+/// let Err(EdnError::Parse(err)) = Edn::from_str("{:a 1\n :b \"c\\q\"}") else {
+///     panic!()
+/// };
+///
+/// assert_eq!(err.kind, ParseErrorKind::InvalidEscape('q'));
+/// assert_eq!((err.line, err.column, err.offset), (2, 7, 12));
+/// assert_eq!(
+///     err.to_string(),
+///     "Invalid escape sequence \\q at line 2, column 7\n  |\n2 |  :b \"c\\q\"}\n  |       ^"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+    pub snippet: String,
+}
+
+/// Reason for a [`ParseError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The source ended where an element was expected
+    UnexpectedEof,
+    /// A closing delimiter without a matching opening one
+    UnexpectedDelimiter(char),
+    /// A collection that is never closed, holds its opening delimiter
+    Unclosed(&'static str),
+    UnterminatedString,
+    InvalidEscape(char),
+    /// A `\u` escape not followed by 4 hex digits, holds the escape as written
+    InvalidUnicodeEscape(String),
+    /// A `\u` escape of a lone surrogate, or of a char out of range
+    InvalidCodePoint(u32),
+    InvalidChar(String),
+    /// A keyword that does not follow the grammar of [`crate::Keyword`]
+    InvalidKeyword(String),
+    /// A symbol that does not follow the grammar of [`crate::Symbol`]
+    InvalidSymbol(String),
+    InvalidNumber {
+        number: String,
+        radix: u32,
+    },
+    InvalidRadix(String),
+    RadixOutOfBounds(u32),
+    /// `##` followed by something else than `Inf`, `-Inf` or `NaN`
+    InvalidSymbolicValue(String),
+    /// `#:` not followed by a valid namespace and a map, holds the prefix as written
+    InvalidNamespacedMap(String),
+    /// `#_` not followed by an element
+    DiscardWithoutElement,
+    /// A set was found but feature `sets` is not enabled
+    SetsDisabled,
+    /// A streamed form is not valid UTF-8
+    InvalidUtf8,
+    /// A streamed form longer than [`crate::ReaderOptions::max_form_len`], holds the limit
+    FormTooLong(usize),
+    /// The handler of a tag rejected its element, see [`crate::TagRegistry`]
+    InvalidTagged {
+        tag: String,
+        message: String,
+    },
+}
+
+impl ParseError {
+    pub(crate) fn new(source: &str, offset: usize, kind: ParseErrorKind) -> Self {
+        let offset = offset.min(source.len());
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+
+        Self {
+            kind,
+            line: source[..line_start].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            offset,
+            snippet: source[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => write!(f, "Unexpected end of input"),
+            Self::UnexpectedDelimiter(c) => write!(f, "Unexpected delimiter `{c}`"),
+            Self::Unclosed(delimiter) => write!(f, "Unclosed delimiter `{delimiter}`"),
+            Self::UnterminatedString => write!(f, "Unterminated string"),
+            Self::InvalidEscape(c) => write!(f, "Invalid escape sequence \\{c}"),
+            Self::InvalidUnicodeEscape(escape) => write!(f, "Invalid unicode escape {escape}"),
+            Self::InvalidCodePoint(code_point) => {
+                write!(f, "Invalid code point U+{code_point:04X}")
+            }
+            Self::InvalidChar(c) => write!(f, "{c:?} could not be parsed as a char"),
+            Self::InvalidKeyword(k) => write!(f, "Invalid keyword {k}"),
+            Self::InvalidSymbol(s) => write!(f, "Invalid symbol {s}"),
+            Self::InvalidNumber { number, radix } => {
+                write!(f, "{number} could not be parsed with radix {radix}")
+            }
+            Self::InvalidRadix(number) => write!(f, "Invalid radix in {number}"),
+            Self::RadixOutOfBounds(radix) => write!(f, "Radix of {radix} is out of bounds"),
+            Self::InvalidSymbolicValue(value) => write!(f, "Invalid symbolic value ##{value}"),
+            Self::InvalidNamespacedMap(prefix) => write!(f, "Invalid namespaced map {prefix}"),
+            Self::DiscardWithoutElement => {
+                write!(f, "Discard sequence must have a following element")
+            }
+            Self::SetsDisabled => write!(f, "Could not parse set due to feature not being enabled"),
+            Self::InvalidUtf8 => write!(f, "Invalid UTF-8"),
+            Self::FormTooLong(max) => write!(f, "Form longer than {max} bytes"),
+            Self::InvalidTagged { tag, message } => write!(f, "Invalid #{tag} element: {message}"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+        writeln!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{:gutter$} | {:>col$}", "", "^", col = self.column)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn position_on_first_line() {
+        let err = ParseError::new("[1 2", 0, ParseErrorKind::Unclosed("["));
+
+        assert_eq!((err.line, err.column, err.offset), (1, 1, 0));
+        assert_eq!(err.snippet, "[1 2");
+    }
+
+    #[test]
+    fn position_counts_chars_not_bytes() {
+        let err = ParseError::new(
+            "{:a 1}\r\n{:açaí \\",
+            17,
+            ParseErrorKind::InvalidChar(String::new()),
+        );
+
+        assert_eq!((err.line, err.column), (2, 8));
+        assert_eq!(err.snippet, "{:açaí \\");
+    }
+
+    #[test]
+    fn display_points_at_column() {
+        let err = ParseError::new("(1 2)\n\n[3 4", 7, ParseErrorKind::Unclosed("["));
+
+        assert_eq!(
+            err.to_string(),
+            "Unclosed delimiter `[` at line 3, column 1\n  |\n3 | [3 4\n  | ^"
+        );
+    }
+}
@@ -0,0 +1,549 @@
+use alloc::boxed::Box;
+use alloc::fmt;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::str::FromStr;
+
+use crate::edn::{Edn, Error};
+
+/// `Inst` is the value of an `#inst` tagged element, an RFC 3339 timestamp.
+///
+/// Like Clojure, trailing parts of the timestamp can be left out: `"2020"`, `"2020-07"`,
+/// `"2020-07-16T21:53"` and `"2020-07-16T21:53:14.628-03:00"` are all valid. Missing parts
+/// default to their lowest value and a missing offset to UTC. `offset_minutes` is the offset
+/// from UTC of the local time fields.
+///
+/// An `Inst` is always a valid timestamp, it is built by parsing one, with
+/// [`Inst::from_unix_timestamp_millis`] or from a `chrono` or `time` date.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, Inst};
+///
+/// let edn = Edn::from_str("{:at #inst \"2020-07-16T21:53:14.628-03:00\"}").unwrap();
+/// let inst = edn[":at"].to_inst().unwrap();
+///
+/// assert_eq!((inst.year(), inst.month(), inst.day(), inst.hour()), (2020, 7, 16, 21));
+/// assert_eq!(inst.nanosecond(), 628_000_000);
+/// assert_eq!(inst.offset_minutes(), -180);
+/// assert_eq!(inst.unix_timestamp(), 1_594_947_194);
+/// assert_eq!(Inst::from_str("1985").unwrap().to_string(), "1985-01-01T00:00:00Z");
+/// assert!(Edn::from_str("#inst \"2020-02-30\"").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Inst {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+    offset_minutes: i16,
+}
+
+impl Inst {
+    #[must_use]
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    #[must_use]
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    #[must_use]
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Up to 60, for leap seconds.
+    #[must_use]
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    #[must_use]
+    pub const fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    #[must_use]
+    pub const fn offset_minutes(&self) -> i16 {
+        self.offset_minutes
+    }
+
+    /// Seconds since 1970-01-01T00:00:00Z, a leap second counts as the next second.
+    #[must_use]
+    pub fn unix_timestamp(&self) -> i64 {
+        let days = days_from_civil(i64::from(self.year), self.month, self.day);
+        days * 86_400
+            + i64::from(self.hour) * 3_600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+            - i64::from(self.offset_minutes) * 60
+    }
+
+    /// Milliseconds since 1970-01-01T00:00:00Z, the fraction of a millisecond is truncated.
+    #[must_use]
+    pub fn unix_timestamp_millis(&self) -> i64 {
+        self.unix_timestamp() * 1_000 + i64::from(self.nanosecond / 1_000_000)
+    }
+
+    /// The UTC timestamp `millis` milliseconds after 1970-01-01T00:00:00Z, `None` when its
+    /// year does not have 4 digits.
+    #[must_use]
+    pub fn from_unix_timestamp_millis(millis: i64) -> Option<Self> {
+        let (year, month, day) = civil_from_days(millis.div_euclid(86_400_000))?;
+        let millis = millis.rem_euclid(86_400_000);
+        Some(Self {
+            year: u16::try_from(year).ok().filter(|&year| year <= 9999)?,
+            month,
+            day,
+            hour: u8::try_from(millis / 3_600_000).ok()?,
+            minute: u8::try_from(millis / 60_000 % 60).ok()?,
+            second: u8::try_from(millis / 1_000 % 60).ok()?,
+            nanosecond: u32::try_from(millis % 1_000).ok()? * 1_000_000,
+            offset_minutes: 0,
+        })
+    }
+
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        parse_inst(s).map_err(|reason| format!("invalid RFC 3339 timestamp {s:?}, {reason}"))
+    }
+}
+
+// Days from 1970-01-01 to the given date of the proleptic Gregorian calendar,
+// see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// Inverse of `days_from_civil`, the year, month and day of a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> Option<(i64, u8, u8)> {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = u8::try_from(day_of_year - (153 * shifted_month + 2) / 5 + 1).ok()?;
+    let month = u8::try_from(if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    })
+    .ok()?;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    Some((year, month, day))
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn eat(&mut self, expected: &[u8]) -> Option<u8> {
+        let b = *self.bytes.get(self.pos)?;
+        if expected.contains(&b) {
+            self.pos += 1;
+            Some(b)
+        } else {
+            None
+        }
+    }
+
+    fn digits(&mut self, n: usize, part: &'static str) -> Result<u32, &'static str> {
+        let digits = self
+            .bytes
+            .get(self.pos..self.pos + n)
+            .filter(|d| d.iter().all(u8::is_ascii_digit))
+            .ok_or(part)?;
+        self.pos += n;
+        Ok(digits
+            .iter()
+            .fold(0, |acc, d| acc * 10 + u32::from(d - b'0')))
+    }
+
+    fn field(&mut self, max: u32, part: &'static str) -> Result<u8, &'static str> {
+        let value = self.digits(2, part)?;
+        if value > max {
+            return Err(part);
+        }
+        u8::try_from(value).map_err(|_| part)
+    }
+}
+
+fn parse_inst(s: &str) -> Result<Inst, &'static str> {
+    let mut cursor = Cursor {
+        bytes: s.as_bytes(),
+        pos: 0,
+    };
+    let year = u16::try_from(cursor.digits(4, "expected a 4 digit year")?)
+        .map_err(|_| "expected a 4 digit year")?;
+    let mut inst = Inst {
+        year,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        nanosecond: 0,
+        offset_minutes: 0,
+    };
+
+    if cursor.eat(b"-").is_some() {
+        inst.month = cursor.field(12, "month out of range")?;
+        if inst.month == 0 {
+            return Err("month out of range");
+        }
+        if cursor.eat(b"-").is_some() {
+            inst.day = cursor.field(31, "day out of range")?;
+            if inst.day == 0 || inst.day > days_in_month(inst.year, inst.month) {
+                return Err("day out of range");
+            }
+            if cursor.eat(b"Tt").is_some() {
+                inst.hour = cursor.field(23, "hour out of range")?;
+                if cursor.eat(b":").is_some() {
+                    inst.minute = cursor.field(59, "minute out of range")?;
+                    if cursor.eat(b":").is_some() {
+                        inst.second = cursor.field(60, "second out of range")?;
+                        if cursor.eat(b".").is_some() {
+                            inst.nanosecond = read_fraction(&mut cursor)?;
+                        }
+                    }
+                }
+                inst.offset_minutes = read_offset(&mut cursor)?;
+            }
+        }
+    }
+
+    if cursor.pos == cursor.bytes.len() {
+        Ok(inst)
+    } else {
+        Err("unexpected trailing characters")
+    }
+}
+
+fn read_fraction(cursor: &mut Cursor<'_>) -> Result<u32, &'static str> {
+    let mut nanos = 0;
+    let mut digits = 0;
+    while let Some(d) = cursor.eat(b"0123456789") {
+        // Digits past nanoseconds are truncated
+        if digits < 9 {
+            nanos = nanos * 10 + u32::from(d - b'0');
+        }
+        digits += 1;
+    }
+    if digits == 0 {
+        return Err("expected fraction of a second");
+    }
+    Ok(nanos * 10_u32.pow(9_u32.saturating_sub(digits)))
+}
+
+fn read_offset(cursor: &mut Cursor<'_>) -> Result<i16, &'static str> {
+    let sign = match cursor.eat(b"Zz+-") {
+        None | Some(b'Z' | b'z') => return Ok(0),
+        Some(b'-') => -1,
+        Some(_) => 1,
+    };
+    let hours = cursor.field(23, "offset out of range")?;
+    cursor.eat(b":").ok_or("offset out of range")?;
+    let minutes = cursor.field(59, "offset out of range")?;
+    Ok(sign * (i16::from(hours) * 60 + i16::from(minutes)))
+}
+
+impl FromStr for Inst {
+    type Err = Error;
+
+    /// Parses the RFC 3339 timestamp of an `#inst`, without the tag.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).map_err(Error::ParseEdn)
+    }
+}
+
+impl fmt::Display for Inst {
+    /// Formats as a complete RFC 3339 timestamp, the fraction of a second has no trailing
+    /// zeros and a zero offset is written as `Z`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.nanosecond > 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        match self.offset_minutes {
+            0 => write!(f, "Z"),
+            offset => write!(
+                f,
+                "{}{:02}:{:02}",
+                if offset < 0 { '-' } else { '+' },
+                offset.unsigned_abs() / 60,
+                offset.unsigned_abs() % 60
+            ),
+        }
+    }
+}
+
+impl From<Inst> for Edn {
+    fn from(inst: Inst) -> Self {
+        Self::Tagged(String::from("inst"), Box::new(Self::Str(inst.to_string())))
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Offset, TimeZone, Timelike};
+
+    use super::Inst;
+    use crate::edn::Error;
+
+    impl TryFrom<Inst> for DateTime<FixedOffset> {
+        type Error = Error;
+
+        /// Fails on leap seconds in an offset `chrono` does not accept.
+        fn try_from(inst: Inst) -> Result<Self, Error> {
+            let error = || Error::ParseEdn(alloc::format!("{inst} is out of chrono's range"));
+            let offset =
+                FixedOffset::east_opt(i32::from(inst.offset_minutes) * 60).ok_or_else(error)?;
+            // chrono represents a leap second as a nanosecond overflow of the previous second
+            let (second, nanosecond) = if inst.second == 60 {
+                (59, inst.nanosecond + 1_000_000_000)
+            } else {
+                (inst.second, inst.nanosecond)
+            };
+            let local = NaiveDate::from_ymd_opt(
+                i32::from(inst.year),
+                u32::from(inst.month),
+                u32::from(inst.day),
+            )
+            .and_then(|date| {
+                date.and_hms_nano_opt(
+                    u32::from(inst.hour),
+                    u32::from(inst.minute),
+                    u32::from(second),
+                    nanosecond,
+                )
+            })
+            .ok_or_else(error)?;
+            offset
+                .from_local_datetime(&local)
+                .single()
+                .ok_or_else(error)
+        }
+    }
+
+    impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for Inst {
+        type Error = Error;
+
+        /// Fails for years outside of `0..=9999`.
+        fn try_from(date: DateTime<Tz>) -> Result<Self, Error> {
+            let offset_seconds = date.offset().fix().local_minus_utc();
+            let year = u16::try_from(date.year())
+                .ok()
+                .filter(|year| *year <= 9999)
+                .ok_or_else(|| {
+                    Error::ParseEdn(alloc::format!(
+                        "year {} is out of #inst's range",
+                        date.year()
+                    ))
+                })?;
+            let (second, nanosecond) = if date.nanosecond() >= 1_000_000_000 {
+                (60, date.nanosecond() - 1_000_000_000)
+            } else {
+                (date.second(), date.nanosecond())
+            };
+            #[allow(clippy::cast_possible_truncation, reason = "all fields are in range")]
+            Ok(Self {
+                year,
+                month: date.month() as u8,
+                day: date.day() as u8,
+                hour: date.hour() as u8,
+                minute: date.minute() as u8,
+                second: second as u8,
+                nanosecond,
+                offset_minutes: (offset_seconds / 60) as i16,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+    use super::Inst;
+    use crate::edn::Error;
+
+    impl TryFrom<Inst> for OffsetDateTime {
+        type Error = Error;
+
+        /// Fails on leap seconds, which `time` does not represent.
+        fn try_from(inst: Inst) -> Result<Self, Error> {
+            let error = |_| Error::ParseEdn(alloc::format!("{inst} is out of time's range"));
+            let date = Date::from_calendar_date(
+                i32::from(inst.year),
+                Month::try_from(inst.month).map_err(error)?,
+                inst.day,
+            )
+            .map_err(error)?;
+            let time = Time::from_hms_nano(inst.hour, inst.minute, inst.second, inst.nanosecond)
+                .map_err(error)?;
+            let offset = UtcOffset::from_whole_seconds(i32::from(inst.offset_minutes) * 60)
+                .map_err(error)?;
+            Ok(PrimitiveDateTime::new(date, time).assume_offset(offset))
+        }
+    }
+
+    impl TryFrom<OffsetDateTime> for Inst {
+        type Error = Error;
+
+        /// Fails for years outside of `0..=9999`.
+        fn try_from(date: OffsetDateTime) -> Result<Self, Error> {
+            let year = u16::try_from(date.year())
+                .ok()
+                .filter(|year| *year <= 9999)
+                .ok_or_else(|| {
+                    Error::ParseEdn(alloc::format!(
+                        "year {} is out of #inst's range",
+                        date.year()
+                    ))
+                })?;
+            Ok(Self {
+                year,
+                month: date.month().into(),
+                day: date.day(),
+                hour: date.hour(),
+                minute: date.minute(),
+                second: date.second(),
+                nanosecond: date.nanosecond(),
+                offset_minutes: date.offset().whole_minutes(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn converts_unix_millis() {
+        for source in [
+            "1970-01-01T00:00:00Z",
+            "2020-02-29T23:59:59.999Z",
+            "1969-12-31T23:59:59.001Z",
+            "0000-03-01T00:00:00Z",
+            "9999-12-31T23:59:59.999Z",
+        ] {
+            let inst = Inst::parse(source).unwrap();
+            let millis = inst.unix_timestamp_millis();
+
+            assert_eq!(
+                Inst::from_unix_timestamp_millis(millis),
+                Some(inst),
+                "{source}"
+            );
+        }
+        assert_eq!(
+            Inst::parse("2020-07-16T21:53:14.628-03:00")
+                .unwrap()
+                .unix_timestamp_millis(),
+            1_594_947_194_628
+        );
+        assert_eq!(Inst::from_unix_timestamp_millis(253_402_300_800_000), None);
+    }
+
+    #[test]
+    fn parses_partial_timestamps() {
+        assert_eq!(
+            Inst::parse("2020-02-29T23").unwrap(),
+            Inst {
+                year: 2020,
+                month: 2,
+                day: 29,
+                hour: 23,
+                minute: 0,
+                second: 0,
+                nanosecond: 0,
+                offset_minutes: 0,
+            }
+        );
+        assert_eq!(
+            Inst::parse("1985-04-12T23:20:50.52+05:30")
+                .unwrap()
+                .to_string(),
+            "1985-04-12T23:20:50.52+05:30"
+        );
+        assert_eq!(
+            Inst::parse("2016-12-31t23:59:60.1234567891z")
+                .unwrap()
+                .to_string(),
+            "2016-12-31T23:59:60.123456789Z"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        for (s, reason) in [
+            ("20", "expected a 4 digit year"),
+            ("2021-02-29", "day out of range"),
+            ("2020-00", "month out of range"),
+            ("2020-01-01T24:00", "hour out of range"),
+            ("2020-01-01T10:00:00.", "expected fraction of a second"),
+            ("2020-01-01T10:00+01", "offset out of range"),
+            ("2020-01-01 10:00", "unexpected trailing characters"),
+        ] {
+            assert_eq!(parse_inst(s), Err(reason), "{s}");
+        }
+    }
+
+    #[test]
+    fn unix_timestamp() {
+        assert_eq!(Inst::parse("1970").unwrap().unix_timestamp(), 0);
+        assert_eq!(
+            Inst::parse("1969-12-31T23:00-01:00")
+                .unwrap()
+                .unix_timestamp(),
+            0
+        );
+        assert_eq!(
+            Inst::parse("2000-03-01").unwrap().unix_timestamp(),
+            951_868_800
+        );
+    }
+}
@@ -5,8 +5,8 @@ use alloc::collections::BTreeSet;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::{fmt, format};
-#[cfg(feature = "sets")]
-use core::cmp::{Ord, PartialOrd};
+#[cfg(not(feature = "sets"))]
+use core::cmp::Ordering;
 use core::convert::{Infallible, TryFrom};
 use core::num;
 
@@ -16,14 +16,26 @@ use utils::index::Index;
 #[cfg(feature = "sets")]
 use ordered_float::OrderedFloat;
 
+mod borrowed;
+mod error;
+mod inst;
+mod ordered_map;
+mod symbol;
 #[doc(hidden)]
 pub mod utils;
+mod uuid;
+
+pub use borrowed::EdnRef;
+pub use error::{ParseError, ParseErrorKind};
+pub use inst::Inst;
+pub use ordered_map::OrderedMap;
+pub use symbol::{Keyword, Symbol};
+pub use uuid::Uuid;
 
 /// `EdnType` is an Enum with possible values for an EDN type
 /// Symbol and Char are not yet implemented
 /// String implementation of Edn can be obtained with `.to_string()`
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "sets", derive(Eq, PartialOrd, Ord))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[non_exhaustive]
 pub enum Edn {
     Tagged(String, Box<Self>),
@@ -31,6 +43,8 @@ pub enum Edn {
     #[cfg(feature = "sets")]
     Set(Set),
     Map(Map),
+    /// Map in insertion order, read with `ReaderOptions::ordered_maps`
+    OrderedMap(OrderedMap),
     List(List),
     Key(String),
     Symbol(String),
@@ -39,6 +53,11 @@ pub enum Edn {
     UInt(u64),
     Double(Double),
     Rational(String),
+    /// Arbitrary-precision integer, `42N` or an integer too large for `Int` and `UInt`,
+    /// kept as its decimal digits without the `N` suffix
+    BigInt(String),
+    /// Arbitrary-precision decimal, `3.14M`, kept exactly as written without the `M` suffix
+    Decimal(String),
     Char(char),
     Bool(bool),
     Nil,
@@ -49,13 +68,56 @@ pub enum Edn {
 #[cfg(feature = "sets")]
 pub struct Double(pub(crate) OrderedFloat<f64>);
 
-#[derive(Clone, Debug, PartialEq)]
+/// Without `ordered-float`, `Double` follows the same total order, `NaN` is equal to itself
+/// and greater than any other value, so it can be used inside map keys.
+#[derive(Clone, Debug)]
 #[cfg(not(feature = "sets"))]
 pub struct Double(f64);
 
+#[cfg(not(feature = "sets"))]
+impl PartialEq for Double {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(not(feature = "sets"))]
+impl Eq for Double {}
+
+#[cfg(not(feature = "sets"))]
+impl PartialOrd for Double {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(not(feature = "sets"))]
+impl Ord for Double {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0.is_nan(), other.0.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+/// Always has a decimal point, so it reads back as a `Double` and not as an `Int`, infinity
+/// and `NaN` are written as the symbolic values `##Inf`, `##-Inf` and `##NaN`.
 impl fmt::Display for Double {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        let float = self.to_float();
+        if float.is_nan() {
+            write!(f, "##NaN")
+        } else if float.is_infinite() {
+            write!(f, "##{}Inf", if float < 0f64 { "-" } else { "" })
+        } else {
+            // `f64` never uses an exponent in `Display`, but drops the fraction of integers
+            let float = format!("{float}");
+            let point = if float.contains('.') { "" } else { ".0" };
+            write!(f, "{float}{point}")
+        }
     }
 }
 
@@ -87,9 +149,8 @@ impl From<f64> for Double {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "sets", derive(Eq, PartialOrd, Ord))]
-pub struct Vector(Vec<Edn>);
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Vector(pub(crate) Vec<Edn>);
 impl Vector {
     #[must_use]
     pub const fn new(v: Vec<Edn>) -> Self {
@@ -107,9 +168,8 @@ impl Vector {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "sets", derive(Eq, PartialOrd, Ord))]
-pub struct List(Vec<Edn>);
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct List(pub(crate) Vec<Edn>);
 impl List {
     #[must_use]
     pub const fn new(v: Vec<Edn>) -> Self {
@@ -129,7 +189,7 @@ impl List {
 
 #[cfg(feature = "sets")]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub struct Set(BTreeSet<Edn>);
+pub struct Set(pub(crate) BTreeSet<Edn>);
 
 #[cfg(feature = "sets")]
 impl Set {
@@ -149,12 +209,11 @@ impl Set {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "sets", derive(Eq, PartialOrd, Ord))]
-pub struct Map(BTreeMap<String, Edn>);
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Map(pub(crate) BTreeMap<Edn, Edn>);
 impl Map {
     #[must_use]
-    pub const fn new(m: BTreeMap<String, Edn>) -> Self {
+    pub const fn new(m: BTreeMap<Edn, Edn>) -> Self {
         Self(m)
     }
 
@@ -164,7 +223,7 @@ impl Map {
     }
 
     #[must_use]
-    pub fn to_map(self) -> BTreeMap<String, Edn> {
+    pub fn to_map(self) -> BTreeMap<Edn, Edn> {
         self.0
     }
 }
@@ -230,16 +289,46 @@ impl core::fmt::Display for Map {
     }
 }
 
-fn char_to_edn(c: char) -> String {
+pub(crate) fn char_to_edn(c: char) -> String {
     match c {
         '\n' => "\\newline".to_string(),
         '\r' => "\\return".to_string(),
         ' ' => "\\space".to_string(),
         '\t' => "\\tab".to_string(),
+        '\u{c}' => "\\formfeed".to_string(),
+        '\u{8}' => "\\backspace".to_string(),
+        // `\` followed by whitespace or a control char would not read back
+        _ if c.is_whitespace() || c.is_control() => format!("\\u{:04x}", u32::from(c)),
         _ => format!("\\{c}"),
     }
 }
 
+/// `s` as an EDN string literal, other control chars are written as `\uXXXX` escapes.
+pub(crate) fn str_to_edn(s: &str) -> String {
+    let mut edn = String::with_capacity(s.len() + 2);
+    edn.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => edn.push_str("\\\""),
+            '\\' => edn.push_str("\\\\"),
+            '\n' => edn.push_str("\\n"),
+            '\r' => edn.push_str("\\r"),
+            '\t' => edn.push_str("\\t"),
+            '\u{8}' => edn.push_str("\\b"),
+            '\u{c}' => edn.push_str("\\f"),
+            _ if c.is_control() => {
+                let _ = fmt::Write::write_fmt(&mut edn, format_args!("\\u{:04x}", u32::from(c)));
+            }
+            _ => edn.push(c),
+        }
+    }
+    edn.push('"');
+    edn
+}
+
+/// Emits valid EDN that reads back as the same `Edn`, `Edn::from_str(&edn.to_string())`
+/// returns `edn`. Values the parser never produces read back in their parsed form, a
+/// non-negative `Int` as `UInt`, and `Empty` inside a collection is dropped.
 impl core::fmt::Display for Edn {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let text = match self {
@@ -247,14 +336,17 @@ impl core::fmt::Display for Edn {
             #[cfg(feature = "sets")]
             Self::Set(s) => format!("{s}"),
             Self::Map(m) => format!("{m}"),
+            Self::OrderedMap(m) => format!("{m}"),
             Self::List(l) => format!("{l}"),
             Self::Symbol(sy) => sy.clone(),
             Self::Key(k) => k.clone(),
-            Self::Str(s) => format!("{s:?}"),
+            Self::Str(s) => str_to_edn(s),
             Self::Int(i) => format!("{i}"),
             Self::UInt(u) => format!("{u}"),
             Self::Double(d) => format!("{d}"),
             Self::Rational(r) => r.clone(),
+            Self::BigInt(n) => format!("{n}N"),
+            Self::Decimal(d) => format!("{d}M"),
             Self::Bool(b) => format!("{b}"),
             Self::Char(c) => char_to_edn(*c),
             Self::Nil => String::from("nil"),
@@ -288,6 +380,7 @@ impl Edn {
             Self::UInt(u) => to_double(u).ok(),
             Self::Double(d) => Some(d.to_float()),
             Self::Rational(r) => rational_to_double(r),
+            Self::BigInt(n) | Self::Decimal(n) => n.parse::<f64>().ok(),
             _ => None,
         }
     }
@@ -319,6 +412,10 @@ impl Edn {
             #[allow(clippy::cast_possible_truncation)]
             #[cfg(feature = "std")]
             Self::Rational(r) => Some(rational_to_double(r).unwrap_or(0f64).round() as i64),
+            Self::BigInt(n) => n.parse::<i64>().ok(),
+            #[allow(clippy::cast_possible_truncation)]
+            #[cfg(feature = "std")]
+            Self::Decimal(d) => Some(d.parse::<f64>().ok()?.round() as i64),
             _ => None,
         }
     }
@@ -345,6 +442,14 @@ impl Edn {
                 #[allow(clippy::cast_possible_truncation)]
                 Some(rational_to_double(r)?.round() as u64)
             }
+            Self::BigInt(n) => n.parse::<u64>().ok(),
+            #[cfg(feature = "std")]
+            Self::Decimal(d) if !d.starts_with('-') =>
+            {
+                #[allow(clippy::cast_sign_loss)]
+                #[allow(clippy::cast_possible_truncation)]
+                Some(d.parse::<f64>().ok()?.round() as u64)
+            }
             _ => None,
         }
     }
@@ -388,6 +493,82 @@ impl Edn {
         }
     }
 
+    /// `to_keyword` splits a `Key` into its namespace and name, `None` for other types.
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use edn_rs::edn::Edn;
+    ///
+    /// let keyword = Edn::from_str(":user/id").unwrap().to_keyword().unwrap();
+    ///
+    /// assert_eq!((keyword.namespace(), keyword.name()), (Some("user"), "id"));
+    /// assert_eq!(Edn::Str(":user/id".to_string()).to_keyword(), None);
+    /// ```
+    #[must_use]
+    pub fn to_keyword(&self) -> Option<Keyword> {
+        match self {
+            Self::Key(k) => Keyword::parse(k).ok(),
+            _ => None,
+        }
+    }
+
+    /// `to_symbol` splits a `Symbol` into its namespace and name, `None` for other types.
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use edn_rs::edn::Edn;
+    ///
+    /// let symbol = Edn::from_str("clojure.string/join").unwrap().to_symbol().unwrap();
+    ///
+    /// assert_eq!((symbol.namespace(), symbol.name()), (Some("clojure.string"), "join"));
+    /// ```
+    #[must_use]
+    pub fn to_symbol(&self) -> Option<Symbol> {
+        match self {
+            Self::Symbol(s) => Symbol::parse(s).ok(),
+            _ => None,
+        }
+    }
+
+    /// `to_inst` returns the timestamp of an `#inst` tagged element, `None` for other types.
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use edn_rs::edn::Edn;
+    ///
+    /// let inst = Edn::from_str("#inst \"2020-07-16T21:53:14.628-00:00\"").unwrap();
+    ///
+    /// assert_eq!(inst.to_inst().unwrap().to_string(), "2020-07-16T21:53:14.628Z");
+    /// assert_eq!(Edn::Str("2020".to_string()).to_inst(), None);
+    /// ```
+    #[must_use]
+    pub fn to_inst(&self) -> Option<Inst> {
+        match self {
+            Self::Tagged(tag, edn) if tag == "inst" => match &**edn {
+                Self::Str(s) => Inst::parse(s).ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// `to_uuid` returns the UUID of a `#uuid` tagged element, `None` for other types.
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use edn_rs::edn::Edn;
+    ///
+    /// let uuid = Edn::from_str("#uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\"").unwrap();
+    ///
+    /// assert_eq!(uuid.to_uuid().unwrap().as_u128() >> 96, 0xaf6d_8699);
+    /// ```
+    #[must_use]
+    pub fn to_uuid(&self) -> Option<Uuid> {
+        match self {
+            Self::Tagged(tag, edn) if tag == "uuid" => match &**edn {
+                Self::Str(s) => Uuid::parse(s).ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     /// `to_vec` converts `Edn` types `Vector`, `List` and `Set` into an `Option<Vec<String>>`.
     /// Type String was selected because it is the current way to mix floats, integers and Strings.
     #[must_use]
@@ -638,20 +819,40 @@ impl Edn {
         }
     }
 
-    /// `map_iter` returns am `Option<btree_map::Iter<String, Edn>>` with `Some` for type `Edn::Map`
+    /// `map_iter` returns am `Option<btree_map::Iter<Edn, Edn>>` with `Some` for type `Edn::Map`
     /// Other types return `None`
     #[must_use]
-    pub fn map_iter(&self) -> Option<alloc::collections::btree_map::Iter<'_, String, Self>> {
+    pub fn map_iter(&self) -> Option<alloc::collections::btree_map::Iter<'_, Self, Self>> {
         match self {
             Self::Map(m) => Some(m.0.iter()),
             _ => None,
         }
     }
 
+    /// `ordered_map_iter` returns an `Option<slice::Iter<(Edn, Edn)>>` with `Some` for type
+    /// `Edn::OrderedMap`, in insertion order. Other types return `None`
+    #[must_use]
+    pub fn ordered_map_iter(&self) -> Option<core::slice::Iter<'_, (Self, Self)>> {
+        match self {
+            Self::OrderedMap(m) => Some(m.iter()),
+            _ => None,
+        }
+    }
+
+    /// Entries of a `Map` or an `OrderedMap`, in the order they are printed.
+    pub(crate) fn map_entries(&self) -> Option<Vec<(&Self, &Self)>> {
+        match self {
+            Self::Map(m) => Some(m.0.iter().collect()),
+            Self::OrderedMap(m) => Some(m.iter().map(|(k, v)| (k, v)).collect()),
+            _ => None,
+        }
+    }
+
     /// Method `to_json` allows you to convert a `edn_rs::Edn` into a JSON string. Type convertions are:
     /// `Edn::Vector(v)` => a vector like `[value1, value2, ..., valueN]`
     /// `Edn::Set(s)` => a vector like `[value1, value2, ..., valueN]`
     /// `Edn::Map(map)` => a map like `{\"key1\": value1, ..., \"keyN\": valueN}`
+    /// `Edn::OrderedMap(map)` => the same as a map, with its keys in insertion order
     /// `Edn::List(l)` => a vector like `[value1, value2, ..., valueN]`
     /// `Edn::Key(key)` => a `camelCase` version of the `:kebab-case` keyword,
     /// `Edn::Symbol(s)` => `\"a-simple-string\"`
@@ -660,12 +861,18 @@ impl Edn {
     /// `Edn::UInt(n)` => a number like `5`
     /// `Edn::Double(n)` => a number like `3.14`
     /// `Edn::Rational(r)` => a number like `0.25` for `1/4`.
+    /// `Edn::BigInt(n)` => all the digits of the number, like `12345678901234567890123`
+    /// `Edn::Decimal(d)` => the exact number, like `3.14` for `3.14M`
     /// `Edn::Char(c)` => a simple char `\'c\'`
     /// `Edn::Bool(b)` => boolean options, `true` and `false`
     /// `Edn::Inst(inst)` => a `DateTime` string like `\"2020-10-21T00:00:00.000-00:00\"`
     /// `Edn::Uuid(uuid)` => a UUID string like `\"7a6b6722-0221-4280-865e-ad41060d53b2\"`
     /// `Edn::Nil` => `null`
     /// `Edn::Empty` => empty value, ` `
+    ///
+    /// Chars and empty values are not valid JSON, use [`crate::to_json_with`] for an output
+    /// that always is, with the case of keywords and the form of tagged elements, rationals
+    /// and sets to choose.
     /// ```
     /// use std::str::FromStr;
     ///
@@ -700,6 +907,20 @@ impl core::str::FromStr for Edn {
     }
 }
 
+#[cfg(feature = "num-bigint")]
+impl From<num_bigint::BigInt> for Edn {
+    fn from(n: num_bigint::BigInt) -> Self {
+        Self::BigInt(n.to_string())
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for Edn {
+    fn from(d: rust_decimal::Decimal) -> Self {
+        Self::Decimal(d.to_string())
+    }
+}
+
 fn to_double<T>(i: T) -> Result<f64, num::ParseFloatError>
 where
     T: fmt::Debug,
@@ -723,10 +944,33 @@ pub(crate) fn rational_to_double(r: &str) -> Option<f64> {
 #[derive(Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Error {
+    /// Error produced by the parser, with the position of the failure
+    Parse(ParseError),
     ParseEdn(String),
     Deserialize(String),
+    /// Error produced while serializing a value into `Edn`
+    Serialize(String),
     Iter(String),
+    /// Error produced by a structural edit of a [`crate::cst::Document`], or by a
+    /// [`crate::diff::patch`] that does not apply
+    Edit(String),
+    /// Error produced while compiling an invalid [`crate::schema::Schema`]
+    Schema(String),
+    /// Error produced while reading transit+json, see `transit::from_str`. Requires feature
+    /// `transit`
+    #[cfg(feature = "transit")]
+    Transit(String),
+    /// Error produced while reading Fressian, see `fressian::from_bytes`. Requires feature
+    /// `fressian`
+    #[cfg(feature = "fressian")]
+    Fressian(String),
+    /// Error produced while reading JSON, see `JsonReadOptions::parse`. Requires feature `json`
+    #[cfg(feature = "json")]
+    Json(String),
     TryFromInt(num::TryFromIntError),
+    /// Error produced while reading from a stream, with its kind and message
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind, String),
     #[doc(hidden)]
     Infallable(), // Makes the compiler happy for converting u64 to u64 and i64 to i64
 }
@@ -737,6 +981,12 @@ impl From<String> for Error {
     }
 }
 
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
 impl From<num::ParseIntError> for Error {
     fn from(s: num::ParseIntError) -> Self {
         Self::ParseEdn(s.to_string())
@@ -761,6 +1011,13 @@ impl From<num::TryFromIntError> for Error {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e.kind(), e.to_string())
+    }
+}
+
 impl From<Infallible> for Error {
     fn from(_: Infallible) -> Self {
         Self::Infallable()
@@ -770,13 +1027,29 @@ impl From<Infallible> for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::ParseEdn(s) | Self::Deserialize(s) | Self::Iter(s) => write!(f, "{}", &s),
+            Self::Parse(e) => write!(f, "{e}"),
+            Self::ParseEdn(s)
+            | Self::Deserialize(s)
+            | Self::Serialize(s)
+            | Self::Iter(s)
+            | Self::Edit(s)
+            | Self::Schema(s) => write!(f, "{}", &s),
+            #[cfg(feature = "transit")]
+            Self::Transit(s) => write!(f, "{s}"),
+            #[cfg(feature = "fressian")]
+            Self::Fressian(s) => write!(f, "{s}"),
+            #[cfg(feature = "json")]
+            Self::Json(s) => write!(f, "{s}"),
             Self::TryFromInt(e) => write!(f, "{e}"),
+            #[cfg(feature = "std")]
+            Self::Io(_, message) => write!(f, "{message}"),
             Self::Infallable() => panic!("Infallable conversion"),
         }
     }
 }
 
+impl core::error::Error for Error {}
+
 #[cfg(test)]
 mod test {
     use alloc::borrow::ToOwned;
@@ -873,8 +1146,8 @@ mod test {
     #[test]
     fn edn_to_string() {
         let edn = Edn::Map(Map::new(
-            map! {":a".to_string() => Edn::Key(":something".to_string()),
-            ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil},
+            map! {Edn::Key(":a".to_string()) => Edn::Key(":something".to_string()),
+            Edn::Key(":b".to_string()) => Edn::Bool(false), Edn::Key(":c".to_string()) => Edn::Nil},
         ));
         assert_eq!(edn.to_string(), "{:a :something, :b false, :c nil}");
     }
@@ -882,10 +1155,10 @@ mod test {
     #[test]
     fn edn_to_debug() {
         let edn = Edn::Map(Map::new(
-            map! {":a".to_string() => Edn::Key(":something".to_string()),
-            ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil},
+            map! {Edn::Key(":a".to_string()) => Edn::Key(":something".to_string()),
+            Edn::Key(":b".to_string()) => Edn::Bool(false), Edn::Key(":c".to_string()) => Edn::Nil},
         ));
-        let expected = "Map(Map({\":a\": Key(\":something\"), \":b\": Bool(false), \":c\": Nil}))";
+        let expected = "Map(Map({Key(\":a\"): Key(\":something\"), Key(\":b\"): Bool(false), Key(\":c\"): Nil}))";
         assert_eq!(edn.to_debug(), expected);
     }
 
@@ -960,8 +1233,8 @@ mod test {
     fn get_map() {
         let expected = &Edn::Key(":val".to_string());
         let map = Edn::Map(Map::new(map! {
-            ":key".to_string() => Edn::Key(":val".to_string()),
-            "1".to_string() => Edn::Key(":value".to_string())
+            Edn::Key(":key".to_string()) => Edn::Key(":val".to_string()),
+            Edn::UInt(1) => Edn::Key(":value".to_string())
         }));
 
         let val = &map[Edn::Key(":key".to_owned())];
@@ -0,0 +1,221 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::edn::Edn;
+
+/// `OrderedMap` is a map that keeps its entries in insertion order.
+///
+/// It is read instead of [`crate::Map`] with [`crate::ReaderOptions::ordered_maps`], so that a
+/// map is written back with its keys in source order.
+///
+/// Entries are kept in a `Vec` beside a sorted index of their keys, so looking up and inserting
+/// a key are logarithmic in the size of the map, and removing one is linear.
+/// Two ordered maps with the same entries are equal whatever their order, but an
+/// `OrderedMap` is never equal to a `Map`.
+///
+/// ```rust
+/// use edn_rs::{Edn, ReaderOptions};
+///
+/// let options = ReaderOptions::new().ordered_maps(true);
+/// let edn = options.parse("{:name \"app\" :port 8080 :debug false}").unwrap();
+///
+/// assert_eq!(edn.to_string(), "{:name \"app\", :port 8080, :debug false}");
+/// assert_eq!(edn[":port"], Edn::UInt(8080));
+/// ```
+#[derive(Clone, Default)]
+pub struct OrderedMap {
+    entries: Vec<(Edn, Edn)>,
+    // Position of each key in `entries`
+    index: BTreeMap<Edn, usize>,
+}
+
+impl OrderedMap {
+    /// Builds a map from `entries` in their order. A repeated key keeps the position of its
+    /// first entry and the value of its last one.
+    #[must_use]
+    pub fn new(entries: Vec<(Edn, Edn)>) -> Self {
+        let mut map = Self::empty();
+        for (key, value) in entries {
+            map.insert(key, value);
+        }
+        map
+    }
+
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            entries: Vec::new(),
+            index: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &Edn) -> Option<&Edn> {
+        self.index.get(key).map(|&i| &self.entries[i].1)
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, key: &Edn) -> Option<&mut Edn> {
+        self.index.get(key).map(|&i| &mut self.entries[i].1)
+    }
+
+    /// Replaces the value of `key` in place and returns the previous one, or appends the entry.
+    pub fn insert(&mut self, key: Edn, value: Edn) -> Option<Edn> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(core::mem::replace(old, value));
+        }
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+        None
+    }
+
+    /// Removes `key` and returns its value, the following entries keep their order.
+    pub fn remove(&mut self, key: &Edn) -> Option<Edn> {
+        let i = self.index.remove(key)?;
+        for position in self.index.values_mut() {
+            if *position > i {
+                *position -= 1;
+            }
+        }
+        Some(self.entries.remove(i).1)
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, (Edn, Edn)> {
+        self.entries.iter()
+    }
+
+    #[must_use]
+    pub fn to_vec(self) -> Vec<(Edn, Edn)> {
+        self.entries
+    }
+
+    /// Entries sorted by key, dropping the order.
+    #[must_use]
+    pub fn to_map(self) -> BTreeMap<Edn, Edn> {
+        self.entries.into_iter().collect()
+    }
+
+    fn sorted(&self) -> impl Iterator<Item = (&Edn, &Edn)> {
+        self.index.iter().map(|(k, &i)| (k, &self.entries[i].1))
+    }
+}
+
+impl core::fmt::Debug for OrderedMap {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("OrderedMap").field(&self.entries).finish()
+    }
+}
+
+impl PartialEq for OrderedMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedMap {}
+
+impl PartialOrd for OrderedMap {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Same order as a `Map` with the same entries.
+impl Ord for OrderedMap {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sorted().cmp(other.sorted())
+    }
+}
+
+impl core::fmt::Display for OrderedMap {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{{")?;
+        let mut it = self.entries.iter().peekable();
+        while let Some(kv) = it.next() {
+            if it.peek().is_some() {
+                write!(f, "{} {}, ", kv.0, kv.1)?;
+            } else {
+                write!(f, "{} {}", kv.0, kv.1)?;
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<'a> IntoIterator for &'a OrderedMap {
+    type Item = &'a (Edn, Edn);
+    type IntoIter = core::slice::Iter<'a, (Edn, Edn)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl From<BTreeMap<Edn, Edn>> for OrderedMap {
+    fn from(map: BTreeMap<Edn, Edn>) -> Self {
+        Self::new(map.into_iter().collect())
+    }
+}
+
+impl FromIterator<(Edn, Edn)> for OrderedMap {
+    fn from_iter<I: IntoIterator<Item = (Edn, Edn)>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn keeps_insertion_order() {
+        let mut map = OrderedMap::new(vec![
+            (Edn::Key(":b".to_string()), Edn::UInt(1)),
+            (Edn::Key(":a".to_string()), Edn::UInt(2)),
+            (Edn::Key(":b".to_string()), Edn::UInt(3)),
+        ]);
+        assert_eq!(map.to_string(), "{:b 3, :a 2}");
+
+        assert_eq!(map.insert(Edn::Key(":c".to_string()), Edn::Nil), None);
+        assert_eq!(
+            map.insert(Edn::Key(":a".to_string()), Edn::UInt(4)),
+            Some(Edn::UInt(2))
+        );
+        assert_eq!(map.remove(&Edn::Key(":b".to_string())), Some(Edn::UInt(3)));
+        assert_eq!(map.to_string(), "{:a 4, :c nil}");
+        assert_eq!(map.get(&Edn::Key(":c".to_string())), Some(&Edn::Nil));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn equality_ignores_order() {
+        let a = OrderedMap::new(vec![
+            (Edn::Key(":a".to_string()), Edn::UInt(1)),
+            (Edn::Key(":b".to_string()), Edn::UInt(2)),
+        ]);
+        let b = OrderedMap::new(vec![
+            (Edn::Key(":b".to_string()), Edn::UInt(2)),
+            (Edn::Key(":a".to_string()), Edn::UInt(1)),
+        ]);
+        let c = OrderedMap::new(vec![(Edn::Key(":a".to_string()), Edn::UInt(1))]);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        let ordering = a.cmp(&c);
+        assert_eq!(ordering, a.to_map().cmp(&c.to_map()));
+    }
+}
@@ -0,0 +1,319 @@
+use alloc::fmt;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::str::FromStr;
+
+use crate::edn::{Edn, Error};
+
+// Chars allowed in symbols besides alphanumerics, `:` and `#` are also allowed after the first
+// char.
+const SYMBOL_CHARS: &str = ".*+!-_?$%&=<>'";
+
+/// `Symbol` is the value of `Edn::Symbol`, an identifier like `user/id` split into its optional
+/// namespace and its name.
+///
+/// Symbols begin with a non-numeric char and contain alphanumerics, non-ASCII chars and `. * + ! - _ ? $ % & = < >`,
+/// plus `:` and `#` after the first char, but not `::`. If `-`, `+` or `.` is the first char, the second one
+/// can not be numeric. A single `/` separates the namespace from the name, and `/` alone is a
+/// valid symbol. Quotes are accepted too, so `'(1 2)` reads as the symbol `'` and a list.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, Symbol};
+///
+/// let edn = Edn::from_str("(clojure.core/inc 1)").unwrap();
+/// let symbol = edn[0].to_symbol().unwrap();
+///
+/// assert_eq!(symbol.namespace(), Some("clojure.core"));
+/// assert_eq!(symbol.name(), "inc");
+/// assert_eq!(Symbol::new(None, "inc").unwrap().to_string(), "inc");
+/// assert!(Symbol::from_str("-1a").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol {
+    namespace: Option<String>,
+    name: String,
+}
+
+impl Symbol {
+    /// # Errors
+    ///
+    /// `Error::ParseEdn` when `namespace` or `name` do not follow the symbol grammar.
+    pub fn new(namespace: Option<&str>, name: &str) -> Result<Self, Error> {
+        Self::from_parts(namespace, name, false).map_err(Error::ParseEdn)
+    }
+
+    #[must_use]
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        let (namespace, name) = split_namespace(s);
+        Self::from_parts(namespace, name, false).map_err(|_| format!("invalid symbol {s:?}"))
+    }
+
+    fn from_parts(namespace: Option<&str>, name: &str, keyword: bool) -> Result<Self, String> {
+        if let Some(namespace) = namespace
+            && !is_valid_part(namespace, false)
+        {
+            return Err(format!("invalid namespace {namespace:?}"));
+        }
+        // `/` alone is a name, but only without a namespace in a keyword
+        let slash = name == "/" && !(keyword && namespace.is_some());
+        if !slash && !is_valid_part(name, keyword) {
+            return Err(format!("invalid name {name:?}"));
+        }
+        Ok(Self {
+            namespace: namespace.map(ToString::to_string),
+            name: name.to_string(),
+        })
+    }
+}
+
+/// `Keyword` is the value of `Edn::Key`, an identifier like `:user/id` split into its optional
+/// namespace and its name, both without the leading `:`.
+///
+/// Keywords follow the grammar of [`Symbol`], except that they begin with a `:`. As in Clojure,
+/// their name may begin with a digit, like `:1` or `:-1`, and a keyword beginning with `::` is
+/// auto-resolved, its namespace is left for the reader of the data to fill in.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, Keyword};
+///
+/// let edn = Edn::from_str("{:user/id 42}").unwrap();
+/// let keyword = edn.map_iter().unwrap().next().unwrap().0.to_keyword().unwrap();
+///
+/// assert_eq!(keyword.namespace(), Some("user"));
+/// assert_eq!(keyword.name(), "id");
+/// assert_eq!(keyword, Keyword::new(Some("user"), "id").unwrap());
+/// assert_eq!(keyword.to_string(), ":user/id");
+/// assert!(Keyword::from_str("::id").unwrap().is_auto_resolved());
+/// assert!(Keyword::from_str(":::id").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Keyword {
+    auto_resolved: bool,
+    symbol: Symbol,
+}
+
+impl Keyword {
+    /// # Errors
+    ///
+    /// `Error::ParseEdn` when `namespace` or `name` do not follow the keyword grammar.
+    pub fn new(namespace: Option<&str>, name: &str) -> Result<Self, Error> {
+        let symbol = Symbol::from_parts(namespace, name, true).map_err(Error::ParseEdn)?;
+        Ok(Self {
+            auto_resolved: false,
+            symbol,
+        })
+    }
+
+    #[must_use]
+    pub fn namespace(&self) -> Option<&str> {
+        self.symbol.namespace()
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.symbol.name()
+    }
+
+    /// `true` for `::id`, which Clojure resolves to the namespace where it is read.
+    #[must_use]
+    pub const fn is_auto_resolved(&self) -> bool {
+        self.auto_resolved
+    }
+
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid keyword {s:?}");
+        let symbol = s.strip_prefix(':').ok_or_else(invalid)?;
+        let (auto_resolved, symbol) = symbol
+            .strip_prefix(':')
+            .map_or((false, symbol), |symbol| (true, symbol));
+        let (namespace, name) = split_namespace(symbol);
+        let symbol = Symbol::from_parts(namespace, name, true).map_err(|_| invalid())?;
+        Ok(Self {
+            auto_resolved,
+            symbol,
+        })
+    }
+}
+
+// The namespace ends at the first `/`, unless the symbol is `/` or ends in a `/` name.
+fn split_namespace(s: &str) -> (Option<&str>, &str) {
+    if let Some(namespace) = s.strip_suffix("//") {
+        return (Some(namespace), "/");
+    }
+    match s.split_once('/') {
+        Some((namespace, name)) if s != "/" => (Some(namespace), name),
+        _ => (None, s),
+    }
+}
+
+fn is_valid_part(part: &str, leading_digit: bool) -> bool {
+    let mut chars = part.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let second = chars.clone().next();
+    // Like Clojure, any non-ASCII char is accepted, so `λ` and `∞` are symbols. Keyword names
+    // cannot be mistaken for numbers, so `:-1` is a keyword like `:1`
+    let valid_first = first.is_ascii_alphabetic()
+        || !first.is_ascii()
+        || (leading_digit && first.is_ascii_digit())
+        || (SYMBOL_CHARS.contains(first)
+            && (leading_digit
+                || !(matches!(first, '-' | '+' | '.')
+                    && second.is_some_and(|c| c.is_ascii_digit()))));
+    valid_first
+        && !part.contains("::")
+        && chars.all(|c| {
+            c.is_ascii_alphanumeric()
+                || !c.is_ascii()
+                || SYMBOL_CHARS.contains(c)
+                || c == ':'
+                || c == '#'
+        })
+}
+
+impl FromStr for Symbol {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).map_err(Error::ParseEdn)
+    }
+}
+
+impl FromStr for Keyword {
+    type Err = Error;
+
+    /// Parses a keyword with its leading `:`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).map_err(Error::ParseEdn)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.namespace {
+            Some(namespace) => write!(f, "{namespace}/{}", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let colons = if self.auto_resolved { "::" } else { ":" };
+        write!(f, "{colons}{}", self.symbol)
+    }
+}
+
+impl From<Symbol> for Edn {
+    fn from(symbol: Symbol) -> Self {
+        Self::Symbol(symbol.to_string())
+    }
+}
+
+impl From<Keyword> for Edn {
+    fn from(keyword: Keyword) -> Self {
+        Self::Key(keyword.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validates_symbols() {
+        for s in [
+            "a",
+            "/",
+            "-",
+            "+",
+            ".",
+            "...",
+            "-a",
+            "+a",
+            ".a",
+            "a1",
+            "a/b",
+            "a.b/c-d",
+            "a:b#c'",
+            "<=",
+            "clojure.core//",
+            "é",
+            "'",
+            "'a",
+            "λ",
+            "∞",
+        ] {
+            assert!(Symbol::parse(s).is_ok(), "{s}");
+        }
+        for s in [
+            "", "1a", "-1", "+1", ".1", "a/", "/a", "a/b/c", "a//b", "a/1", "#a", ":a", "a@b",
+            "a\\b", "a b", "a::b", "a/b::",
+        ] {
+            assert!(Symbol::parse(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn validates_keywords() {
+        for s in [
+            ":a",
+            ":/",
+            ":1",
+            ":a/b",
+            ":a.b/c-d",
+            ":a:b",
+            ":user/1",
+            "::a",
+            "::alias/a",
+            ":-1",
+            ":+1",
+            ":.5",
+        ] {
+            assert!(Keyword::parse(s).is_ok(), "{s}");
+        }
+        for s in [
+            "a", ":", "::", ":::a", ":a/", ":/a", ":a/b/c", ":a//", ":1/a", ":-1/a", ":a::b",
+            "::a::b", ":a::b/c", ":a/b::c",
+        ] {
+            assert!(Keyword::parse(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn splits_namespaces() {
+        let keyword = Keyword::parse(":user/id").unwrap();
+        assert_eq!((keyword.namespace(), keyword.name()), (Some("user"), "id"));
+
+        let symbol = Symbol::parse("clojure.core//").unwrap();
+        assert_eq!(
+            (symbol.namespace(), symbol.name()),
+            (Some("clojure.core"), "/")
+        );
+
+        let symbol = Symbol::parse("/").unwrap();
+        assert_eq!((symbol.namespace(), symbol.name()), (None, "/"));
+    }
+
+    #[test]
+    fn displays_as_written() {
+        for s in [":a", ":user/id", ":/", "::id"] {
+            assert_eq!(Keyword::parse(s).unwrap().to_string(), s);
+        }
+        for s in ["a", "user/id", "/", "clojure.core//"] {
+            assert_eq!(Symbol::parse(s).unwrap().to_string(), s);
+        }
+    }
+}
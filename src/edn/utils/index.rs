@@ -1,9 +1,9 @@
-use alloc::borrow::ToOwned;
-use alloc::string::{String, ToString};
+use alloc::string::String;
 use core::convert::TryFrom;
 use core::{fmt, ops};
 
-use crate::edn::{Edn, Map};
+use crate::deserialize::parse;
+use crate::edn::{Edn, Map, OrderedMap};
 
 /// This is a Copy of [`Serde_json::index`](https://docs.serde.rs/src/serde_json/value/index.rs.html)
 pub trait Index: private::Sealed {
@@ -14,12 +14,47 @@ pub trait Index: private::Sealed {
     fn index_or_insert<'v>(&self, v: &'v mut Edn) -> &'v mut Edn;
 }
 
+// `edn!` builds integer keys as `Int` while the parser reads them as `UInt`, so integer
+// lookups fall back to the other representation.
+fn int_alias(key: &Edn) -> Option<Edn> {
+    match *key {
+        Edn::Int(i) => u64::try_from(i).ok().map(Edn::UInt),
+        Edn::UInt(u) => i64::try_from(u).ok().map(Edn::Int),
+        _ => None,
+    }
+}
+
+fn map_get<'v>(map: &'v Map, key: &Edn) -> Option<&'v Edn> {
+    map.0.get(key).or_else(|| map.0.get(&int_alias(key)?))
+}
+
+fn map_get_mut<'v>(map: &'v mut Map, key: &Edn) -> Option<&'v mut Edn> {
+    if map.0.contains_key(key) {
+        map.0.get_mut(key)
+    } else {
+        map.0.get_mut(&int_alias(key)?)
+    }
+}
+
+fn ordered_map_get<'v>(map: &'v OrderedMap, key: &Edn) -> Option<&'v Edn> {
+    map.get(key).or_else(|| map.get(&int_alias(key)?))
+}
+
+fn ordered_map_get_mut<'v>(map: &'v mut OrderedMap, key: &Edn) -> Option<&'v mut Edn> {
+    if map.get(key).is_some() {
+        map.get_mut(key)
+    } else {
+        map.get_mut(&int_alias(key)?)
+    }
+}
+
 impl Index for usize {
     fn index_into<'v>(&self, v: &'v Edn) -> Option<&'v Edn> {
         match *v {
             Edn::Vector(ref vec) => vec.0.get(*self),
             Edn::List(ref vec) => vec.0.get(*self),
-            Edn::Map(ref map) => map.0.get(&self.to_string()),
+            Edn::Map(ref map) => map_get(map, &Edn::UInt(*self as u64)),
+            Edn::OrderedMap(ref map) => ordered_map_get(map, &Edn::UInt(*self as u64)),
             _ => None,
         }
     }
@@ -27,7 +62,8 @@ impl Index for usize {
         match *v {
             Edn::Vector(ref mut vec) => vec.0.get_mut(*self),
             Edn::List(ref mut vec) => vec.0.get_mut(*self),
-            Edn::Map(ref mut map) => map.0.get_mut(&self.to_string()),
+            Edn::Map(ref mut map) => map_get_mut(map, &Edn::UInt(*self as u64)),
+            Edn::OrderedMap(ref mut map) => ordered_map_get_mut(map, &Edn::UInt(*self as u64)),
             _ => None,
         }
     }
@@ -44,16 +80,30 @@ impl Index for usize {
     }
 }
 
+/// The map key a `str` index stands for: its EDN text when it holds a single element, so
+/// `":a"` is the keyword `:a`, `"\"a\""` the string `"a"`, `"a"` the symbol `a` and `"[1 2]"`
+/// the vector `[1 2]`. Any other text, like `"hello world"` or `":a :b"`, is no key at all.
+pub(crate) fn str_key(key: &str) -> Option<Edn> {
+    parse::parse_single(key)
+}
+
+/// A `str` index is read as the EDN text of a map key, see [`str_key`]. A `str` that is not
+/// exactly one EDN element misses, and panics when assigned to, so a string key is indexed
+/// with `Edn::Str`, `edn[Edn::Str("hello world".to_string())]`.
 impl Index for str {
     fn index_into<'v>(&self, v: &'v Edn) -> Option<&'v Edn> {
+        let key = str_key(self)?;
         match *v {
-            Edn::Map(ref map) => map.0.get(self),
+            Edn::Map(ref map) => map_get(map, &key),
+            Edn::OrderedMap(ref map) => ordered_map_get(map, &key),
             _ => None,
         }
     }
     fn index_into_mut<'v>(&self, v: &'v mut Edn) -> Option<&'v mut Edn> {
+        let key = str_key(self)?;
         match *v {
-            Edn::Map(ref mut map) => map.0.get_mut(self),
+            Edn::Map(ref mut map) => map_get_mut(map, &key),
+            Edn::OrderedMap(ref mut map) => ordered_map_get_mut(map, &key),
             _ => None,
         }
     }
@@ -61,8 +111,17 @@ impl Index for str {
         if *v == Edn::Nil {
             *v = Edn::Map(Map::new(alloc::collections::BTreeMap::new()));
         }
+        let Some(key) = str_key(self) else {
+            panic!("cannot access key {self:?}, it is not a single EDN element")
+        };
         match *v {
-            Edn::Map(ref mut map) => map.0.entry(self.to_owned()).or_insert(Edn::Nil),
+            Edn::Map(ref mut map) => map.0.entry(key).or_insert(Edn::Nil),
+            Edn::OrderedMap(ref mut map) => {
+                if map.get(&key).is_none() {
+                    map.insert(key.clone(), Edn::Nil);
+                }
+                map.get_mut(&key).unwrap()
+            }
             _ => panic!("cannot access key {:?} in EDN {}", self, Type(v)),
         }
     }
@@ -82,11 +141,11 @@ impl Index for String {
 
 impl Index for Edn {
     fn index_into<'v>(&self, v: &'v Edn) -> Option<&'v Edn> {
-        let key = self.to_string();
         let index = self.to_uint();
 
         match (v, index) {
-            (Self::Map(map), _) => map.0.get(&key),
+            (Self::Map(map), _) => map_get(map, self),
+            (Self::OrderedMap(map), _) => ordered_map_get(map, self),
             (Self::List(_) | Self::Vector(_), Some(idx)) => {
                 // A panic is expected behavior when trying to index beyond usize
                 let idx = usize::try_from(idx).unwrap();
@@ -145,12 +204,14 @@ impl fmt::Display for Type<'_> {
             #[cfg(feature = "sets")]
             Edn::Set(_) => formatter.write_str("set"),
             Edn::List(_) => formatter.write_str("list"),
-            Edn::Map(_) => formatter.write_str("map"),
+            Edn::Map(_) | Edn::OrderedMap(_) => formatter.write_str("map"),
             Edn::Key(_) => formatter.write_str("key"),
             Edn::Char(_) => formatter.write_str("char"),
             Edn::Symbol(_) => formatter.write_str("symbol"),
             Edn::Double(_) => formatter.write_str("double"),
             Edn::Rational(_) => formatter.write_str("rational"),
+            Edn::BigInt(_) => formatter.write_str("bigint"),
+            Edn::Decimal(_) => formatter.write_str("decimal"),
             Edn::Tagged(_, _) => formatter.write_str("tagged-element"),
         }
     }
@@ -0,0 +1,140 @@
+use alloc::boxed::Box;
+use alloc::fmt;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::str::FromStr;
+
+use crate::edn::{Edn, Error};
+
+/// `Uuid` is the value of a `#uuid` tagged element, a 128-bit UUID written as
+/// `"xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx"` in hexadecimal.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, Uuid};
+///
+/// let edn = Edn::from_str("#uuid \"AF6D8699-F442-4DFD-8B26-37D80543186B\"").unwrap();
+/// let uuid = edn.to_uuid().unwrap();
+///
+/// assert_eq!(uuid.as_u128(), 0xaf6d_8699_f442_4dfd_8b26_37d8_0543_186b);
+/// assert_eq!(uuid.to_string(), "af6d8699-f442-4dfd-8b26-37d80543186b");
+/// assert!(Edn::from_str("#uuid \"af6d8699\"").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid(u128);
+
+impl Uuid {
+    #[must_use]
+    pub const fn from_u128(uuid: u128) -> Self {
+        Self(uuid)
+    }
+
+    #[must_use]
+    pub const fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// Big-endian bytes, in the order they are written.
+    #[must_use]
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(u128::from_be_bytes(bytes))
+    }
+
+    /// Big-endian bytes, in the order they are written.
+    #[must_use]
+    pub const fn to_bytes(&self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        parse_uuid(s).ok_or_else(|| format!("invalid UUID {s:?}"))
+    }
+}
+
+fn parse_uuid(s: &str) -> Option<Uuid> {
+    if s.len() != 36 {
+        return None;
+    }
+    let mut uuid = 0;
+    for (i, c) in s.chars().enumerate() {
+        if matches!(i, 8 | 13 | 18 | 23) {
+            if c != '-' {
+                return None;
+            }
+        } else {
+            uuid = uuid << 4 | u128::from(c.to_digit(16)?);
+        }
+    }
+    Some(Uuid(uuid))
+}
+
+impl FromStr for Uuid {
+    type Err = Error;
+
+    /// Parses the hexadecimal form of a `#uuid`, without the tag, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).map_err(Error::ParseEdn)
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = format!("{:032x}", self.0);
+        write!(
+            f,
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+}
+
+impl From<Uuid> for Edn {
+    fn from(uuid: Uuid) -> Self {
+        Self::Tagged(String::from("uuid"), Box::new(Self::Str(uuid.to_string())))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<Uuid> for uuid::Uuid {
+    fn from(uuid: Uuid) -> Self {
+        Self::from_u128(uuid.0)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for Uuid {
+    fn from(uuid: uuid::Uuid) -> Self {
+        Self(uuid.as_u128())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rejects_malformed_uuids() {
+        for s in [
+            "af6d8699f4424dfd8b2637d80543186b",
+            "af6d8699-f442-4dfd-8b26-37d80543186",
+            "af6d8699-f442-4dfd-8b26_37d80543186b",
+            "gf6d8699-f442-4dfd-8b26-37d80543186b",
+            "af6d8699-f442-4dfd-8b26-37d80543186b0",
+        ] {
+            assert_eq!(parse_uuid(s), None, "{s}");
+        }
+    }
+
+    #[test]
+    fn round_trips_bytes() {
+        let uuid = Uuid::parse("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+
+        assert_eq!(uuid.to_bytes()[..3], [0x00, 0x11, 0x22]);
+        assert_eq!(Uuid::from_bytes(uuid.to_bytes()), uuid);
+        assert_eq!(uuid.to_string(), "00112233-4455-6677-8899-aabbccddeeff");
+    }
+}
@@ -0,0 +1,921 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+#[cfg(feature = "sets")]
+use crate::edn::Set;
+use crate::edn::{Edn, Error, Inst, List, Map, Uuid, Vector};
+
+// Single byte integers are `-1` and `0` to `63`, larger ones start with a code between
+// `0x40` and `0x7F` holding their top bits, or `INT` followed by 8 bytes
+const INT_PACKED_1_END: u8 = 0x40;
+const INT_PACKED_2_ZERO: u8 = 0x50;
+const INT_PACKED_3_ZERO: u8 = 0x68;
+const INT_PACKED_4_ZERO: u8 = 0x72;
+const INT_PACKED_5_ZERO: u8 = 0x76;
+const INT_PACKED_6_ZERO: u8 = 0x7A;
+const INT_PACKED_7_ZERO: u8 = 0x7E;
+const PRIORITY_CACHE_PACKED_START: u8 = 0x80;
+const PRIORITY_CACHE_PACKED_END: u8 = 0xA0;
+const STRUCT_CACHE_PACKED_START: u8 = 0xA0;
+const STRUCT_CACHE_PACKED_END: u8 = 0xB0;
+const LONG_ARRAY: u8 = 0xB0;
+const DOUBLE_ARRAY: u8 = 0xB1;
+const BOOLEAN_ARRAY: u8 = 0xB2;
+const INT_ARRAY: u8 = 0xB3;
+const FLOAT_ARRAY: u8 = 0xB4;
+const OBJECT_ARRAY: u8 = 0xB5;
+const MAP: u8 = 0xC0;
+const SET: u8 = 0xC1;
+const UUID: u8 = 0xC3;
+const REGEX: u8 = 0xC4;
+const URI: u8 = 0xC5;
+const BIGINT: u8 = 0xC6;
+const BIGDEC: u8 = 0xC7;
+const INST: u8 = 0xC8;
+const SYM: u8 = 0xC9;
+const KEY: u8 = 0xCA;
+const GET_PRIORITY_CACHE: u8 = 0xCC;
+const PUT_PRIORITY_CACHE: u8 = 0xCD;
+const FOOTER: u8 = 0xCF;
+const BYTES_PACKED_LENGTH_START: u8 = 0xD0;
+const BYTES_PACKED_LENGTH_END: u8 = 0xD8;
+const BYTES_CHUNK: u8 = 0xD8;
+const BYTES: u8 = 0xD9;
+const STRING_PACKED_LENGTH_START: u8 = 0xDA;
+const STRING_PACKED_LENGTH_END: u8 = 0xE2;
+const STRING_CHUNK: u8 = 0xE2;
+const STRING: u8 = 0xE3;
+const LIST_PACKED_LENGTH_START: u8 = 0xE4;
+const LIST_PACKED_LENGTH_END: u8 = 0xEC;
+const LIST: u8 = 0xEC;
+const BEGIN_CLOSED_LIST: u8 = 0xED;
+const BEGIN_OPEN_LIST: u8 = 0xEE;
+const STRUCTTYPE: u8 = 0xEF;
+const STRUCT: u8 = 0xF0;
+const META: u8 = 0xF1;
+const TRUE: u8 = 0xF5;
+const FALSE: u8 = 0xF6;
+const NULL: u8 = 0xF7;
+const INT: u8 = 0xF8;
+const FLOAT: u8 = 0xF9;
+const DOUBLE: u8 = 0xFA;
+const DOUBLE_0: u8 = 0xFB;
+const DOUBLE_1: u8 = 0xFC;
+const END_COLLECTION: u8 = 0xFD;
+const RESET_CACHES: u8 = 0xFE;
+const INT_MINUS_ONE: u8 = 0xFF;
+
+// Written after the last object by `FressianWriter.writeFooter`
+const FOOTER_MAGIC: [u8; 4] = [FOOTER; 4];
+// Deeper blobs are rejected instead of overflowing the stack
+const MAX_DEPTH: usize = 512;
+
+/// Writes `edn` as Fressian, the binary format of Datomic and `clojure.data.fressian`.
+///
+/// Vectors and lists are both written as lists, maps and sets wrap the list of their entries
+/// or elements, `#inst` and `#uuid` elements use their own codes, and keyword and symbol
+/// names go through the priority cache. Characters and ratios are the `char` and `ratio`
+/// structs of `clojure.data.fressian`, and any other tagged element is a struct of one field
+/// under its tag, cached after its first use.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, fressian};
+///
+/// let edn = Edn::from_str("[:a/b :a/b \"hi\" -1 1.0]").unwrap();
+///
+/// assert_eq!(
+///     fressian::to_bytes(&edn),
+///     [0xE9, 0xCA, 0xCD, 0xDB, b'a', 0xCD, 0xDB, b'b', 0xCA, 0x80, 0x81, 0xDC, b'h', b'i', 0xFF, 0xFC]
+/// );
+/// assert_eq!(fressian::from_bytes(&fressian::to_bytes(&edn)).unwrap(), edn);
+/// ```
+#[must_use]
+pub fn to_bytes(edn: &Edn) -> Vec<u8> {
+    let mut writer = Writer {
+        out: Vec::new(),
+        priority_cache: BTreeMap::new(),
+        struct_cache: BTreeMap::new(),
+    };
+    writer.write(edn);
+    writer.out
+}
+
+/// Reads a single Fressian object, optionally followed by its footer, into `Edn`.
+///
+/// Integers become `Edn::UInt` when positive, lists and arrays vectors, `inst` and `uuid`
+/// `#inst` and `#uuid` elements, and byte arrays, uris and regexes are tagged `#bytes`,
+/// `#uri` and `#regex`. Structs with a tag other than `char` and `ratio` become tagged
+/// elements, holding their field or a vector of their fields.
+///
+/// ```rust
+/// use edn_rs::{Edn, fressian};
+///
+/// // STRUCTTYPE "my/point" 2 1 2, then the cached struct type 0 with 3 4
+/// let bytes = [
+///     0xE6, 0xEF, 0xE3, 0x08, b'm', b'y', b'/', b'p', b'o', b'i', b'n', b't', 0x02, 0x01, 0x02,
+///     0xA0, 0x03, 0x04,
+/// ];
+/// let edn = fressian::from_bytes(&bytes).unwrap();
+///
+/// assert_eq!(edn.to_string(), "[#my/point [1 2] #my/point [3 4]]");
+/// ```
+///
+/// # Errors
+///
+/// `EdnError::Fressian` when `bytes` end early, hold an unknown code or a cache reference to
+/// nothing, an object of the wrong type, like a map of an odd number of elements, or trailing
+/// bytes.
+pub fn from_bytes(bytes: &[u8]) -> Result<Edn, Error> {
+    let mut reader = Reader {
+        bytes,
+        pos: 0,
+        priority_cache: Vec::new(),
+        struct_cache: Vec::new(),
+    };
+    let edn = reader.read(0)?;
+    let rest = &bytes[reader.pos..];
+    if rest.is_empty() || rest.starts_with(&FOOTER_MAGIC) {
+        Ok(edn)
+    } else {
+        Err(reader.error("unexpected trailing bytes"))
+    }
+}
+
+struct Writer {
+    out: Vec<u8>,
+    priority_cache: BTreeMap<String, usize>,
+    struct_cache: BTreeMap<String, usize>,
+}
+
+impl Writer {
+    fn int(&mut self, i: i64) {
+        let packed = |zero: u8, shift: u32| (i64::from(zero) + (i >> shift)).to_be_bytes()[7];
+        let bytes = i.to_be_bytes();
+        let (code, raw) = match (if i < 0 { !i } else { i }).leading_zeros() {
+            0..=14 => {
+                self.out.push(INT);
+                self.out.extend(bytes);
+                return;
+            }
+            15..=22 => (packed(INT_PACKED_7_ZERO, 48), &bytes[2..]),
+            23..=30 => (packed(INT_PACKED_6_ZERO, 40), &bytes[3..]),
+            31..=38 => (packed(INT_PACKED_5_ZERO, 32), &bytes[4..]),
+            39..=44 => (packed(INT_PACKED_4_ZERO, 24), &bytes[5..]),
+            45..=51 => (packed(INT_PACKED_3_ZERO, 16), &bytes[6..]),
+            _ if i < -1 || i >= i64::from(INT_PACKED_1_END) => {
+                (packed(INT_PACKED_2_ZERO, 8), &bytes[7..])
+            }
+            _ => (bytes[7], &bytes[8..]),
+        };
+        self.out.push(code);
+        self.out.extend_from_slice(raw);
+    }
+
+    fn count(&mut self, count: usize) {
+        self.int(i64::try_from(count).unwrap_or(i64::MAX));
+    }
+
+    fn double(&mut self, d: f64) {
+        if d.to_bits() == 0 {
+            self.out.push(DOUBLE_0);
+        } else if d.to_bits() == 1.0f64.to_bits() {
+            self.out.push(DOUBLE_1);
+        } else {
+            self.out.push(DOUBLE);
+            self.out.extend(d.to_be_bytes());
+        }
+    }
+
+    fn string(&mut self, s: &str) {
+        let encoded = encode_string(s);
+        if encoded.len() < usize::from(STRING_PACKED_LENGTH_END - STRING_PACKED_LENGTH_START) {
+            self.out
+                .push(STRING_PACKED_LENGTH_START + encoded.len().to_be_bytes()[7]);
+        } else {
+            self.out.push(STRING);
+            self.count(encoded.len());
+        }
+        self.out.extend(encoded);
+    }
+
+    // Keyword and symbol parts, the first use is put in the priority cache and the following
+    // ones refer to it
+    fn cached_string(&mut self, s: Option<&str>) {
+        match s {
+            None => self.out.push(NULL),
+            Some("") => self.string(""),
+            Some(s) => match self.priority_cache.get(s) {
+                Some(&index)
+                    if index
+                        < usize::from(PRIORITY_CACHE_PACKED_END - PRIORITY_CACHE_PACKED_START) =>
+                {
+                    self.out
+                        .push(PRIORITY_CACHE_PACKED_START + index.to_be_bytes()[7]);
+                }
+                Some(&index) => {
+                    self.out.push(GET_PRIORITY_CACHE);
+                    self.count(index);
+                }
+                None => {
+                    let index = self.priority_cache.len();
+                    self.priority_cache.insert(s.to_string(), index);
+                    self.out.push(PUT_PRIORITY_CACHE);
+                    self.string(s);
+                }
+            },
+        }
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        if bytes.len() < usize::from(BYTES_PACKED_LENGTH_END - BYTES_PACKED_LENGTH_START) {
+            self.out
+                .push(BYTES_PACKED_LENGTH_START + bytes.len().to_be_bytes()[7]);
+        } else {
+            self.out.push(BYTES);
+            self.count(bytes.len());
+        }
+        self.out.extend_from_slice(bytes);
+    }
+
+    fn list<'a>(&mut self, items: impl ExactSizeIterator<Item = &'a Edn>) {
+        if items.len() < usize::from(LIST_PACKED_LENGTH_END - LIST_PACKED_LENGTH_START) {
+            self.out
+                .push(LIST_PACKED_LENGTH_START + items.len().to_be_bytes()[7]);
+        } else {
+            self.out.push(LIST);
+            self.count(items.len());
+        }
+        for item in items {
+            self.write(item);
+        }
+    }
+
+    // The struct type is written with its tag and number of fields the first time, then as
+    // its index in the struct cache
+    fn struct_type(&mut self, tag: &str, fields: usize) {
+        match self.struct_cache.get(tag) {
+            Some(&index)
+                if index < usize::from(STRUCT_CACHE_PACKED_END - STRUCT_CACHE_PACKED_START) =>
+            {
+                self.out
+                    .push(STRUCT_CACHE_PACKED_START + index.to_be_bytes()[7]);
+            }
+            Some(&index) => {
+                self.out.push(STRUCT);
+                self.count(index);
+            }
+            None => {
+                let index = self.struct_cache.len();
+                self.struct_cache.insert(tag.to_string(), index);
+                self.out.push(STRUCTTYPE);
+                self.string(tag);
+                self.count(fields);
+            }
+        }
+    }
+
+    fn big_integer(&mut self, digits: &str) {
+        match to_twos_complement(digits) {
+            Some(bytes) => {
+                self.out.push(BIGINT);
+                self.bytes(&bytes);
+            }
+            None => self.string(digits),
+        }
+    }
+
+    fn named(&mut self, code: u8, name: &str) {
+        let (namespace, name) = match name.split_once('/') {
+            Some((namespace, name)) if !namespace.is_empty() && !name.is_empty() => {
+                (Some(namespace), name)
+            }
+            _ => (None, name),
+        };
+        self.out.push(code);
+        self.cached_string(namespace);
+        self.cached_string(Some(name));
+    }
+
+    fn write(&mut self, edn: &Edn) {
+        match edn {
+            Edn::Nil | Edn::Empty => self.out.push(NULL),
+            Edn::Bool(true) => self.out.push(TRUE),
+            Edn::Bool(false) => self.out.push(FALSE),
+            Edn::Int(i) => self.int(*i),
+            Edn::UInt(u) => match i64::try_from(*u) {
+                Ok(i) => self.int(i),
+                Err(_) => self.big_integer(&u.to_string()),
+            },
+            Edn::Double(_) => self.double(edn.to_float().unwrap_or_default()),
+            Edn::BigInt(n) => self.big_integer(n),
+            Edn::Decimal(d) => match to_unscaled(d) {
+                Some((unscaled, scale)) => {
+                    self.out.push(BIGDEC);
+                    self.bytes(&unscaled);
+                    self.int(scale);
+                }
+                None => self.string(d),
+            },
+            Edn::Rational(r) => {
+                let (numerator, denominator) = r.split_once('/').unwrap_or((r, "1"));
+                self.struct_type("ratio", 2);
+                self.big_integer(numerator);
+                self.big_integer(denominator);
+            }
+            Edn::Char(c) => {
+                self.struct_type("char", 1);
+                self.int(i64::from(u32::from(*c)));
+            }
+            Edn::Str(s) => self.string(s),
+            Edn::Key(k) => self.named(KEY, k.strip_prefix(':').unwrap_or(k)),
+            Edn::Symbol(s) => self.named(SYM, s),
+            Edn::Vector(Vector(items)) | Edn::List(List(items)) => self.list(items.iter()),
+            #[cfg(feature = "sets")]
+            Edn::Set(Set(items)) => {
+                self.out.push(SET);
+                self.list(items.iter());
+            }
+            Edn::Map(_) | Edn::OrderedMap(_) => {
+                let entries = edn.map_entries().unwrap_or_default();
+                self.out.push(MAP);
+                self.list(
+                    entries
+                        .iter()
+                        .flat_map(|&entry| <[&Edn; 2]>::from(entry))
+                        .collect::<Vec<_>>()
+                        .into_iter(),
+                );
+            }
+            Edn::Tagged(tag, element) => self.tagged(tag, element),
+        }
+    }
+
+    fn tagged(&mut self, tag: &str, element: &Edn) {
+        match (tag, element) {
+            ("inst", Edn::Str(s)) if Inst::parse(s).is_ok() => {
+                let millis = Inst::parse(s).map_or(0, |inst| inst.unix_timestamp_millis());
+                self.out.push(INST);
+                self.int(millis);
+            }
+            ("uuid", Edn::Str(s)) if Uuid::from_str(s).is_ok() => {
+                let uuid = Uuid::from_str(s)
+                    .map(|uuid| uuid.to_bytes())
+                    .unwrap_or_default();
+                self.out.push(UUID);
+                self.bytes(&uuid);
+            }
+            ("uri", Edn::Str(s)) => {
+                self.out.push(URI);
+                self.string(s);
+            }
+            ("regex", Edn::Str(s)) => {
+                self.out.push(REGEX);
+                self.string(s);
+            }
+            ("bytes", Edn::Vector(Vector(items)))
+                if items.iter().all(|item| matches!(item, Edn::UInt(0..=255))) =>
+            {
+                let bytes = items
+                    .iter()
+                    .filter_map(|item| u8::try_from(item.to_uint()?).ok())
+                    .collect::<Vec<_>>();
+                self.bytes(&bytes);
+            }
+            _ => {
+                self.struct_type(tag, 1);
+                self.write(element);
+            }
+        }
+    }
+}
+
+// Fressian writes each UTF-16 code unit as 1 to 3 bytes, so characters outside of the
+// basic plane take 6 bytes instead of the 4 of UTF-8
+fn encode_string(s: &str) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(s.len());
+    for unit in s.encode_utf16() {
+        let [hi, lo] = unit.to_be_bytes();
+        match unit {
+            0..=0x7F => encoded.push(lo),
+            0x80..=0x7FF => encoded.extend([0xC0 | (hi << 2) | (lo >> 6), 0x80 | (lo & 0x3F)]),
+            _ => encoded.extend([
+                0xE0 | (hi >> 4),
+                0x80 | ((hi & 0x0F) << 2) | (lo >> 6),
+                0x80 | (lo & 0x3F),
+            ]),
+        }
+    }
+    encoded
+}
+
+fn decode_string(bytes: &[u8]) -> Option<String> {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut bytes = bytes.iter().map(|&b| u16::from(b));
+    while let Some(b) = bytes.next() {
+        let mut continuation = || bytes.next().filter(|c| c & 0xC0 == 0x80).map(|c| c & 0x3F);
+        units.push(match b >> 4 {
+            0..=7 => b,
+            12 | 13 => ((b & 0x1F) << 6) | continuation()?,
+            14 => ((b & 0x0F) << 12) | (continuation()? << 6) | continuation()?,
+            _ => return None,
+        });
+    }
+    String::from_utf16(&units).ok()
+}
+
+// Big-endian two's complement of the decimal `digits`, in as few bytes as Java's
+// `BigInteger.toByteArray`
+fn to_twos_complement(digits: &str) -> Option<Vec<u8>> {
+    let (negative, digits) = digits.strip_prefix('-').map_or_else(
+        || (false, digits.strip_prefix('+').unwrap_or(digits)),
+        |digits| (true, digits),
+    );
+    if digits.is_empty() || !digits.bytes().all(|d| d.is_ascii_digit()) {
+        return None;
+    }
+    // Little-endian while it is built
+    let mut bytes: Vec<u8> = Vec::new();
+    for digit in digits.bytes() {
+        let mut carry = u32::from(digit - b'0');
+        for byte in &mut bytes {
+            let value = u32::from(*byte) * 10 + carry;
+            *byte = value.to_le_bytes()[0];
+            carry = value >> 8;
+        }
+        if carry > 0 {
+            bytes.push(carry.to_le_bytes()[0]);
+        }
+    }
+    if negative && !bytes.is_empty() {
+        let mut carry = true;
+        for byte in &mut bytes {
+            (*byte, carry) = (!*byte).overflowing_add(u8::from(carry));
+        }
+        if bytes.last().is_some_and(|b| b & 0x80 == 0) {
+            bytes.push(0xFF);
+        }
+    } else if bytes.last().is_none_or(|b| b & 0x80 != 0) {
+        bytes.push(0);
+    }
+    bytes.reverse();
+    Some(bytes)
+}
+
+fn from_twos_complement(bytes: &[u8]) -> String {
+    let negative = bytes.first().is_some_and(|b| b & 0x80 != 0);
+    let mut magnitude = bytes.to_vec();
+    if negative {
+        let mut carry = true;
+        for byte in magnitude.iter_mut().rev() {
+            (*byte, carry) = (!*byte).overflowing_add(u8::from(carry));
+        }
+    }
+    let mut digits = Vec::new();
+    while magnitude.iter().any(|&b| b != 0) {
+        let mut remainder = 0;
+        for byte in &mut magnitude {
+            let value = (remainder << 8) | u32::from(*byte);
+            *byte = (value / 10).to_le_bytes()[0];
+            remainder = value % 10;
+        }
+        digits.push(char::from_digit(remainder, 10).unwrap_or('0'));
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    if negative {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+// `1.50` is the unscaled `150` with a scale of `2`, `15e2` is `15` with a scale of `-2`
+fn to_unscaled(decimal: &str) -> Option<(Vec<u8>, i64)> {
+    let (mantissa, exponent) = match decimal.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().ok()?),
+        None => (decimal, 0),
+    };
+    let (int, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let scale = i64::try_from(fraction.len()).ok()?.checked_sub(exponent)?;
+    Some((to_twos_complement(&format!("{int}{fraction}"))?, scale))
+}
+
+// Like `BigDecimal`, the scale is an `i32`. A fraction is only padded with a few zeros, past
+// that the decimal is written with an exponent, `1e-40` rather than forty digits
+fn from_unscaled(unscaled: &[u8], scale: i32) -> String {
+    const MAX_PADDING: usize = 6;
+    let digits = from_twos_complement(unscaled);
+    let (sign, digits) = digits
+        .strip_prefix('-')
+        .map_or(("", digits.as_str()), |digits| ("-", digits));
+    match usize::try_from(scale) {
+        Ok(0) => format!("{sign}{digits}"),
+        Ok(scale) if scale <= digits.len() + MAX_PADDING => {
+            let digits = format!("{digits:0>width$}", width = scale + 1);
+            let (int, fraction) = digits.split_at(digits.len() - scale);
+            format!("{sign}{int}.{fraction}")
+        }
+        Ok(scale) => format!("{sign}{digits}e-{scale}"),
+        Err(_) => format!("{sign}{digits}e{}", scale.unsigned_abs()),
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    priority_cache: Vec<Edn>,
+    struct_cache: Vec<(String, usize)>,
+}
+
+impl<'a> Reader<'a> {
+    fn error(&self, message: &str) -> Error {
+        Error::Fressian(format!("{message} at offset {}", self.pos))
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or_else(|| self.error("unexpected end of input"))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        let taken = self
+            .pos
+            .checked_add(n)
+            .and_then(|end| self.bytes.get(self.pos..end))
+            .ok_or_else(|| self.error("unexpected end of input"))?;
+        self.pos += n;
+        Ok(taken)
+    }
+
+    fn raw_int(&mut self, n: usize) -> Result<i64, Error> {
+        Ok(self
+            .take(n)?
+            .iter()
+            .fold(0, |int, &byte| (int << 8) | i64::from(byte)))
+    }
+
+    fn packed_int(&mut self, code: u8, zero: u8, n: usize) -> Result<i64, Error> {
+        let top = i64::from(code) - i64::from(zero);
+        Ok((top << (8 * n)) | self.raw_int(n)?)
+    }
+
+    fn int(&mut self, depth: usize) -> Result<i64, Error> {
+        match self.read(depth)? {
+            Edn::Int(i) => Ok(i),
+            Edn::UInt(u) => i64::try_from(u).map_err(|_| self.error("integer out of range")),
+            _ => Err(self.error("expected an integer")),
+        }
+    }
+
+    fn count(&mut self, depth: usize) -> Result<usize, Error> {
+        let count = self.int(depth)?;
+        usize::try_from(count).map_err(|_| self.error("invalid count"))
+    }
+
+    fn string(&mut self, depth: usize) -> Result<String, Error> {
+        match self.read(depth)? {
+            Edn::Str(s) => Ok(s),
+            _ => Err(self.error("expected a string")),
+        }
+    }
+
+    // Bytes and strings longer than a chunk are written as chunks followed by their last part
+    fn chunked(&mut self, code: u8, depth: usize) -> Result<(u8, Vec<u8>), Error> {
+        let (start, chunk) = if (BYTES_PACKED_LENGTH_START..=BYTES).contains(&code) {
+            (BYTES_PACKED_LENGTH_START, BYTES_CHUNK)
+        } else {
+            (STRING_PACKED_LENGTH_START, STRING_CHUNK)
+        };
+        let mut bytes = Vec::new();
+        let mut code = code;
+        loop {
+            let len = if code < chunk {
+                usize::from(code - start)
+            } else {
+                self.count(depth)?
+            };
+            bytes.extend_from_slice(self.take(len)?);
+            if code != chunk {
+                return Ok((start, bytes));
+            }
+            code = self.byte()?;
+            if !(start..=chunk + 1).contains(&code) {
+                return Err(self.error("unterminated chunks"));
+            }
+        }
+    }
+
+    fn byte_array(&mut self, depth: usize) -> Result<Vec<u8>, Error> {
+        let code = self.byte()?;
+        if (BYTES_PACKED_LENGTH_START..=BYTES).contains(&code) {
+            Ok(self.chunked(code, depth)?.1)
+        } else {
+            Err(self.error("expected bytes"))
+        }
+    }
+
+    fn items(&mut self, count: usize, depth: usize) -> Result<Vec<Edn>, Error> {
+        (0..count).map(|_| self.read(depth)).collect()
+    }
+
+    fn items_until_end(&mut self, open: bool, depth: usize) -> Result<Vec<Edn>, Error> {
+        let mut items = Vec::new();
+        loop {
+            match self.bytes.get(self.pos) {
+                Some(&END_COLLECTION) => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                None if open => return Ok(items),
+                _ => items.push(self.read(depth)?),
+            }
+        }
+    }
+
+    fn list(&mut self, depth: usize) -> Result<Vec<Edn>, Error> {
+        match self.read(depth)? {
+            Edn::Vector(Vector(items)) => Ok(items),
+            _ => Err(self.error("expected a list")),
+        }
+    }
+
+    fn named(&mut self, depth: usize) -> Result<String, Error> {
+        let namespace = match self.read(depth)? {
+            Edn::Nil => None,
+            Edn::Str(namespace) => Some(namespace),
+            _ => return Err(self.error("expected a namespace")),
+        };
+        let name = self.string(depth)?;
+        Ok(namespace.map_or_else(|| name.clone(), |namespace| format!("{namespace}/{name}")))
+    }
+
+    fn cached(&self, index: usize) -> Result<Edn, Error> {
+        self.priority_cache
+            .get(index)
+            .cloned()
+            .ok_or_else(|| self.error("reference to an empty priority cache entry"))
+    }
+
+    fn structure(&mut self, index: usize, depth: usize) -> Result<Edn, Error> {
+        let (tag, fields) = self
+            .struct_cache
+            .get(index)
+            .cloned()
+            .ok_or_else(|| self.error("reference to an empty struct cache entry"))?;
+        self.fields(tag, fields, depth)
+    }
+
+    fn fields(&mut self, tag: String, fields: usize, depth: usize) -> Result<Edn, Error> {
+        let mut fields = self.items(fields, depth)?;
+        let integer = |edn: &Edn| match edn {
+            Edn::Int(i) => Some(i.to_string()),
+            Edn::UInt(u) => Some(u.to_string()),
+            Edn::BigInt(n) => Some(n.clone()),
+            _ => None,
+        };
+        match (tag.as_str(), fields.as_slice()) {
+            ("char", [code]) => code
+                .to_uint()
+                .and_then(|code| char::from_u32(u32::try_from(code).ok()?))
+                .map(Edn::Char)
+                .ok_or_else(|| self.error("invalid char")),
+            ("ratio", [numerator, denominator]) => integer(numerator)
+                .zip(integer(denominator))
+                .map(|(numerator, denominator)| Edn::Rational(format!("{numerator}/{denominator}")))
+                .ok_or_else(|| self.error("invalid ratio")),
+            (_, [_]) => Ok(Edn::Tagged(tag, Box::new(fields.remove(0)))),
+            _ => Ok(Edn::Tagged(tag, Box::new(Edn::Vector(Vector::new(fields))))),
+        }
+    }
+
+    // Codes followed by the fields of a type known to Fressian
+    fn tagged(&mut self, code: u8, depth: usize) -> Result<Edn, Error> {
+        Ok(match code {
+            MAP => {
+                let items = self.list(depth)?;
+                if items.len() % 2 != 0 {
+                    return Err(self.error("map of an odd number of elements"));
+                }
+                let mut map = BTreeMap::new();
+                let mut items = items.into_iter();
+                while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                    map.insert(key, value);
+                }
+                Edn::Map(Map::new(map))
+            }
+            #[cfg(feature = "sets")]
+            SET => Edn::Set(Set::new(self.list(depth)?.into_iter().collect())),
+            #[cfg(not(feature = "sets"))]
+            SET => Edn::Vector(Vector::new(self.list(depth)?)),
+            UUID => {
+                let bytes = self.byte_array(depth)?;
+                let bytes = <[u8; 16]>::try_from(bytes.as_slice())
+                    .map_err(|_| self.error("uuid of other than 16 bytes"))?;
+                Edn::from(Uuid::from_bytes(bytes))
+            }
+            REGEX => Edn::Tagged("regex".to_string(), Box::new(Edn::Str(self.string(depth)?))),
+            URI => Edn::Tagged("uri".to_string(), Box::new(Edn::Str(self.string(depth)?))),
+            BIGINT => Edn::BigInt(from_twos_complement(&self.byte_array(depth)?)),
+            BIGDEC => {
+                let unscaled = self.byte_array(depth)?;
+                let scale = i32::try_from(self.int(depth)?)
+                    .map_err(|_| self.error("decimal scale out of range"))?;
+                Edn::Decimal(from_unscaled(&unscaled, scale))
+            }
+            INST => {
+                let millis = self.int(depth)?;
+                let inst = Inst::from_unix_timestamp_millis(millis)
+                    .ok_or_else(|| self.error("inst out of range"))?;
+                Edn::from(inst)
+            }
+            SYM => Edn::Symbol(self.named(depth)?),
+            KEY => Edn::Key(format!(":{}", self.named(depth)?)),
+            _ => {
+                self.pos -= 1;
+                return Err(self.error(&format!("unexpected code 0x{code:02X}")));
+            }
+        })
+    }
+
+    fn read(&mut self, depth: usize) -> Result<Edn, Error> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("too deeply nested"));
+        }
+        let depth = depth + 1;
+        let code = self.byte()?;
+        Ok(match code {
+            0x00..INT_PACKED_1_END => Edn::UInt(u64::from(code)),
+            INT_MINUS_ONE => Edn::Int(-1),
+            0x40..0x60 => int(self.packed_int(code, INT_PACKED_2_ZERO, 1)?),
+            0x60..0x70 => int(self.packed_int(code, INT_PACKED_3_ZERO, 2)?),
+            0x70..0x74 => int(self.packed_int(code, INT_PACKED_4_ZERO, 3)?),
+            0x74..0x78 => int(self.packed_int(code, INT_PACKED_5_ZERO, 4)?),
+            0x78..0x7C => int(self.packed_int(code, INT_PACKED_6_ZERO, 5)?),
+            0x7C..PRIORITY_CACHE_PACKED_START => {
+                int(self.packed_int(code, INT_PACKED_7_ZERO, 6)?)
+            }
+            INT => int(self.raw_int(8)?),
+            PRIORITY_CACHE_PACKED_START..PRIORITY_CACHE_PACKED_END => {
+                self.cached(usize::from(code - PRIORITY_CACHE_PACKED_START))?
+            }
+            GET_PRIORITY_CACHE => {
+                let index = self.count(depth)?;
+                self.cached(index)?
+            }
+            PUT_PRIORITY_CACHE => {
+                let edn = self.read(depth)?;
+                self.priority_cache.push(edn.clone());
+                edn
+            }
+            STRUCT_CACHE_PACKED_START..STRUCT_CACHE_PACKED_END => {
+                self.structure(usize::from(code - STRUCT_CACHE_PACKED_START), depth)?
+            }
+            STRUCT => {
+                let index = self.count(depth)?;
+                self.structure(index, depth)?
+            }
+            STRUCTTYPE => {
+                let tag = self.string(depth)?;
+                let fields = self.count(depth)?;
+                self.struct_cache.push((tag.clone(), fields));
+                self.fields(tag, fields, depth)?
+            }
+            LONG_ARRAY | DOUBLE_ARRAY | BOOLEAN_ARRAY | INT_ARRAY | FLOAT_ARRAY | OBJECT_ARRAY => {
+                let count = self.count(depth)?;
+                Edn::Vector(Vector::new(self.items(count, depth)?))
+            }
+            MAP..=KEY => self.tagged(code, depth)?,
+            BYTES_PACKED_LENGTH_START..=BYTES | STRING_PACKED_LENGTH_START..=STRING => {
+                match self.chunked(code, depth)? {
+                    (STRING_PACKED_LENGTH_START, bytes) => {
+                        Edn::Str(decode_string(&bytes).ok_or_else(|| self.error("invalid string"))?)
+                    }
+                    (_, bytes) => Edn::Tagged(
+                        "bytes".to_string(),
+                        Box::new(Edn::Vector(Vector::new(
+                            bytes.into_iter().map(|b| Edn::UInt(u64::from(b))).collect(),
+                        ))),
+                    ),
+                }
+            }
+            LIST_PACKED_LENGTH_START..LIST_PACKED_LENGTH_END => Edn::Vector(Vector::new(
+                self.items(usize::from(code - LIST_PACKED_LENGTH_START), depth)?,
+            )),
+            LIST => {
+                let count = self.count(depth)?;
+                Edn::Vector(Vector::new(self.items(count, depth)?))
+            }
+            BEGIN_CLOSED_LIST => Edn::Vector(Vector::new(self.items_until_end(false, depth)?)),
+            BEGIN_OPEN_LIST => Edn::Vector(Vector::new(self.items_until_end(true, depth)?)),
+            META => {
+                self.read(depth)?;
+                self.read(depth)?
+            }
+            TRUE => Edn::Bool(true),
+            FALSE => Edn::Bool(false),
+            NULL => Edn::Nil,
+            FLOAT => {
+                let bytes = self.take(4)?;
+                let float = f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                Edn::Double(f64::from(float).into())
+            }
+            DOUBLE => {
+                let bytes = self.take(8)?;
+                let mut double = [0; 8];
+                double.copy_from_slice(bytes);
+                Edn::Double(f64::from_be_bytes(double).into())
+            }
+            DOUBLE_0 => Edn::Double(0.0.into()),
+            DOUBLE_1 => Edn::Double(1.0.into()),
+            RESET_CACHES => {
+                self.priority_cache.clear();
+                self.struct_cache.clear();
+                self.read(depth)?
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.error(&format!("unexpected code 0x{code:02X}")));
+            }
+        })
+    }
+}
+
+// Integers are read as the parser reads them, `Edn::UInt` unless negative
+fn int(i: i64) -> Edn {
+    u64::try_from(i).map_or(Edn::Int(i), Edn::UInt)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn packs_integers_like_fressian() {
+        let cases: [(i64, &[u8]); 12] = [
+            (0, &[0x00]),
+            (63, &[0x3F]),
+            (-1, &[0xFF]),
+            (64, &[0x50, 0x40]),
+            (-2, &[0x4F, 0xFE]),
+            (-64, &[0x4F, 0xC0]),
+            (4095, &[0x5F, 0xFF]),
+            (4096, &[0x68, 0x10, 0x00]),
+            (-4097, &[0x67, 0xEF, 0xFF]),
+            (1 << 24, &[0x73, 0x00, 0x00, 0x00]),
+            (1 << 40, &[0x7B, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            (i64::MIN, &[INT, 0x80, 0, 0, 0, 0, 0, 0, 0]),
+        ];
+        for (i, bytes) in cases {
+            assert_eq!(to_bytes(&Edn::Int(i)), bytes, "{i}");
+            assert_eq!(from_bytes(bytes).unwrap(), int(i), "{i}");
+        }
+    }
+
+    #[test]
+    fn converts_big_numbers() {
+        for (digits, bytes) in [
+            ("0", vec![0x00]),
+            ("127", vec![0x7F]),
+            ("128", vec![0x00, 0x80]),
+            ("-128", vec![0x80]),
+            ("-129", vec![0xFF, 0x7F]),
+            ("-256", vec![0xFF, 0x00]),
+            ("18446744073709551616", vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0]),
+        ] {
+            assert_eq!(to_twos_complement(digits), Some(bytes.clone()), "{digits}");
+            assert_eq!(from_twos_complement(&bytes), digits);
+        }
+        assert_eq!(to_twos_complement("1.5"), None);
+        assert_eq!(to_unscaled("-1.50"), Some((vec![0xFF, 0x6A], 2)));
+        assert_eq!(from_unscaled(&[0xFF, 0x6A], 2), "-1.50");
+        assert_eq!(from_unscaled(&[0x05], 3), "0.005");
+        assert_eq!(from_unscaled(&[0x0F], -2), "15e2");
+        assert_eq!(from_unscaled(&[0x01], 40), "1e-40");
+        assert_eq!(to_unscaled("1e-40"), Some((vec![0x01], 40)));
+    }
+
+    #[test]
+    fn encodes_strings_as_utf16_units() {
+        let s = "aé€😀";
+        let encoded = encode_string(s);
+        assert_eq!(
+            encoded,
+            [
+                b'a', 0xC3, 0xA9, 0xE2, 0x82, 0xAC, 0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80
+            ]
+        );
+        assert_eq!(decode_string(&encoded).as_deref(), Some(s));
+        assert_eq!(decode_string(&[0xC3]), None);
+    }
+}
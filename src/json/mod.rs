@@ -1,19 +1,22 @@
-use alloc::collections::BTreeMap;
 #[cfg(feature = "sets")]
 use alloc::collections::BTreeSet;
 use alloc::format;
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use crate::edn::{Edn, rational_to_double};
 
+pub mod reader;
+pub mod value;
+pub mod writer;
+
 #[allow(clippy::module_name_repetitions)]
 pub fn display_as_json(edn: &Edn) -> String {
     match edn {
         Edn::Vector(v) => vec_to_json(&v.clone().to_vec()),
         #[cfg(feature = "sets")]
         Edn::Set(s) => set_to_json_vec(&s.clone().to_set()),
-        Edn::Map(map) => map_to_json(&map.clone().to_map()),
+        Edn::Map(_) | Edn::OrderedMap(_) => map_to_json(&edn.map_entries().unwrap_or_default()),
         Edn::List(l) => vec_to_json(&l.clone().to_vec()),
         Edn::Key(key) => format!("{:?}", kebab_to_camel(key)),
         Edn::Symbol(s) | Edn::Str(s) => format!("{s:?}"),
@@ -29,6 +32,8 @@ pub fn display_as_json(edn: &Edn) -> String {
             s
         }
         Edn::Rational(r) => format!("{}", rational_to_double(r).unwrap()),
+        Edn::BigInt(n) => n.clone(),
+        Edn::Decimal(d) => decimal_to_json(d),
         Edn::Char(c) => format!("'{c}'"),
         Edn::Bool(b) => format!("{b}"),
         Edn::Nil => String::from("null"),
@@ -37,6 +42,16 @@ pub fn display_as_json(edn: &Edn) -> String {
     }
 }
 
+// JSON requires digits on both sides of the decimal point, `1.M` and `1.e5M` are valid EDN.
+fn decimal_to_json(d: &str) -> String {
+    let (mantissa, exponent) = d.find(['e', 'E']).map_or((d, ""), |i| d.split_at(i));
+    if mantissa.ends_with('.') {
+        format!("{mantissa}0{exponent}")
+    } else {
+        d.to_string()
+    }
+}
+
 fn kebab_to_camel(key: &str) -> String {
     let keywrod = key
         .chars()
@@ -89,14 +104,14 @@ fn set_to_json_vec(set: &BTreeSet<Edn>) -> String {
     s
 }
 
-fn map_to_json(map: &BTreeMap<String, Edn>) -> String {
+fn map_to_json(map: &[(&Edn, &Edn)]) -> String {
     let map_str = map
         .iter()
         .map(|(k, e)| {
-            let key = if k.starts_with(':') {
-                kebab_to_camel(k)
-            } else {
-                k.clone()
+            let key = match k {
+                Edn::Key(k) => kebab_to_camel(k),
+                Edn::Str(s) => s.clone(),
+                k => k.to_string(),
             };
             let edn = display_as_json(e);
 
@@ -151,6 +166,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn arbitrary_precision_numbers() {
+        assert_eq!(
+            display_as_json(&Edn::BigInt("-12345678901234567890123".to_string())),
+            String::from("-12345678901234567890123")
+        );
+        assert_eq!(
+            display_as_json(&Edn::Decimal("1234567890.123456789012".to_string())),
+            String::from("1234567890.123456789012")
+        );
+        assert_eq!(
+            display_as_json(&Edn::Decimal("1.".to_string())),
+            String::from("1.0")
+        );
+        assert_eq!(
+            display_as_json(&Edn::Decimal("-2.e5".to_string())),
+            String::from("-2.0e5")
+        );
+    }
+
     #[test]
     fn bools() {
         assert_eq!(display_as_json(&Edn::Bool(true)), String::from("true"));
@@ -237,17 +272,28 @@ mod test {
     #[test]
     fn simple_map() {
         let map = Edn::Map(Map::new(map! {
-            String::from("1.2") => Edn::Bool(false),
-            String::from(":belo-monte") => Edn::Rational(String::from("3/4")),
-            String::from("true") => Edn::Char('d')
+            Edn::Double(1.2.into()) => Edn::Bool(false),
+            Edn::Key(String::from(":belo-monte")) => Edn::Rational(String::from("3/4")),
+            Edn::Bool(true) => Edn::Char('d')
         }));
 
         assert_eq!(
             display_as_json(&map),
-            "{\"1.2\": false, \"beloMonte\": 0.75, \"true\": \'d\'}"
+            "{\"beloMonte\": 0.75, \"1.2\": false, \"true\": \'d\'}"
         );
     }
 
+    #[test]
+    fn map_with_edn_keys() {
+        let map = Edn::Map(Map::new(map! {
+            Edn::Str(String::from("a")) => Edn::UInt(1),
+            Edn::Symbol(String::from("b")) => Edn::UInt(2),
+            Edn::Vector(Vector::new(vec![Edn::UInt(1), Edn::UInt(2)])) => Edn::UInt(3)
+        }));
+
+        assert_eq!(display_as_json(&map), "{\"[1 2]\": 3, \"b\": 2, \"a\": 1}");
+    }
+
     #[test]
     fn complex_structure() {
         let edn = Edn::Vector(Vector::new(vec![
@@ -264,14 +310,14 @@ mod test {
                 })),
             ])),
             Edn::Map(Map::new(map![
-                    String::from("false") => Edn::Key(":f".to_string()),
-                    String::from("nil") => Edn::Rational("3/4".to_string()),
-                    String::from(":my-crazy-map") => Edn::Map(Map::new(map![
-                        String::from("false") => Edn::Map(
+                    Edn::Bool(false) => Edn::Key(":f".to_string()),
+                    Edn::Nil => Edn::Rational("3/4".to_string()),
+                    Edn::Key(String::from(":my-crazy-map")) => Edn::Map(Map::new(map![
+                        Edn::Bool(false) => Edn::Map(
                             Map::new( map![
-                                String::from(":f") => Edn::Key(String::from(":b"))
+                                Edn::Key(String::from(":f")) => Edn::Key(String::from(":b"))
                             ])),
-                        String::from("nil") => Edn::Vector(
+                        Edn::Nil => Edn::Vector(
                             Vector::new( vec![
                                 Edn::Rational("3/4".to_string()),
                                 Edn::Int(1i64)
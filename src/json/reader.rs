@@ -0,0 +1,305 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::value::{self, Json};
+use crate::edn::{Edn, Error, Keyword, Map, OrderedMap, Vector};
+
+/// How [`JsonReadOptions`] reads the keys of JSON objects.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum KeyPolicy {
+    /// Keys become kebab-case keywords, `"first_name"`, `"first name"` and `"firstName"` are
+    /// all `:first-name`. Keys that cannot be keywords, like `"a/b"` or `"@id"`, stay strings
+    #[default]
+    Keywordize,
+    /// Keys stay strings
+    Strings,
+    /// Keys become kebab-case keywords in the given namespace, `"id"` is `:user/id`
+    Namespace(String),
+}
+
+/// How [`JsonReadOptions`] reads numbers with a fraction or an exponent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Decimals {
+    /// As `Edn::Double`
+    #[default]
+    Double,
+    /// As `Edn::Decimal`, exactly as written
+    Decimal,
+}
+
+/// How [`JsonReadOptions`] reads integers beyond `i64` and `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BigIntegers {
+    /// As `Edn::BigInt`
+    #[default]
+    BigInt,
+    /// As the closest `Edn::Double`, like JavaScript
+    Double,
+    /// As an error
+    Reject,
+}
+
+/// `JsonReadOptions` configures how JSON is read into `Edn`. Requires feature `json`.
+///
+/// Arrays become vectors, `null` nil, and objects maps, where a repeated key keeps its last
+/// value.
+///
+/// ```rust
+/// use edn_rs::{Decimals, Edn, JsonReadOptions, KeyPolicy};
+///
+/// let json = r#"{"userName": "rose", "a/b": null, "price": 10.50}"#;
+///
+/// assert_eq!(
+///     JsonReadOptions::new().parse(json).unwrap().to_string(),
+///     "{:price 10.5, :user-name \"rose\", \"a/b\" nil}"
+/// );
+/// assert_eq!(
+///     JsonReadOptions::new()
+///         .keys(KeyPolicy::Namespace("user".to_string()))
+///         .decimals(Decimals::Decimal)
+///         .ordered_maps(true)
+///         .parse(json)
+///         .unwrap()
+///         .to_string(),
+///     "{:user/user-name \"rose\", \"a/b\" nil, :user/price 10.50M}"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct JsonReadOptions {
+    pub keys: KeyPolicy,
+    pub decimals: Decimals,
+    pub big_integers: BigIntegers,
+    /// Reads objects as `Edn::OrderedMap`, keeping their keys in source order
+    pub ordered_maps: bool,
+}
+
+impl JsonReadOptions {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            keys: KeyPolicy::Keywordize,
+            decimals: Decimals::Double,
+            big_integers: BigIntegers::BigInt,
+            ordered_maps: false,
+        }
+    }
+
+    /// Sets how the keys of objects are read.
+    #[must_use]
+    pub fn keys(mut self, keys: KeyPolicy) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Sets how numbers with a fraction or an exponent are read.
+    #[must_use]
+    pub const fn decimals(mut self, decimals: Decimals) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Sets how integers too large for `Edn::Int` and `Edn::UInt` are read.
+    #[must_use]
+    pub const fn big_integers(mut self, big_integers: BigIntegers) -> Self {
+        self.big_integers = big_integers;
+        self
+    }
+
+    /// Reads objects as [`crate::OrderedMap`] instead of [`crate::Map`].
+    #[must_use]
+    pub const fn ordered_maps(mut self, ordered_maps: bool) -> Self {
+        self.ordered_maps = ordered_maps;
+        self
+    }
+
+    /// Parses a JSON `&str` into an `Edn` using these options.
+    ///
+    /// # Errors
+    ///
+    /// `EdnError::Json` when `json` is not a single valid JSON value, or holds a big integer
+    /// with [`BigIntegers::Reject`].
+    pub fn parse(&self, json: &str) -> Result<Edn, Error> {
+        let json = value::parse(json).map_err(Error::Json)?;
+        self.read(json)
+    }
+
+    fn read(&self, json: Json) -> Result<Edn, Error> {
+        Ok(match json {
+            Json::Null => Edn::Nil,
+            Json::Bool(b) => Edn::Bool(b),
+            Json::Number(n) => self.number(n)?,
+            Json::String(s) => Edn::Str(s),
+            Json::Array(items) => Edn::Vector(Vector::new(
+                items
+                    .into_iter()
+                    .map(|item| self.read(item))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Json::Object(entries) => {
+                let entries = entries
+                    .into_iter()
+                    .map(|(key, value)| Ok((self.key(&key), self.read(value)?)));
+                if self.ordered_maps {
+                    let mut map = OrderedMap::default();
+                    for entry in entries {
+                        let (key, value) = entry?;
+                        map.insert(key, value);
+                    }
+                    Edn::OrderedMap(map)
+                } else {
+                    Edn::Map(Map::new(
+                        entries.collect::<Result<BTreeMap<_, _>, Error>>()?,
+                    ))
+                }
+            }
+        })
+    }
+
+    fn number(&self, n: String) -> Result<Edn, Error> {
+        if n.contains(['.', 'e', 'E']) {
+            return Ok(match self.decimals {
+                Decimals::Double => Edn::Double(n.parse::<f64>().unwrap_or_default().into()),
+                Decimals::Decimal => Edn::Decimal(n),
+            });
+        }
+        if let Ok(u) = n.parse::<u64>() {
+            return Ok(Edn::UInt(u));
+        }
+        if let Ok(i) = n.parse::<i64>() {
+            return Ok(Edn::Int(i));
+        }
+        match self.big_integers {
+            BigIntegers::BigInt => Ok(Edn::BigInt(n)),
+            BigIntegers::Double => Ok(Edn::Double(n.parse::<f64>().unwrap_or_default().into())),
+            BigIntegers::Reject => Err(Error::Json(format!("Integer {n} is too large"))),
+        }
+    }
+
+    fn key(&self, key: &str) -> Edn {
+        let namespace = match &self.keys {
+            KeyPolicy::Strings => return Edn::Str(key.to_string()),
+            KeyPolicy::Keywordize => None,
+            KeyPolicy::Namespace(namespace) => Some(namespace.as_str()),
+        };
+        Keyword::new(namespace, &kebab_case(key))
+            .map_or_else(|_| Edn::Str(key.to_string()), Edn::from)
+    }
+}
+
+// Spaces and underscores become dashes, and each word of camelCase or PascalCase is
+// lowercased behind a dash, `"URLPath"` is `url-path`
+fn kebab_case(key: &str) -> String {
+    let chars = key.chars().collect::<Vec<_>>();
+    let mut kebab = String::with_capacity(key.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| chars[i]);
+        if matches!(c, ' ' | '_') {
+            kebab.push('-');
+        } else if c.is_uppercase() {
+            let word_start = previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                || (previous.is_some_and(char::is_uppercase)
+                    && chars.get(i + 1).is_some_and(|n| n.is_lowercase()));
+            if word_start {
+                kebab.push('-');
+            }
+            kebab.extend(c.to_lowercase());
+        } else {
+            kebab.push(c);
+        }
+    }
+    kebab
+}
+
+// Rewrites text that is not valid JSON for `json_to_edn` as EDN text token by token, keeping
+// its layout: keys are read with the default `KeyPolicy` and lose their `:`, strings are
+// escaped as EDN, `null` becomes `nil` and the non-standard `'c'` becomes the char `\c`
+pub fn rewrite(json: &str) -> String {
+    let options = JsonReadOptions::new();
+    let mut edn = String::with_capacity(json.len());
+    let mut rest = json;
+    while let Some(c) = rest.chars().next() {
+        if c == '"' {
+            let end = string_end(rest);
+            let (token, after) = rest.split_at(end);
+            let decoded = match value::parse(token) {
+                Ok(Json::String(s)) => s,
+                _ => token.trim_matches('"').to_string(),
+            };
+            rest = after;
+            let trimmed = after.trim_start();
+            if let Some(after_colon) = trimmed.strip_prefix(':') {
+                edn.push_str(&options.key(&decoded).to_string());
+                // The space around the `:` is kept, and added when there was none
+                edn.push_str(&after[..after.len() - trimmed.len()]);
+                if trimmed.len() == after.len() && !after_colon.starts_with(char::is_whitespace) {
+                    edn.push(' ');
+                }
+                rest = after_colon;
+            } else {
+                edn.push_str(&Edn::Str(decoded).to_string());
+            }
+        } else if let Some(after) = rest.strip_prefix("null") {
+            edn.push_str("nil");
+            rest = after;
+        } else if let Some((c, after)) = single_quoted_char(rest) {
+            edn.push_str(&Edn::Char(c).to_string());
+            rest = after;
+        } else {
+            edn.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    edn
+}
+
+fn single_quoted_char(s: &str) -> Option<(char, &str)> {
+    let quoted = s.strip_prefix('\'')?;
+    let c = quoted.chars().next().filter(|&c| c != '\'')?;
+    let after = quoted[c.len_utf8()..].strip_prefix('\'')?;
+    Some((c, after))
+}
+
+// The length of the string token at the start of `s`, up to the end of `s` when unterminated
+fn string_end(s: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            '"' if !escaped => return i + 1,
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    s.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn kebab_cases_keys() {
+        for (key, kebab) in [
+            ("firstName", "first-name"),
+            ("FirstName", "first-name"),
+            ("first_name", "first-name"),
+            ("first name", "first-name"),
+            ("URLPath", "url-path"),
+            ("utf8Text", "utf8-text"),
+            ("são paulo", "são-paulo"),
+            ("already-kebab", "already-kebab"),
+        ] {
+            assert_eq!(kebab_case(key), kebab);
+        }
+    }
+
+    #[test]
+    fn rewrites_keeping_the_layout() {
+        assert_eq!(
+            rewrite("{\"a b\": [null, 'c', \"x\\\"y\"],\n \"@id\" :\"null: 1\",\"b\":2}"),
+            "{:a-b [nil, \\c, \"x\\\"y\"],\n \"@id\" \"null: 1\",:b 2}"
+        );
+    }
+}
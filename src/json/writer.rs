@@ -0,0 +1,322 @@
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+use super::value::{self, Json};
+#[cfg(feature = "sets")]
+use crate::edn::Set;
+use crate::edn::{Edn, List, Vector, rational_to_double};
+
+/// How [`JsonOptions`] writes keywords, as map keys and as values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyCase {
+    /// `:first-name` is `"firstName"`, like [`Edn::to_json`]
+    #[default]
+    Camel,
+    /// `:first-name` is `"first_name"`
+    Snake,
+    /// `:first-name` is `"first-name"`
+    DropColon,
+    /// `:first-name` is `":first-name"`
+    Keep,
+}
+
+/// How [`JsonOptions`] writes tagged elements, `#my/point [1 2]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tags {
+    /// As an object of a single key, `{"my/point": [1, 2]}`
+    #[default]
+    Object,
+    /// As an array of the tag and the element, `["my/point", [1, 2]]`
+    Array,
+    /// As the element alone, `[1, 2]`
+    Value,
+}
+
+/// How [`JsonOptions`] writes rationals, `-3/4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rationals {
+    /// As the closest number, `-0.75`
+    #[default]
+    Double,
+    /// As a string, `"-3/4"`
+    String,
+    /// As an array of the numerator and the denominator, `[-3, 4]`
+    Array,
+}
+
+/// How [`JsonOptions`] writes sets, `#{:a :b}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sets {
+    /// As an array, `["a", "b"]`
+    #[default]
+    Array,
+    /// As an object with each element as a key to `true`, `{"a": true, "b": true}`
+    Object,
+    /// As an array tagged `set` following [`Tags`], `{"set": ["a", "b"]}`
+    Tagged,
+}
+
+/// `JsonOptions` configures how [`to_json_with`] writes `Edn` as JSON.
+/// Requires feature `json`.
+///
+/// Unlike [`Edn::to_json`], the output is always valid JSON: strings are escaped as in
+/// RFC 8259, chars are strings of one char, map keys that are neither strings, keywords nor
+/// symbols are written as their EDN, and nil, `NaN` and infinities are `null`.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, JsonOptions, KeyCase, Rationals, Tags, to_json_with};
+///
+/// let edn = Edn::from_str("{:first-name \"rose\\n\" :ratio 1/4 :at #inst \"2020-07-16T21:53:14.628Z\" :grade \\A}").unwrap();
+///
+/// assert_eq!(
+///     to_json_with(&edn, JsonOptions::new()),
+///     r#"{"at": {"inst": "2020-07-16T21:53:14.628Z"}, "firstName": "rose\n", "grade": "A", "ratio": 0.25}"#
+/// );
+/// assert_eq!(
+///     to_json_with(
+///         &edn,
+///         JsonOptions::new().keywords(KeyCase::Snake).tags(Tags::Value).rationals(Rationals::String)
+///     ),
+///     r#"{"at": "2020-07-16T21:53:14.628Z", "first_name": "rose\n", "grade": "A", "ratio": "1/4"}"#
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct JsonOptions {
+    pub keywords: KeyCase,
+    pub tags: Tags,
+    pub rationals: Rationals,
+    pub sets: Sets,
+}
+
+impl JsonOptions {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            keywords: KeyCase::Camel,
+            tags: Tags::Object,
+            rationals: Rationals::Double,
+            sets: Sets::Array,
+        }
+    }
+
+    /// Sets how keywords are written.
+    #[must_use]
+    pub const fn keywords(mut self, keywords: KeyCase) -> Self {
+        self.keywords = keywords;
+        self
+    }
+
+    /// Sets how tagged elements are written.
+    #[must_use]
+    pub const fn tags(mut self, tags: Tags) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets how rationals are written.
+    #[must_use]
+    pub const fn rationals(mut self, rationals: Rationals) -> Self {
+        self.rationals = rationals;
+        self
+    }
+
+    /// Sets how sets are written.
+    #[must_use]
+    pub const fn sets(mut self, sets: Sets) -> Self {
+        self.sets = sets;
+        self
+    }
+}
+
+/// Writes `edn` as JSON following `options`. Requires feature `json`.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::{Edn, JsonOptions, Tags, to_json_with};
+///
+/// let edn = Edn::from_str("[\\c \"say \\\"hi\\\"\" #point [1 2] {[1] 2}]").unwrap();
+///
+/// assert_eq!(
+///     to_json_with(&edn, JsonOptions::new().tags(Tags::Array)),
+///     r#"["c", "say \"hi\"", ["point", [1, 2]], {"[1]": 2}]"#
+/// );
+/// ```
+#[must_use]
+pub fn to_json_with(edn: &Edn, options: JsonOptions) -> String {
+    let mut out = String::new();
+    write(&mut out, edn, options);
+    out
+}
+
+fn keyword(key: &str, case: KeyCase) -> String {
+    match case {
+        KeyCase::Camel => super::kebab_to_camel(key),
+        KeyCase::Snake => key.strip_prefix(':').unwrap_or(key).replace('-', "_"),
+        KeyCase::DropColon => key.strip_prefix(':').unwrap_or(key).to_string(),
+        KeyCase::Keep => key.to_string(),
+    }
+}
+
+fn number(out: &mut String, n: Option<f64>) {
+    match n {
+        Some(n) if n.is_finite() => {
+            let n = n.to_string();
+            out.push_str(&n);
+            if !n.contains('.') {
+                out.push_str(".0");
+            }
+        }
+        _ => out.push_str("null"),
+    }
+}
+
+// Numbers kept as text are checked, so that an `Edn` built by hand with `+1` or `1.M` as
+// digits cannot make invalid JSON
+fn digits(out: &mut String, n: &str) {
+    if matches!(value::parse(n), Ok(Json::Number(_))) {
+        out.push_str(n);
+    } else {
+        value::write_string(out, n);
+    }
+}
+
+fn array<'a>(out: &mut String, items: impl IntoIterator<Item = &'a Edn>, options: JsonOptions) {
+    out.push('[');
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write(out, item, options);
+    }
+    out.push(']');
+}
+
+fn tagged(out: &mut String, tag: &str, options: JsonOptions, element: impl FnOnce(&mut String)) {
+    match options.tags {
+        Tags::Object => {
+            out.push('{');
+            value::write_string(out, tag);
+            out.push_str(": ");
+            element(out);
+            out.push('}');
+        }
+        Tags::Array => {
+            out.push('[');
+            value::write_string(out, tag);
+            out.push_str(", ");
+            element(out);
+            out.push(']');
+        }
+        Tags::Value => element(out),
+    }
+}
+
+fn write(out: &mut String, edn: &Edn, options: JsonOptions) {
+    match edn {
+        Edn::Nil | Edn::Empty => out.push_str("null"),
+        Edn::Bool(b) => {
+            let _ = write!(out, "{b}");
+        }
+        Edn::Int(n) => {
+            let _ = write!(out, "{n}");
+        }
+        Edn::UInt(n) => {
+            let _ = write!(out, "{n}");
+        }
+        Edn::BigInt(n) => digits(out, n),
+        Edn::Decimal(d) => digits(out, &super::decimal_to_json(d)),
+        Edn::Double(_) => number(out, edn.to_float()),
+        Edn::Rational(r) => match options.rationals {
+            Rationals::Double => number(out, rational_to_double(r)),
+            Rationals::String => value::write_string(out, r),
+            Rationals::Array => {
+                let (numerator, denominator) = r.split_once('/').unwrap_or((r, "1"));
+                out.push('[');
+                digits(out, numerator);
+                out.push_str(", ");
+                digits(out, denominator);
+                out.push(']');
+            }
+        },
+        Edn::Char(c) => value::write_string(out, c.encode_utf8(&mut [0; 4])),
+        Edn::Str(s) | Edn::Symbol(s) => value::write_string(out, s),
+        Edn::Key(k) => value::write_string(out, &keyword(k, options.keywords)),
+        Edn::Vector(Vector(items)) | Edn::List(List(items)) => array(out, items, options),
+        #[cfg(feature = "sets")]
+        Edn::Set(Set(items)) => match options.sets {
+            Sets::Array => array(out, items, options),
+            Sets::Object => {
+                out.push('{');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    key(out, item, options);
+                    out.push_str(": true");
+                }
+                out.push('}');
+            }
+            Sets::Tagged => tagged(out, "set", options, |out| array(out, items, options)),
+        },
+        Edn::Map(_) | Edn::OrderedMap(_) => {
+            out.push('{');
+            for (i, (k, v)) in edn
+                .map_entries()
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+            {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                key(out, k, options);
+                out.push_str(": ");
+                write(out, v, options);
+            }
+            out.push('}');
+        }
+        Edn::Tagged(tag, element) => tagged(out, tag, options, |out| write(out, element, options)),
+    }
+}
+
+fn key(out: &mut String, key: &Edn, options: JsonOptions) {
+    match key {
+        Edn::Str(s) | Edn::Symbol(s) => value::write_string(out, s),
+        Edn::Key(k) => value::write_string(out, &keyword(k, options.keywords)),
+        key => value::write_string(out, &key.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cases_keywords() {
+        for (case, json) in [
+            (KeyCase::Camel, "firstName/againId"),
+            (KeyCase::Snake, "first_name/again.id"),
+            (KeyCase::DropColon, "first-name/again.id"),
+            (KeyCase::Keep, ":first-name/again.id"),
+        ] {
+            assert_eq!(keyword(":first-name/again.id", case), json);
+        }
+    }
+
+    #[test]
+    fn writes_valid_numbers() {
+        for (n, json) in [
+            (Some(1.5), "1.5"),
+            (Some(32.0), "32.0"),
+            (Some(f64::NAN), "null"),
+            (Some(f64::INFINITY), "null"),
+            (None, "null"),
+        ] {
+            let mut out = String::new();
+            number(&mut out, n);
+            assert_eq!(out, json);
+        }
+    }
+}
@@ -46,20 +46,136 @@ pub mod serialize;
 #[cfg(feature = "json")]
 pub(crate) mod json;
 
+/// Pretty-printing of `Edn` over several lines, see [`pretty::to_string_pretty`].
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::Edn;
+/// use edn_rs::pretty::{PrettyOptions, to_string_pretty};
+///
+/// let edn = Edn::from_str(
+///     "{:db {:host \"localhost\" :port 5432} :features [:search :billing :audit-log :export]}",
+/// )
+/// .unwrap();
+/// let options = PrettyOptions { width: 45, align_map_values: true, ..PrettyOptions::default() };
+///
+/// assert_eq!(
+///     to_string_pretty(&edn, options),
+///     "{\n  :db       {:host \"localhost\" :port 5432}\n  :features [\n    :search\n    :billing\n    :audit-log\n    :export\n  ]\n}"
+/// );
+/// ```
+pub mod pretty;
+
+/// Lossless concrete syntax tree of EDN sources, see [`cst::parse`].
+///
+/// Comments, discarded elements, commas and whitespace are kept as trivia attached to the
+/// following node, so a [`cst::Document`] is written back exactly as it was read. This is
+/// what tools editing hand-written EDN files build on.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::Edn;
+/// use edn_rs::cst::Document;
+///
+/// let source = ";; service config\n{:db {:port 5432}, ; primary\n :replicas #_[:b] [:a]}\n";
+/// let doc = Document::from_str(source).unwrap();
+///
+/// assert_eq!(doc.to_string(), source);
+/// assert_eq!(
+///     doc.lower().unwrap(),
+///     [Edn::from_str("{:db {:port 5432} :replicas [:a]}").unwrap()]
+/// );
+/// ```
+pub mod cst;
+
 #[cfg(feature = "json")]
 use alloc::borrow::Cow;
 #[cfg(feature = "json")]
-#[allow(unused_imports)]
 use alloc::string::ToString;
 
+/// Multi-step paths and queries over `Edn`, see [`query::Query`] and [`Edn::get_in`].
+pub mod query;
+
+/// Structural diff and patch of `Edn` values, see [`diff()`] and [`patch()`].
+pub mod diff;
+
+/// Recursive merge of `Edn` values, see [`Edn::deep_merge`].
+pub mod merge;
+
+/// Validation of `Edn` against schemas written in EDN, see [`schema::Schema`].
+pub mod schema;
+
+/// Conversion between `Edn` and transit+json, see [`transit::to_string`] and
+/// [`transit::from_str`]. Requires feature `transit`.
+#[cfg(feature = "transit")]
+pub mod transit;
+
+/// Conversion between `Edn` and Fressian, see [`fressian::to_bytes`] and
+/// [`fressian::from_bytes`]. Requires feature `fressian`.
+#[cfg(feature = "fressian")]
+pub mod fressian;
+
 mod deserialize;
-/// `json_to_edn` receives a json string and parses its common key-values to a regular EDN format. It requires feature `json`
+
+/// `serde` support for EDN. Requires feature `serde`.
+///
+/// Any type implementing `serde::Serialize`/`serde::Deserialize` can be converted to and from
+/// `Edn`, no `edn-derive` needed. The data model maps as follows:
+/// - structs become maps keyed by keywords, `{:name "rose" :age 66}`
+/// - sequences and tuples become vectors, `Option::None` and `()` become `nil`
+/// - unit variants become keywords, `:Red`
+/// - newtype, tuple and struct variants become tagged elements, `#Rgb [255 0 0]`
+///
+/// Use `#[serde(rename_all = "kebab-case")]` for idiomatic `:kebab-case` keywords.
+/// When deserializing, tags are ignored unless the target is an enum, so `#inst "..."`
+/// deserializes into a `String`.
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// #[serde(rename_all = "kebab-case")]
+/// struct Person {
+///     first_name: String,
+///     age: u64,
+///     role: Role,
+/// }
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// enum Role {
+///     Admin,
+///     Member { since: u16 },
+/// }
+///
+/// let person = Person {
+///     first_name: "rose".to_string(),
+///     age: 66,
+///     role: Role::Member { since: 2019 },
+/// };
+/// let edn = edn_rs::serde::to_string(&person).unwrap();
+///
+/// assert_eq!(edn, "{:age 66, :first-name \"rose\", :role #Member {:since 2019}}");
+/// assert_eq!(edn_rs::serde::from_str::<Person>(&edn).unwrap(), person);
+/// assert_eq!(
+///     edn_rs::serde::from_str::<Role>(":Admin").unwrap(),
+///     Role::Admin
+/// );
+/// ```
+#[cfg(feature = "serde")]
+pub mod serde;
+
+/// `json_to_edn` receives a json string and writes it as an EDN string. It requires feature `json`
 /// tested examples are:
 /// 1. `"{\"hello world\": \"julia\"}"` becomes `"{:hello-world \"julia\"}"`
 /// 2. `"{\"hello\": null}"` becomes `"{:hello nil}"`
 /// 3. `{\"hello\": 'c'}` becomes `"{:hello \\c}"`
 /// 4. `"{\"multi_string with underscore\": 545643}"` becomes `"{:multi-string-with-underscore 545643}"`
 ///
+/// The JSON is read with [`from_json`], so keys follow [`KeyPolicy::Keywordize`], and the
+/// `Edn` is written with `Display`. As this function cannot fail, text that is not valid JSON,
+/// like the `'c'` chars above, falls back to being rewritten token by token, keeping its
+/// layout. Use [`from_json`] or [`JsonReadOptions::parse`] to get an `Edn` or an error instead.
+///
 /// ```
 /// use edn_rs::json_to_edn;
 ///
@@ -78,34 +194,55 @@ mod deserialize;
 /// }
 /// ```
 #[cfg(feature = "json")]
-#[allow(clippy::missing_panics_doc)] // Our regex's don't rely on user-input
 pub fn json_to_edn<'a>(json: impl AsRef<str>) -> Cow<'a, str> {
-    use regex::{Captures, Regex};
-
-    // Convert string keys to EDN keywords
-    let re = Regex::new(r#""\w*(\s\w*)*":"#).unwrap();
-    let json = re.replace_all(json.as_ref(), |caps: &Captures<'_>| {
-        let mut rcap = caps[0].replace(['\"', ':'], "").replace(['_', ' '], "-");
-        rcap.insert(0, ':');
-        rcap.clone()
-    });
-
-    // Convert chars
-    let c_re = Regex::new(r"'.'").unwrap();
-    let json = c_re.replace_all(&json[..], |caps: &Captures<'_>| {
-        let mut rcap = caps[0].replace('\'', "");
-        rcap.insert(0, '\\');
-        rcap.clone()
-    });
-
-    json.replace("null", "nil").into()
+    let json = json.as_ref();
+    from_json(json)
+        .map_or_else(|_| json::reader::rewrite(json), |edn| edn.to_string())
+        .into()
 }
 
+/// `from_json` reads a JSON string into an `Edn`, with the default [`JsonReadOptions`]. It
+/// requires feature `json`.
+///
+/// ```
+/// use edn_rs::{Edn, from_json};
+///
+/// let edn = from_json(r#"{"userName": "rose", "tags": ["a", null], "note": "null: \"none\""}"#).unwrap();
+///
+/// assert_eq!(edn[":user-name"], Edn::Str("rose".to_string()));
+/// assert_eq!(
+///     edn.to_string(),
+///     "{:note \"null: \\\"none\\\"\", :tags [\"a\" nil], :user-name \"rose\"}"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Same as [`JsonReadOptions::parse`].
+#[cfg(feature = "json")]
+pub fn from_json(json: &str) -> Result<Edn, EdnError> {
+    JsonReadOptions::new().parse(json)
+}
+
+#[cfg(feature = "async")]
+pub use deserialize::async_reader::AsyncEdnReader;
+pub use deserialize::options::{ReaderOptions, TagRegistry};
+pub use deserialize::parse::parse_borrowed;
+#[cfg(feature = "std")]
+pub use deserialize::reader::EdnReader;
 pub use deserialize::{Deserialize, from_edn, from_str};
+pub use diff::{Diff, diff, patch};
 pub use edn::Error as EdnError;
 #[cfg(feature = "sets")]
 pub use edn::Set;
-pub use edn::{Edn, List, Map, Vector};
+pub use edn::{
+    Edn, EdnRef, Inst, Keyword, List, Map, OrderedMap, ParseError, ParseErrorKind, Symbol, Uuid,
+    Vector,
+};
+#[cfg(feature = "json")]
+pub use json::reader::{BigIntegers, Decimals, JsonReadOptions, KeyPolicy};
+#[cfg(feature = "json")]
+pub use json::writer::{JsonOptions, KeyCase, Rationals, Sets, Tags, to_json_with};
 pub use serialize::Serialize;
 
 /// Function for converting Rust types into EDN Strings.
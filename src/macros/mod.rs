@@ -48,9 +48,8 @@
 ///     let expected = Edn::Map(
 ///         Map::new(
 ///             map!{
-///                 String::from("1.2") => Edn::Bool(false),
-///                 // Note `:b` becomes `b`
-///                 String::from(":b") => Edn::Rational(String::from("3/4"))
+///                 Edn::Double(1.2.into()) => Edn::Bool(false),
+///                 Edn::Key(String::from(":b")) => Edn::Rational(String::from("3/4"))
 ///             }
 ///         )
 ///     );
@@ -75,11 +74,11 @@
 ///             Edn::Int(3),
 ///             Edn::Map(
 ///                 Map::new( map![
-///                     String::from("false") => Edn::Map(
+///                     Edn::Bool(false) => Edn::Map(
 ///                         Map::new( map![
-///                             String::from(":f") => Edn::Key(String::from(":b"))
+///                             Edn::Key(String::from(":f")) => Edn::Key(String::from(":b"))
 ///                         ])),
-///                     String::from("nil") => Edn::Vector(
+///                     Edn::Nil => Edn::Vector(
 ///                         Vector::new( vec![
 ///                             Edn::Rational("3/4".to_string()),
 ///                             Edn::Int(1i64)
@@ -142,7 +141,7 @@ macro_rules! edn_internal {
 
     // this matches an even number of things between square brackets
     (@seq @map [$($key:expr_2021, $val:expr_2021,)*]) => {
-        map!{$(std::format!("{}", $key) => $val),*}
+        map!{$($key => $val),*}
     };
 
     // eat commas with no effect
@@ -0,0 +1,224 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+#[cfg(feature = "sets")]
+use crate::edn::Set;
+use crate::edn::{Edn, List, Map, Vector};
+
+type Resolver = dyn Fn(&[Edn], Edn, Edn) -> Edn + Send + Sync;
+
+/// How [`Edn::deep_merge`] merges two vectors, or two lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeqMerge {
+    /// The merged vector replaces the current one
+    #[default]
+    Replace,
+    /// The elements of the merged vector are appended to the current one
+    Concat,
+}
+
+/// `MergeStrategy` configures [`Edn::deep_merge`].
+///
+/// Maps are always merged key by key and sets are always united. Vectors and lists follow
+/// [`SeqMerge`], and any other pair of different values is a conflict given to the resolver,
+/// the merged value winning when there is none.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::Edn;
+/// use edn_rs::merge::{MergeStrategy, SeqMerge};
+///
+/// let strategy = MergeStrategy::new()
+///     .vectors(SeqMerge::Concat)
+///     .resolve(|path: &[Edn], current: Edn, other: Edn| {
+///         if path.last() == Some(&Edn::Key(":pool-size".to_string())) {
+///             current.to_uint().max(other.to_uint()).map_or(other, Edn::UInt)
+///         } else {
+///             other
+///         }
+///     });
+///
+/// let mut config = Edn::from_str("{:db {:host \"localhost\" :pool-size 8} :features [:search]}").unwrap();
+/// config.deep_merge(Edn::from_str("{:db {:host \"db-1\" :pool-size 4} :features [:audit]}").unwrap(), &strategy);
+///
+/// assert_eq!(
+///     config,
+///     Edn::from_str("{:db {:host \"db-1\" :pool-size 8} :features [:search :audit]}").unwrap()
+/// );
+/// ```
+#[derive(Default)]
+#[non_exhaustive]
+pub struct MergeStrategy {
+    pub vectors: SeqMerge,
+    resolve: Option<Box<Resolver>>,
+}
+
+impl MergeStrategy {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            vectors: SeqMerge::Replace,
+            resolve: None,
+        }
+    }
+
+    /// Sets how vectors and lists are merged.
+    #[must_use]
+    pub const fn vectors(mut self, vectors: SeqMerge) -> Self {
+        self.vectors = vectors;
+        self
+    }
+
+    /// Sets the function resolving conflicts, called with the path of the conflicting value,
+    /// as in [`crate::query::Match`], the current value and the merged one.
+    #[must_use]
+    pub fn resolve<F>(mut self, resolve: F) -> Self
+    where
+        F: Fn(&[Edn], Edn, Edn) -> Edn + Send + Sync + 'static,
+    {
+        self.resolve = Some(Box::new(resolve));
+        self
+    }
+
+    fn merge(&self, path: &mut Vec<Edn>, into: &mut Edn, other: Edn) {
+        match (into, other) {
+            (into @ (Edn::Map(_) | Edn::OrderedMap(_)), Edn::Map(Map(entries))) => {
+                self.merge_entries(path, into, entries);
+            }
+            (into @ (Edn::Map(_) | Edn::OrderedMap(_)), Edn::OrderedMap(map)) => {
+                self.merge_entries(path, into, map.to_vec());
+            }
+            (Edn::Vector(Vector(seq)), Edn::Vector(Vector(other)))
+            | (Edn::List(List(seq)), Edn::List(List(other))) => match self.vectors {
+                SeqMerge::Replace => *seq = other,
+                SeqMerge::Concat => seq.extend(other),
+            },
+            #[cfg(feature = "sets")]
+            (Edn::Set(Set(set)), Edn::Set(Set(other))) => set.extend(other),
+            (into, other) if *into == other => {}
+            (into, other) => {
+                *into = match &self.resolve {
+                    Some(resolve) => resolve(path, mem::replace(into, Edn::Nil), other),
+                    None => other,
+                };
+            }
+        }
+    }
+
+    fn merge_entries(
+        &self,
+        path: &mut Vec<Edn>,
+        into: &mut Edn,
+        entries: impl IntoIterator<Item = (Edn, Edn)>,
+    ) {
+        for (key, value) in entries {
+            let current = match into {
+                Edn::Map(map) => map.0.get_mut(&key),
+                Edn::OrderedMap(map) => map.get_mut(&key),
+                _ => None,
+            };
+            if let Some(current) = current {
+                path.push(key);
+                self.merge(path, current, value);
+                path.pop();
+            } else {
+                match into {
+                    Edn::Map(map) => {
+                        map.0.insert(key, value);
+                    }
+                    Edn::OrderedMap(map) => {
+                        map.insert(key, value);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for MergeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeStrategy")
+            .field("vectors", &self.vectors)
+            .field("resolve", &self.resolve.is_some())
+            .finish()
+    }
+}
+
+impl Edn {
+    /// Merges `other` into `self` recursively following `strategy`, like a layer of
+    /// configuration over the previous ones. Keys only in `other` are added at the end of an
+    /// `OrderedMap`.
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use edn_rs::Edn;
+    /// use edn_rs::merge::MergeStrategy;
+    ///
+    /// let mut config = Edn::from_str("{:db {:host \"localhost\" :port 5432} :debug false}").unwrap();
+    /// config.deep_merge(Edn::from_str("{:db {:host \"db-1\"} :debug true}").unwrap(), &MergeStrategy::new());
+    ///
+    /// assert_eq!(
+    ///     config,
+    ///     Edn::from_str("{:db {:host \"db-1\" :port 5432} :debug true}").unwrap()
+    /// );
+    /// ```
+    pub fn deep_merge(&mut self, other: Self, strategy: &MergeStrategy) {
+        strategy.merge(&mut Vec::new(), self, other);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use alloc::vec;
+    use core::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn merges_sequences() {
+        let mut replaced = Edn::from_str("{:a [1 2] :b (1)}").unwrap();
+        replaced.deep_merge(
+            Edn::from_str("{:a [3] :b (2)}").unwrap(),
+            &MergeStrategy::new(),
+        );
+        assert_eq!(replaced, Edn::from_str("{:a [3] :b (2)}").unwrap());
+
+        let mut concatenated = Edn::from_str("{:a [1 2] :b (1)}").unwrap();
+        concatenated.deep_merge(
+            Edn::from_str("{:a [3] :b (2) :c [4]}").unwrap(),
+            &MergeStrategy::new().vectors(SeqMerge::Concat),
+        );
+        assert_eq!(
+            concatenated,
+            Edn::from_str("{:a [1 2 3] :b (1 2) :c [4]}").unwrap()
+        );
+    }
+
+    #[test]
+    fn resolves_conflicts_with_their_path() {
+        let strategy = MergeStrategy::new().resolve(|path: &[Edn], current: Edn, other: Edn| {
+            Edn::Vector(Vector::new(vec![
+                Edn::Vector(Vector::new(path.to_vec())),
+                current,
+                other,
+            ]))
+        });
+        let mut config = Edn::from_str("{:a {:b 1 :c 2} :d [1]}").unwrap();
+        config.deep_merge(
+            Edn::from_str("{:a {:b 1 :c \"2\"} :d {:e 1}}").unwrap(),
+            &strategy,
+        );
+
+        assert_eq!(
+            config,
+            Edn::from_str("{:a {:b 1 :c [[:a :c] 2 \"2\"]} :d [[:d] [1] {:e 1}]}").unwrap()
+        );
+        assert_eq!(
+            format!("{strategy:?}"),
+            "MergeStrategy { vectors: Replace, resolve: true }"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::edn::{Edn, Keyword};
+
+/// Layout of [`to_string_pretty`].
+///
+/// Build it from `PrettyOptions::default()`, which uses an indentation of 2 spaces, a width
+/// of 80 chars, no alignment, no commas, the map's own order and fully qualified keys:
+///
+/// ```rust
+/// use edn_rs::pretty::PrettyOptions;
+///
+/// let options = PrettyOptions { width: 100, map_commas: true, ..PrettyOptions::default() };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct PrettyOptions {
+    /// Spaces added for each nested collection that is broken over lines
+    pub indent: usize,
+    /// Maximum line width in chars. Collections that do not fit are broken over lines, scalars
+    /// are never split, so a long string may still go past it.
+    pub width: usize,
+    /// Pads the keys of a map broken over lines so its values start on the same column
+    pub align_map_values: bool,
+    /// Separates map entries with commas, like `Display` does
+    pub map_commas: bool,
+    /// Sorts map entries by their printed key, otherwise they keep the map's order
+    pub sort_keys: bool,
+    /// Writes a map whose keys are all keywords of the same namespace with Clojure's namespaced
+    /// map syntax, `{:user/id 1 :user/name "a"}` as `#:user{:id 1 :name "a"}`
+    pub namespace_maps: bool,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            width: 80,
+            align_map_values: false,
+            map_commas: false,
+            sort_keys: false,
+            namespace_maps: false,
+        }
+    }
+}
+
+/// `to_string_pretty` emits `edn` over several lines when it does not fit in `options.width`.
+///
+/// A collection that fits in the rest of the line is written on it. Otherwise each of its
+/// elements, or map entries, goes on its own line indented by `options.indent`, with the
+/// closing delimiter on a line of its own. Adding or removing an element only changes its
+/// own line, which keeps diffs of EDN files readable.
+#[must_use]
+pub fn to_string_pretty(edn: &Edn, options: PrettyOptions) -> String {
+    let mut printer = Printer {
+        options,
+        out: String::new(),
+        column: 0,
+    };
+    let node = printer.node(edn);
+    printer.write(&node, 0, 0);
+    printer.out
+}
+
+// `edn` ready to print, with the width it takes on a single line worked out once, bottom-up,
+// so that deciding whether a nested collection fits does not print it again for each level
+struct Node<'a> {
+    width: usize,
+    kind: Kind<'a>,
+}
+
+enum Kind<'a> {
+    Scalar(String),
+    Seq(&'static str, Vec<Node<'a>>, &'static str),
+    // The `#:ns` prefix and the entries with their keys written flat
+    Map(String, Vec<(String, Node<'a>)>),
+    Tagged(&'a str, Box<Node<'a>>),
+}
+
+struct Printer {
+    options: PrettyOptions,
+    out: String,
+    // Chars written since the last newline
+    column: usize,
+}
+
+impl Printer {
+    fn push(&mut self, s: &str) {
+        match s.rfind('\n') {
+            Some(i) => self.column = s[i + 1..].chars().count(),
+            None => self.column += s.chars().count(),
+        }
+        self.out.push_str(s);
+    }
+
+    fn newline(&mut self, indent: usize) {
+        self.out.push('\n');
+        self.out.extend(core::iter::repeat_n(' ', indent));
+        self.column = indent;
+    }
+
+    /// Writes `node` on a line indented by `indent`, keeping room for `trailing` chars after it.
+    fn write(&mut self, node: &Node<'_>, indent: usize, trailing: usize) {
+        if self.column + node.width + trailing <= self.options.width {
+            let mut flat = String::new();
+            self.write_flat(&mut flat, node);
+            self.push(&flat);
+            return;
+        }
+
+        match &node.kind {
+            Kind::Seq(open, items, close) => self.write_seq(open, items, close, indent),
+            Kind::Map(prefix, entries) => self.write_map(prefix, entries, indent),
+            Kind::Tagged(tag, node) => {
+                self.push("#");
+                self.push(tag);
+                self.push(" ");
+                self.write(node, indent, trailing);
+            }
+            Kind::Scalar(scalar) => self.push(scalar),
+        }
+    }
+
+    fn write_seq(&mut self, open: &str, items: &[Node<'_>], close: &str, indent: usize) {
+        let inner = indent + self.options.indent;
+        self.push(open);
+        if items.is_empty() {
+            self.push(close);
+            return;
+        }
+        for item in items {
+            self.newline(inner);
+            self.write(item, inner, 0);
+        }
+        self.newline(indent);
+        self.push(close);
+    }
+
+    fn write_map(&mut self, prefix: &str, entries: &[(String, Node<'_>)], indent: usize) {
+        let inner = indent + self.options.indent;
+        let key_width = if self.options.align_map_values {
+            entries
+                .iter()
+                .map(|(key, _)| key.chars().count())
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        self.push(prefix);
+        self.push("{");
+        if entries.is_empty() {
+            self.push("}");
+            return;
+        }
+        for (i, (key, value)) in entries.iter().enumerate() {
+            let comma = self.options.map_commas && i + 1 < entries.len();
+            self.newline(inner);
+            self.push(key);
+            let padding = key_width.saturating_sub(key.chars().count()) + 1;
+            self.out.extend(core::iter::repeat_n(' ', padding));
+            self.column += padding;
+            self.write(value, inner, usize::from(comma));
+            if comma {
+                self.push(",");
+            }
+        }
+        self.newline(indent);
+        self.push("}");
+    }
+
+    /// Map entries with their keys written flat, in the order they are printed, after the
+    /// `#:ns` prefix of a namespaced map or an empty one.
+    fn entries<'a>(&self, map: &'a Edn) -> (String, Vec<(String, &'a Edn)>) {
+        let namespace = self.shared_namespace(map);
+        let mut entries: Vec<(String, &Edn)> = map
+            .map_entries()
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| match (&namespace, key.to_keyword()) {
+                (Some(_), Some(keyword)) => ([":", keyword.name()].concat(), value),
+                _ => (self.flat(key), value),
+            })
+            .collect();
+        if self.options.sort_keys {
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        let prefix = namespace.map_or_else(String::new, |namespace| ["#:", &namespace].concat());
+        (prefix, entries)
+    }
+
+    /// The namespace of all keys of `map` when `namespace_maps` is set and they share one.
+    fn shared_namespace(&self, map: &Edn) -> Option<String> {
+        if !self.options.namespace_maps {
+            return None;
+        }
+        let mut namespaces = map.map_entries()?.into_iter().map(|(key, _)| {
+            key.to_keyword()
+                .filter(|keyword| !keyword.is_auto_resolved())
+                .as_ref()
+                .and_then(Keyword::namespace)
+                .map(ToString::to_string)
+        });
+        let namespace = namespaces.next()??;
+        namespaces
+            .all(|other| other.as_ref() == Some(&namespace))
+            .then_some(namespace)
+    }
+
+    const fn map_separator(&self) -> &'static str {
+        if self.options.map_commas { ", " } else { " " }
+    }
+
+    /// `node` on a single line, same as `Display` but following the options.
+    fn write_flat(&self, out: &mut String, node: &Node<'_>) {
+        match &node.kind {
+            Kind::Scalar(scalar) => out.push_str(scalar),
+            Kind::Seq(open, items, close) => {
+                out.push_str(open);
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(' ');
+                    }
+                    self.write_flat(out, item);
+                }
+                out.push_str(close);
+            }
+            Kind::Map(prefix, entries) => {
+                out.push_str(prefix);
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(self.map_separator());
+                    }
+                    out.push_str(key);
+                    out.push(' ');
+                    self.write_flat(out, value);
+                }
+                out.push('}');
+            }
+            Kind::Tagged(tag, node) => {
+                out.push('#');
+                out.push_str(tag);
+                out.push(' ');
+                self.write_flat(out, node);
+            }
+        }
+    }
+
+    fn flat(&self, edn: &Edn) -> String {
+        let mut flat = String::new();
+        self.write_flat(&mut flat, &self.node(edn));
+        flat
+    }
+
+    fn node<'a>(&self, edn: &'a Edn) -> Node<'a> {
+        match edn {
+            Edn::Vector(_) => self.node_seq("[", edn.iter_some(), "]"),
+            Edn::List(_) => self.node_seq("(", edn.iter_some(), ")"),
+            #[cfg(feature = "sets")]
+            Edn::Set(_) => self.node_seq("#{", edn.set_iter(), "}"),
+            Edn::Map(_) | Edn::OrderedMap(_) => {
+                let (prefix, entries) = self.entries(edn);
+                let entries: Vec<(String, Node<'_>)> = entries
+                    .into_iter()
+                    .map(|(key, value)| (key, self.node(value)))
+                    .collect();
+                let separators = entries.len().saturating_sub(1) * self.map_separator().len();
+                let width = prefix.chars().count()
+                    + 2
+                    + separators
+                    + entries
+                        .iter()
+                        .map(|(key, value)| key.chars().count() + 1 + value.width)
+                        .sum::<usize>();
+                Node {
+                    width,
+                    kind: Kind::Map(prefix, entries),
+                }
+            }
+            Edn::Tagged(tag, edn) => {
+                let node = self.node(edn);
+                Node {
+                    width: tag.chars().count() + 2 + node.width,
+                    kind: Kind::Tagged(tag, Box::new(node)),
+                }
+            }
+            _ => {
+                let scalar = edn.to_string();
+                Node {
+                    width: scalar.chars().count(),
+                    kind: Kind::Scalar(scalar),
+                }
+            }
+        }
+    }
+
+    fn node_seq<'a>(
+        &self,
+        open: &'static str,
+        items: Option<impl Iterator<Item = &'a Edn>>,
+        close: &'static str,
+    ) -> Node<'a> {
+        let items: Vec<Node<'_>> = items
+            .into_iter()
+            .flatten()
+            .map(|item| self.node(item))
+            .collect();
+        let width = open.len()
+            + close.len()
+            + items.len().saturating_sub(1)
+            + items.iter().map(|item| item.width).sum::<usize>();
+        Node {
+            width,
+            kind: Kind::Seq(open, items, close),
+        }
+    }
+}
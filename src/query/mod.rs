@@ -0,0 +1,477 @@
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::mem;
+use core::str::FromStr;
+
+use crate::cst;
+use crate::edn::utils::index::str_key;
+use crate::edn::{Edn, Error};
+
+/// A step of a [`Query`], selecting elements from each element selected so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// The value of a key in a map, the element at an index of a vector or list, or an element
+    /// of a set
+    Key(Edn),
+    /// `*`, all values of a map and all elements of a vector, list or set
+    Wildcard,
+    /// `..`, the element itself and everything nested in it, at any depth
+    Descendants,
+    /// `#{...}`, the values of a map whose keys are in the set, and the elements of a vector,
+    /// list or set that are in it
+    Members(BTreeSet<Edn>),
+    /// `(op path value)`, the values of a map and elements of a vector, list or set for which
+    /// the predicate holds
+    Filter(Predicate),
+}
+
+/// Test of a [`Step::Filter`] on the element at `path` in each candidate, `[]` being the
+/// candidate itself. A missing element is `nil`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Predicate {
+    pub path: Vec<Edn>,
+    pub test: Test,
+}
+
+/// Numbers compare by value whatever their type, other elements only compare to elements of
+/// the same type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Test {
+    /// `(some? path)`, the element is there and is not `nil`
+    Some,
+    /// `(= path value)`
+    Eq(Edn),
+    /// `(not= path value)`
+    NotEq(Edn),
+    /// `(< path value)`
+    Lt(Edn),
+    /// `(<= path value)`
+    Le(Edn),
+    /// `(> path value)`
+    Gt(Edn),
+    /// `(>= path value)`
+    Ge(Edn),
+}
+
+/// An element selected by a query and the path to it: the keys of maps, the indexes of
+/// vectors and lists as `Edn::UInt`, and the elements of sets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match<'a> {
+    pub path: Vec<Edn>,
+    pub value: &'a Edn,
+}
+
+/// `Query` selects all the elements of an `Edn` matching a list of [`Step`]s.
+///
+/// Its string form is the steps written as EDN and separated by whitespace, so keys are read
+/// as with `edn[":key"]`. `*` and `..` are the wildcard and the recursive descent, a set
+/// selects its members and a list is a predicate.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::Edn;
+/// use edn_rs::query::Query;
+///
+/// let edn = Edn::from_str(
+///     "{:users [{:name \"ana\" :role :admin :age 41} {:name \"bo\" :role :dev :age 29}]}",
+/// )
+/// .unwrap();
+///
+/// let query = Query::from_str(":users (= :role :admin) :name").unwrap();
+/// let matches = query.select(&edn);
+///
+/// assert_eq!(matches[0].value, &Edn::Str("ana".to_string()));
+/// assert_eq!(matches[0].path, [Edn::Key(":users".to_string()), Edn::UInt(0), Edn::Key(":name".to_string())]);
+///
+/// let ages = edn.query(".. :age").unwrap();
+/// assert_eq!(ages.iter().map(|m| m.value.to_uint().unwrap()).collect::<Vec<_>>(), [41, 29]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Query(pub Vec<Step>);
+
+impl Query {
+    /// All elements matching the steps, in the order of their collections.
+    #[must_use]
+    pub fn select<'a>(&self, edn: &'a Edn) -> Vec<Match<'a>> {
+        let mut matches = vec![Match {
+            path: Vec::new(),
+            value: edn,
+        }];
+        for step in &self.0 {
+            matches = mem::take(&mut matches)
+                .into_iter()
+                .flat_map(|m| step.select(m))
+                .collect();
+        }
+        matches
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    /// # Errors
+    ///
+    /// `EdnError::Parse` when `s` is not valid EDN, and `EdnError::ParseEdn` for a predicate
+    /// that is not one of the [`Test`]s.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        cst::parse(s)?
+            .lower()?
+            .into_iter()
+            .map(Step::read)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl Step {
+    fn read(edn: Edn) -> Result<Self, Error> {
+        Ok(match edn {
+            Edn::Symbol(s) if s == "*" => Self::Wildcard,
+            Edn::Symbol(s) if s == ".." => Self::Descendants,
+            #[cfg(feature = "sets")]
+            Edn::Set(set) => Self::Members(set.to_set()),
+            Edn::List(list) => Self::Filter(Predicate::read(&list.to_vec())?),
+            edn => Self::Key(edn),
+        })
+    }
+
+    fn select<'a>(&self, from: Match<'a>) -> Vec<Match<'a>> {
+        match self {
+            Self::Key(key) => lookup(from.value, key)
+                .map(|value| vec![from.child(key.clone(), value)])
+                .unwrap_or_default(),
+            Self::Wildcard => children(&from),
+            Self::Descendants => {
+                let mut descendants = Vec::new();
+                descend(from, &mut descendants);
+                descendants
+            }
+            Self::Members(set) => children(&from)
+                .into_iter()
+                .filter(|m| match from.value {
+                    Edn::Map(_) | Edn::OrderedMap(_) => {
+                        m.path.last().is_some_and(|k| set.contains(k))
+                    }
+                    _ => set.contains(m.value),
+                })
+                .collect(),
+            Self::Filter(predicate) => children(&from)
+                .into_iter()
+                .filter(|m| predicate.holds(m.value))
+                .collect(),
+        }
+    }
+}
+
+impl Predicate {
+    fn read(list: &[Edn]) -> Result<Self, Error> {
+        let invalid = || {
+            let list = list.iter().map(Edn::to_string).collect::<Vec<_>>();
+            Error::ParseEdn(format!("Invalid query predicate ({})", list.join(" ")))
+        };
+        let path = |path: &Edn| match path {
+            Edn::Vector(v) => v.clone().to_vec(),
+            key => vec![key.clone()],
+        };
+        let test = match list {
+            [Edn::Symbol(op), p] if op == "some?" => {
+                return Ok(Self {
+                    path: path(p),
+                    test: Test::Some,
+                });
+            }
+            [Edn::Symbol(op), p, value] => (op.as_str(), path(p), value.clone()),
+            _ => return Err(invalid()),
+        };
+        let (op, path, value) = test;
+        let test = match op {
+            "=" => Test::Eq(value),
+            "not=" => Test::NotEq(value),
+            "<" => Test::Lt(value),
+            "<=" => Test::Le(value),
+            ">" => Test::Gt(value),
+            ">=" => Test::Ge(value),
+            _ => return Err(invalid()),
+        };
+        Ok(Self { path, test })
+    }
+
+    /// Whether the test holds for the element at `path` in `edn`.
+    #[must_use]
+    pub fn holds(&self, edn: &Edn) -> bool {
+        let value = self
+            .path
+            .iter()
+            .try_fold(edn, |edn, key| lookup(edn, key))
+            .unwrap_or(&Edn::Nil);
+        let order = |other: &Edn| compare(value, other);
+        match &self.test {
+            Test::Some => *value != Edn::Nil,
+            Test::Eq(other) => order(other) == Some(Ordering::Equal),
+            Test::NotEq(other) => order(other) != Some(Ordering::Equal),
+            Test::Lt(other) => order(other) == Some(Ordering::Less),
+            Test::Le(other) => order(other).is_some_and(Ordering::is_le),
+            Test::Gt(other) => order(other) == Some(Ordering::Greater),
+            Test::Ge(other) => order(other).is_some_and(Ordering::is_ge),
+        }
+    }
+}
+
+impl Match<'_> {
+    fn child<'b>(&self, key: Edn, value: &'b Edn) -> Match<'b> {
+        let mut path = self.path.clone();
+        path.push(key);
+        Match { path, value }
+    }
+}
+
+const fn is_number(edn: &Edn) -> bool {
+    matches!(
+        edn,
+        Edn::Int(_)
+            | Edn::UInt(_)
+            | Edn::Double(_)
+            | Edn::Rational(_)
+            | Edn::BigInt(_)
+            | Edn::Decimal(_)
+    )
+}
+
+fn compare(a: &Edn, b: &Edn) -> Option<Ordering> {
+    if let (Some(a), Some(b)) = (integer(a), integer(b)) {
+        Some(compare_integers(&a, &b))
+    } else if is_number(a) && is_number(b) {
+        a.to_float()?.partial_cmp(&b.to_float()?)
+    } else if mem::discriminant(a) == mem::discriminant(b) {
+        Some(a.cmp(b))
+    } else {
+        None
+    }
+}
+
+// An integer as its sign and digits without leading zeros, so that integers beyond 2^53,
+// like Datomic entity ids, are compared exactly rather than as `f64`
+fn integer(edn: &Edn) -> Option<(bool, String)> {
+    let n = match edn {
+        Edn::Int(i) => i.to_string(),
+        Edn::UInt(u) => u.to_string(),
+        Edn::BigInt(n) => n.clone(),
+        _ => return None,
+    };
+    let (negative, digits) = n
+        .strip_prefix('-')
+        .map_or((false, n.as_str()), |digits| (true, digits));
+    let digits = digits.trim_start_matches('0');
+    Some((negative && !digits.is_empty(), digits.to_string()))
+}
+
+fn compare_integers(
+    (a_negative, a): &(bool, String),
+    (b_negative, b): &(bool, String),
+) -> Ordering {
+    let magnitude = a.len().cmp(&b.len()).then_with(|| a.cmp(b));
+    match (a_negative, b_negative) {
+        (false, true) => Ordering::Greater,
+        (true, false) => Ordering::Less,
+        (false, false) => magnitude,
+        (true, true) => magnitude.reverse(),
+    }
+}
+
+fn lookup<'a>(edn: &'a Edn, key: &Edn) -> Option<&'a Edn> {
+    match edn {
+        Edn::Map(_) | Edn::OrderedMap(_) => edn.get(key),
+        Edn::Vector(_) | Edn::List(_) => {
+            let i = match *key {
+                Edn::UInt(i) => usize::try_from(i).ok()?,
+                Edn::Int(i) => usize::try_from(i).ok()?,
+                _ => return None,
+            };
+            edn.get(i)
+        }
+        #[cfg(feature = "sets")]
+        Edn::Set(_) => edn.set_iter()?.find(|&e| e == key),
+        _ => None,
+    }
+}
+
+fn children<'a>(from: &Match<'a>) -> Vec<Match<'a>> {
+    match from.value {
+        Edn::Vector(_) | Edn::List(_) => from
+            .value
+            .iter_some()
+            .into_iter()
+            .flatten()
+            .zip(0u64..)
+            .map(|(value, i)| from.child(Edn::UInt(i), value))
+            .collect(),
+        #[cfg(feature = "sets")]
+        Edn::Set(_) => from
+            .value
+            .set_iter()
+            .into_iter()
+            .flatten()
+            .map(|e| from.child(e.clone(), e))
+            .collect(),
+        edn => edn
+            .map_entries()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(k, v)| from.child(k.clone(), v))
+            .collect(),
+    }
+}
+
+fn descend<'a>(from: Match<'a>, descendants: &mut Vec<Match<'a>>) {
+    let nested = children(&from);
+    descendants.push(from);
+    for child in nested {
+        descend(child, descendants);
+    }
+}
+
+impl From<Edn> for Step {
+    fn from(edn: Edn) -> Self {
+        Self::Key(edn)
+    }
+}
+
+/// A key read as EDN like with `edn[":name"]`. A `str` that is not a single EDN element
+/// selects nothing, a string key is `Step::Key(Edn::Str(..))`.
+impl From<&str> for Step {
+    fn from(key: &str) -> Self {
+        str_key(key).map_or_else(|| Self::Members(BTreeSet::new()), Self::Key)
+    }
+}
+
+macro_rules! step_from_int {
+    ( $( $int:ty => $variant:ident ),+ ) => {
+        $(
+            impl From<$int> for Step {
+                fn from(i: $int) -> Self {
+                    Self::Key(Edn::$variant(i.into()))
+                }
+            }
+        )+
+    };
+}
+
+step_from_int![u32 => UInt, u64 => UInt, i32 => Int, i64 => Int];
+
+impl From<usize> for Step {
+    fn from(i: usize) -> Self {
+        Self::Key(Edn::UInt(i as u64))
+    }
+}
+
+impl Edn {
+    /// The first element at `path`, following [`Query::select`].
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use edn_rs::Edn;
+    ///
+    /// let edn = Edn::from_str("{:users [{:name \"ana\"}]}").unwrap();
+    ///
+    /// assert_eq!(
+    ///     edn.get_in(&[":users".into(), 0.into(), ":name".into()]),
+    ///     Some(&Edn::Str("ana".to_string()))
+    /// );
+    /// assert_eq!(edn.get_in(&[":users".into(), 1.into()]), None);
+    /// ```
+    #[must_use]
+    pub fn get_in(&self, path: &[Step]) -> Option<&Self> {
+        let query = Query(path.to_vec());
+        query.select(self).into_iter().next().map(|m| m.value)
+    }
+
+    /// All elements matching `steps`, see [`Query`].
+    #[must_use]
+    pub fn select(&self, steps: &[Step]) -> Vec<Match<'_>> {
+        Query(steps.to_vec()).select(self)
+    }
+
+    /// All elements matching `query`, see [`Query`] for its syntax.
+    ///
+    /// # Errors
+    ///
+    /// Same as `Query::from_str`.
+    pub fn query(&self, query: &str) -> Result<Vec<Match<'_>>, Error> {
+        Ok(Query::from_str(query)?.select(self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn reads_steps() {
+        assert_eq!(
+            Query::from_str(":a 0 \"b c\" * .. (some? [:d :e]) (>= :f 1.5)").unwrap(),
+            Query(vec![
+                Step::Key(Edn::Key(":a".to_string())),
+                Step::Key(Edn::UInt(0)),
+                Step::Key(Edn::Str("b c".to_string())),
+                Step::Wildcard,
+                Step::Descendants,
+                Step::Filter(Predicate {
+                    path: vec![Edn::Key(":d".to_string()), Edn::Key(":e".to_string())],
+                    test: Test::Some
+                }),
+                Step::Filter(Predicate {
+                    path: vec![Edn::Key(":f".to_string())],
+                    test: Test::Ge(Edn::Double(1.5.into()))
+                }),
+            ])
+        );
+        assert_eq!(
+            Query::from_str("(like :a \"b\")"),
+            Err(Error::ParseEdn(
+                "Invalid query predicate (like :a \"b\")".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn compares_numbers_by_value() {
+        assert_eq!(compare(&Edn::Int(2), &Edn::UInt(2)), Some(Ordering::Equal));
+        assert_eq!(
+            compare(&Edn::Rational("1/2".to_string()), &Edn::Double(0.4.into())),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare(
+                &Edn::UInt(9_007_199_254_740_993),
+                &Edn::UInt(9_007_199_254_740_992)
+            ),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare(
+                &Edn::BigInt("-100000000000000000000".to_string()),
+                &Edn::Int(i64::MIN)
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare(&Edn::BigInt("-0".to_string()), &Edn::UInt(0)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare(&Edn::Key(":a".to_string()), &Edn::Str(":a".to_string())),
+            None
+        );
+        assert_eq!(
+            compare(&Edn::Key(":a".to_string()), &Edn::Key(":b".to_string())),
+            Some(Ordering::Less)
+        );
+    }
+}
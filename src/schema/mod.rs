@@ -0,0 +1,592 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::edn::{Edn, Error, OrderedMap, Vector};
+
+/// A compiled schema, written in EDN with Malli's vector syntax.
+///
+/// Types are keywords, `:any`, `:nil`, `:boolean`, `:int`, `:double`, `:number`, `:string`,
+/// `:keyword`, `:qualified-keyword`, `:symbol`, `:char`, `:uuid` and `:inst`, and vectors
+/// combine them:
+/// - `[:map [:key schema] [:key {:optional true} schema]]`, closed to other keys with
+///   `[:map {:closed true} ...]`
+/// - `[:map-of key-schema value-schema]`
+/// - `[:vector schema]`, `[:list schema]`, `[:set schema]`, `[:sequential schema]` and
+///   `[:tuple schema...]`
+/// - `[:maybe schema]`, `[:or schema...]` and `[:and schema...]`
+/// - `[:enum value...]` and `[:= value]`
+///
+/// A properties map after the type bounds numbers with `{:min 1 :max 10}`, and the length
+/// of strings and collections, `[:string {:min 1}]`.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::Edn;
+/// use edn_rs::schema::Schema;
+///
+/// let user = Schema::from_str(
+///     "[:map [:id :int] [:tags [:vector :keyword]] [:email {:optional true} [:string {:min 3}]]]",
+/// )
+/// .unwrap();
+///
+/// assert!(user.is_valid(&Edn::from_str("{:id 1 :tags [:admin]}").unwrap()));
+///
+/// let explanation = user.validate(&Edn::from_str("{:id \"1\" :tags [:a \"b\"]}").unwrap()).unwrap_err();
+/// assert_eq!(
+///     explanation.errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+///     [
+///         "{:path [:id], :in [:id], :schema :int, :value \"1\"}",
+///         "{:path [:tags 0], :in [:tags 1], :schema :keyword, :value \"b\"}"
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    form: Edn,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Type(Type, Bounds),
+    Map { entries: Vec<Entry>, closed: bool },
+    MapOf(Box<Schema>, Box<Schema>, Bounds),
+    Vector(Box<Schema>, Bounds),
+    List(Box<Schema>, Bounds),
+    Set(Box<Schema>, Bounds),
+    Sequential(Box<Schema>, Bounds),
+    Tuple(Vec<Schema>),
+    Maybe(Box<Schema>),
+    Or(Vec<Schema>),
+    And(Vec<Schema>),
+    Enum(Vec<Edn>),
+    Eq(Edn),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Type {
+    Any,
+    Nil,
+    Boolean,
+    Int,
+    Double,
+    Number,
+    String,
+    Keyword,
+    QualifiedKeyword,
+    Symbol,
+    Char,
+    Uuid,
+    Inst,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    key: Edn,
+    optional: bool,
+    schema: Schema,
+}
+
+// `:min` and `:max`, kept as read so that schemas stay comparable
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Bounds {
+    min: Option<Edn>,
+    max: Option<Edn>,
+}
+
+/// Why a value does not match a [`Schema`], from [`Schema::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub schema: Edn,
+    pub value: Edn,
+    pub errors: Vec<Problem>,
+}
+
+/// A value at `path` not matching `schema`. The path holds the keys of maps, the indexes of
+/// vectors and lists as `Edn::UInt`, and the elements of sets, as in
+/// [`crate::query::Match`].
+///
+/// `schema_path` is where `schema` is in the validated schema: the keys of `:map` entries, and
+/// the indexes of the children of other schemas, properties aside, so `1` is the value schema
+/// of a `:map-of`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub path: Vec<Edn>,
+    pub schema_path: Vec<Edn>,
+    pub schema: Edn,
+    pub value: Edn,
+    pub kind: ProblemKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProblemKind {
+    /// The value is not valid for the schema
+    Invalid,
+    /// A required key of a `:map` is missing, the value is `nil`
+    MissingKey,
+    /// A key that is not in a closed `:map`, the schema is the map's
+    ExtraKey,
+}
+
+impl Schema {
+    /// Compiles the schema written as `form`.
+    ///
+    /// # Errors
+    ///
+    /// `EdnError::Schema` when `form` is not a valid schema.
+    pub fn compile(form: &Edn) -> Result<Self, Error> {
+        let invalid = || Error::Schema(format!("Invalid schema {form}"));
+        let kind = match form {
+            Edn::Key(k) => Kind::Type(Type::read(k).ok_or_else(invalid)?, Bounds::default()),
+            Edn::Vector(Vector(items)) => {
+                let [Edn::Key(head), rest @ ..] = items.as_slice() else {
+                    return Err(invalid());
+                };
+                let (props, children) = match rest {
+                    [props @ (Edn::Map(_) | Edn::OrderedMap(_)), children @ ..] => {
+                        (Some(props), children)
+                    }
+                    _ => (None, rest),
+                };
+                let bounds = Bounds::read(props);
+                let one = || match children {
+                    [child] => Self::compile(child).map(Box::new),
+                    _ => Err(invalid()),
+                };
+                let all = || children.iter().map(Self::compile).collect::<Result<_, _>>();
+                match (head.as_str(), children) {
+                    (":map", _) => Kind::Map {
+                        entries: children
+                            .iter()
+                            .map(|entry| Entry::compile(entry).unwrap_or_else(|| Err(invalid())))
+                            .collect::<Result<_, _>>()?,
+                        closed: props.and_then(|p| p.get(":closed")) == Some(&Edn::Bool(true)),
+                    },
+                    (":map-of", [key, value]) => Kind::MapOf(
+                        Box::new(Self::compile(key)?),
+                        Box::new(Self::compile(value)?),
+                        bounds,
+                    ),
+                    (":vector", _) => Kind::Vector(one()?, bounds),
+                    (":list", _) => Kind::List(one()?, bounds),
+                    (":set", _) => Kind::Set(one()?, bounds),
+                    (":sequential", _) => Kind::Sequential(one()?, bounds),
+                    (":tuple", _) => Kind::Tuple(all()?),
+                    (":maybe", _) => Kind::Maybe(one()?),
+                    (":or", [_, ..]) => Kind::Or(all()?),
+                    (":and", [_, ..]) => Kind::And(all()?),
+                    (":enum", [_, ..]) => Kind::Enum(children.to_vec()),
+                    (":=", [value]) => Kind::Eq(value.clone()),
+                    (k, []) => Kind::Type(Type::read(k).ok_or_else(invalid)?, bounds),
+                    _ => return Err(invalid()),
+                }
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            form: form.clone(),
+            kind,
+        })
+    }
+
+    /// The EDN form the schema was compiled from.
+    #[must_use]
+    pub const fn form(&self) -> &Edn {
+        &self.form
+    }
+
+    /// Whether `value` matches the schema.
+    #[must_use]
+    pub fn is_valid(&self, value: &Edn) -> bool {
+        self.validate(value).is_ok()
+    }
+
+    /// Checks that `value` matches the schema.
+    ///
+    /// # Errors
+    ///
+    /// The [`Explanation`] of every part of `value` that does not match.
+    pub fn validate(&self, value: &Edn) -> Result<(), Explanation> {
+        let mut explainer = Explainer::default();
+        self.explain(&mut explainer, value);
+        if explainer.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Explanation {
+                schema: self.form.clone(),
+                value: value.clone(),
+                errors: explainer.errors,
+            })
+        }
+    }
+
+    fn explain(&self, at: &mut Explainer, value: &Edn) {
+        let valid = match (&self.kind, value) {
+            (Kind::Type(ty, bounds), _) => ty.accepts(value) && bounds.accept(ty.size(value)),
+            (Kind::Map { entries, closed }, Edn::Map(_) | Edn::OrderedMap(_)) => {
+                for entry in entries {
+                    let key = &entry.key;
+                    match value.get(key) {
+                        Some(v) => at.child(&entry.schema, key.clone(), Some(key.clone()), v),
+                        None if entry.optional => {}
+                        None => {
+                            at.problem(key, &entry.schema.form, Edn::Nil, ProblemKind::MissingKey);
+                        }
+                    }
+                }
+                let extra = value
+                    .map_entries()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(k, _)| *closed && entries.iter().all(|e| e.key != **k));
+                for (key, v) in extra {
+                    at.problem(key, &self.form, v.clone(), ProblemKind::ExtraKey);
+                }
+                true
+            }
+            (Kind::MapOf(key_schema, value_schema, bounds), Edn::Map(_) | Edn::OrderedMap(_)) => {
+                let entries = value.map_entries().unwrap_or_default();
+                for &(k, v) in &entries {
+                    at.child(key_schema, Edn::UInt(0), Some(k.clone()), k);
+                    at.child(value_schema, Edn::UInt(1), Some(k.clone()), v);
+                }
+                bounds.accept(Some(entries.len()))
+            }
+            (Kind::Vector(schema, bounds), Edn::Vector(_))
+            | (Kind::List(schema, bounds), Edn::List(_))
+            | (Kind::Sequential(schema, bounds), Edn::Vector(_) | Edn::List(_)) => {
+                let items = value.iter_some().into_iter().flatten();
+                let mut len = 0;
+                for (i, item) in items.enumerate() {
+                    at.child(schema, Edn::UInt(0), Some(Edn::UInt(i as u64)), item);
+                    len += 1;
+                }
+                bounds.accept(Some(len))
+            }
+            #[cfg(feature = "sets")]
+            (Kind::Set(schema, bounds), Edn::Set(set)) => {
+                for item in &set.0 {
+                    at.child(schema, Edn::UInt(0), Some(item.clone()), item);
+                }
+                bounds.accept(Some(set.0.len()))
+            }
+            (Kind::Tuple(schemas), Edn::Vector(Vector(items))) if schemas.len() == items.len() => {
+                for (i, (schema, item)) in schemas.iter().zip(items).enumerate() {
+                    at.child(schema, Edn::UInt(i as u64), Some(Edn::UInt(i as u64)), item);
+                }
+                true
+            }
+            (Kind::Maybe(_), Edn::Nil) => true,
+            (Kind::Maybe(schema), _) => {
+                at.child(schema, Edn::UInt(0), None, value);
+                true
+            }
+            (Kind::Or(schemas), _) => schemas.iter().any(|schema| schema.is_valid(value)),
+            (Kind::And(schemas), _) => {
+                for (i, schema) in schemas.iter().enumerate() {
+                    at.child(schema, Edn::UInt(i as u64), None, value);
+                }
+                true
+            }
+            (Kind::Enum(values), _) => values.contains(value),
+            (Kind::Eq(expected), _) => expected == value,
+            _ => false,
+        };
+        if !valid {
+            at.errors.push(Problem {
+                path: at.path.clone(),
+                schema_path: at.schema_path.clone(),
+                schema: self.form.clone(),
+                value: value.clone(),
+                kind: ProblemKind::Invalid,
+            });
+        }
+    }
+}
+
+// Where `Schema::explain` is in the value and in the schema, and the problems found so far
+#[derive(Default)]
+struct Explainer {
+    path: Vec<Edn>,
+    schema_path: Vec<Edn>,
+    errors: Vec<Problem>,
+}
+
+impl Explainer {
+    // Explains `value` with `schema`, the child of the current schema at `schema_key`. `value`
+    // is the child of the current value at `key`, or the current value itself
+    fn child(&mut self, schema: &Schema, schema_key: Edn, key: Option<Edn>, value: &Edn) {
+        let nested = key.map(|key| self.path.push(key)).is_some();
+        self.schema_path.push(schema_key);
+        schema.explain(self, value);
+        self.schema_path.pop();
+        if nested {
+            self.path.pop();
+        }
+    }
+
+    // A problem with the entry at `key` of a map, which is at `key` in both paths
+    fn problem(&mut self, key: &Edn, schema: &Edn, value: Edn, kind: ProblemKind) {
+        self.errors.push(Problem {
+            path: child_path(&self.path, key),
+            schema_path: child_path(&self.schema_path, key),
+            schema: schema.clone(),
+            value,
+            kind,
+        });
+    }
+}
+
+impl FromStr for Schema {
+    type Err = Error;
+
+    /// # Errors
+    ///
+    /// `EdnError::Parse` when `s` is not valid EDN, and `EdnError::Schema` when it is not a
+    /// valid schema.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::compile(&Edn::from_str(s)?)
+    }
+}
+
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.form)
+    }
+}
+
+impl Type {
+    fn read(keyword: &str) -> Option<Self> {
+        Some(match keyword {
+            ":any" => Self::Any,
+            ":nil" => Self::Nil,
+            ":boolean" => Self::Boolean,
+            ":int" => Self::Int,
+            ":double" => Self::Double,
+            ":number" => Self::Number,
+            ":string" => Self::String,
+            ":keyword" => Self::Keyword,
+            ":qualified-keyword" => Self::QualifiedKeyword,
+            ":symbol" => Self::Symbol,
+            ":char" => Self::Char,
+            ":uuid" => Self::Uuid,
+            ":inst" => Self::Inst,
+            _ => return None,
+        })
+    }
+
+    fn accepts(self, value: &Edn) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Nil => *value == Edn::Nil,
+            Self::Boolean => matches!(value, Edn::Bool(_)),
+            Self::Int => matches!(value, Edn::Int(_) | Edn::UInt(_) | Edn::BigInt(_)),
+            Self::Double => matches!(value, Edn::Double(_)),
+            Self::Number => matches!(
+                value,
+                Edn::Int(_)
+                    | Edn::UInt(_)
+                    | Edn::BigInt(_)
+                    | Edn::Double(_)
+                    | Edn::Rational(_)
+                    | Edn::Decimal(_)
+            ),
+            Self::String => matches!(value, Edn::Str(_)),
+            Self::Keyword => matches!(value, Edn::Key(_)),
+            Self::QualifiedKeyword => value.to_keyword().is_some_and(|k| k.namespace().is_some()),
+            Self::Symbol => matches!(value, Edn::Symbol(_)),
+            Self::Char => matches!(value, Edn::Char(_)),
+            Self::Uuid => value.to_uuid().is_some(),
+            Self::Inst => value.to_inst().is_some(),
+        }
+    }
+
+    // What `:min` and `:max` bound: the length of strings, and numbers themselves
+    fn size(self, value: &Edn) -> Size {
+        match (self, value) {
+            (Self::String, Edn::Str(s)) => Size::Len(s.chars().count()),
+            (Self::Int | Self::Double | Self::Number, _) => {
+                value.to_float().map_or(Size::None, Size::Number)
+            }
+            _ => Size::None,
+        }
+    }
+}
+
+enum Size {
+    Len(usize),
+    Number(f64),
+    None,
+}
+
+impl From<Option<usize>> for Size {
+    fn from(len: Option<usize>) -> Self {
+        len.map_or(Self::None, Self::Len)
+    }
+}
+
+impl Bounds {
+    fn read(props: Option<&Edn>) -> Self {
+        let bound = |key: &str| props.and_then(|p| p.get(key)).cloned();
+        Self {
+            min: bound(":min"),
+            max: bound(":max"),
+        }
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        reason = "lengths are compared to bounds written in a schema"
+    )]
+    fn accept(&self, size: impl Into<Size>) -> bool {
+        let size = match size.into() {
+            Size::Len(len) => len as f64,
+            Size::Number(n) => n,
+            Size::None => return true,
+        };
+        let min = self.min.as_ref().and_then(Edn::to_float);
+        let max = self.max.as_ref().and_then(Edn::to_float);
+        min.is_none_or(|min| size >= min) && max.is_none_or(|max| size <= max)
+    }
+}
+
+impl Entry {
+    fn compile(form: &Edn) -> Option<Result<Self, Error>> {
+        let Edn::Vector(Vector(items)) = form else {
+            return None;
+        };
+        let (key, props, schema) = match items.as_slice() {
+            [key, schema] => (key, None, schema),
+            [key, props @ (Edn::Map(_) | Edn::OrderedMap(_)), schema] => (key, Some(props), schema),
+            _ => return None,
+        };
+        Some(Schema::compile(schema).map(|schema| Self {
+            key: key.clone(),
+            optional: props.and_then(|p| p.get(":optional")) == Some(&Edn::Bool(true)),
+            schema,
+        }))
+    }
+}
+
+fn child_path(path: &[Edn], key: &Edn) -> Vec<Edn> {
+    let mut path = path.to_vec();
+    path.push(key.clone());
+    path
+}
+
+fn key(k: &str) -> Edn {
+    Edn::Key(k.to_string())
+}
+
+impl Problem {
+    /// The EDN form of the problem, `{:path [...], :in [...], :schema ..., :value ...}` with
+    /// `:type :malli.core/missing-key` or `:type :malli.core/extra-key` for keys of maps. As in
+    /// Malli's explain data, `:path` is the schema path and `:in` the path in the value.
+    #[must_use]
+    pub fn to_edn(&self) -> Edn {
+        let mut entries = vec![
+            (
+                key(":path"),
+                Edn::Vector(Vector::new(self.schema_path.clone())),
+            ),
+            (key(":in"), Edn::Vector(Vector::new(self.path.clone()))),
+            (key(":schema"), self.schema.clone()),
+            (key(":value"), self.value.clone()),
+        ];
+        match self.kind {
+            ProblemKind::Invalid => {}
+            ProblemKind::MissingKey => {
+                entries.push((key(":type"), key(":malli.core/missing-key")));
+            }
+            ProblemKind::ExtraKey => entries.push((key(":type"), key(":malli.core/extra-key"))),
+        }
+        Edn::OrderedMap(OrderedMap::new(entries))
+    }
+}
+
+impl Explanation {
+    /// The EDN form of the explanation, `{:schema ..., :value ..., :errors [...]}` with the
+    /// errors as in [`Problem::to_edn`].
+    #[must_use]
+    pub fn to_edn(&self) -> Edn {
+        let errors = self.errors.iter().map(Problem::to_edn).collect();
+        Edn::OrderedMap(OrderedMap::new(vec![
+            (key(":schema"), self.schema.clone()),
+            (key(":value"), self.value.clone()),
+            (key(":errors"), Edn::Vector(Vector::new(errors))),
+        ]))
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_edn())
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_edn())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn compiles_schemas() {
+        for form in [
+            ":int",
+            "[:string {:min 1 :max 8}]",
+            "[:map {:closed true} [:id :int] [:name {:optional true} :string]]",
+            "[:map-of :keyword [:vector [:maybe :double]]]",
+            "[:or :nil [:enum :a :b] [:= 1] [:tuple :int :symbol]]",
+        ] {
+            assert_eq!(
+                Schema::from_str(form).unwrap().to_string(),
+                Edn::from_str(form).unwrap().to_string()
+            );
+        }
+        for (form, message) in [
+            (":integer", "Invalid schema :integer"),
+            ("[:vector :int :int]", "Invalid schema [:vector :int :int]"),
+            ("[:map [:id]]", "Invalid schema [:map [:id]]"),
+            ("[:map [:id :nope]]", "Invalid schema :nope"),
+            ("[:or]", "Invalid schema [:or]"),
+            ("{:type :int}", "Invalid schema {:type :int}"),
+        ] {
+            assert_eq!(
+                Schema::from_str(form),
+                Err(Error::Schema(message.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let schema = Schema::from_str("[:vector {:max 2} [:int {:min 0 :max 10}]]").unwrap();
+
+        assert!(schema.is_valid(&Edn::from_str("[0 10]").unwrap()));
+        assert_eq!(
+            schema
+                .validate(&Edn::from_str("[-1 11 5]").unwrap())
+                .unwrap_err()
+                .errors
+                .into_iter()
+                .map(|e| e.path)
+                .collect::<Vec<_>>(),
+            [vec![Edn::UInt(0)], vec![Edn::UInt(1)], vec![]]
+        );
+        assert!(
+            Schema::from_str("[:string {:min 2}]")
+                .unwrap()
+                .is_valid(&Edn::from_str("\"çé\"").unwrap())
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use ::serde::de::value::BorrowedStrDeserializer;
+use ::serde::de::{self, DeserializeSeed, IntoDeserializer, Unexpected, Visitor};
+use ::serde::forward_to_deserialize_any;
+
+use super::token;
+use crate::edn::{Edn, Error};
+
+/// `Deserializer` is a `serde::Deserializer` reading from a borrowed [`Edn`],
+/// see [`super::from_edn`].
+#[derive(Debug, Clone, Copy)]
+pub struct Deserializer<'de> {
+    edn: &'de Edn,
+}
+
+impl<'de> Deserializer<'de> {
+    #[must_use]
+    pub const fn new(edn: &'de Edn) -> Self {
+        Self { edn }
+    }
+
+    // Lossless form used by `Edn`'s own `Deserialize`, EDN-only types are sent as enum
+    // variants that only `Edn`'s visitor understands.
+    fn deserialize_edn<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let (kind, content) = match self.edn {
+            Edn::Key(k) => ("Key", RawContent::Str(k)),
+            Edn::Symbol(s) => ("Symbol", RawContent::Str(s)),
+            Edn::Rational(r) => ("Rational", RawContent::Str(r)),
+            Edn::BigInt(n) => ("BigInt", RawContent::Str(n)),
+            Edn::Decimal(d) => ("Decimal", RawContent::Str(d)),
+            Edn::List(_) => ("List", RawContent::Seq(self.edn)),
+            Edn::OrderedMap(_) => ("OrderedMap", RawContent::Seq(self.edn)),
+            #[cfg(feature = "sets")]
+            Edn::Set(_) => ("Set", RawContent::Seq(self.edn)),
+            Edn::Tagged(tag, edn) => ("Tagged", RawContent::Tagged(tag, edn)),
+            Edn::Empty => ("Empty", RawContent::Unit),
+            _ => return de::Deserializer::deserialize_any(self, visitor),
+        };
+        visitor.visit_enum(RawEnum { kind, content })
+    }
+}
+
+fn unexpected(edn: &Edn) -> Unexpected<'_> {
+    match edn {
+        Edn::Bool(b) => Unexpected::Bool(*b),
+        Edn::Int(i) => Unexpected::Signed(*i),
+        Edn::UInt(u) => Unexpected::Unsigned(*u),
+        Edn::Double(_) | Edn::Rational(_) => Unexpected::Float(edn.to_float().unwrap_or(f64::NAN)),
+        Edn::Char(c) => Unexpected::Char(*c),
+        Edn::Str(s) => Unexpected::Str(s),
+        Edn::Key(_) => Unexpected::Other("keyword"),
+        Edn::Symbol(_) => Unexpected::Other("symbol"),
+        Edn::BigInt(_) => Unexpected::Other("bigint"),
+        Edn::Decimal(_) => Unexpected::Other("decimal"),
+        Edn::Vector(_) | Edn::List(_) => Unexpected::Seq,
+        #[cfg(feature = "sets")]
+        Edn::Set(_) => Unexpected::Seq,
+        Edn::Map(_) | Edn::OrderedMap(_) => Unexpected::Map,
+        Edn::Tagged(_, _) => Unexpected::Other("tagged element"),
+        Edn::Nil | Edn::Empty => Unexpected::Unit,
+    }
+}
+
+// Map keys naming a field or variant, `:name`, `"name"` and `name` are all accepted.
+fn identifier(edn: &Edn) -> Option<&str> {
+    match edn {
+        Edn::Key(k) => Some(k.strip_prefix(':').unwrap_or(k)),
+        Edn::Str(s) | Edn::Symbol(s) => Some(s),
+        _ => None,
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.edn {
+            Edn::Tagged(_, edn) => Deserializer::new(edn).deserialize_any(visitor),
+            Edn::Vector(_) | Edn::List(_) => visitor.visit_seq(SeqAccess {
+                iter: self.edn.iter_some().into_iter().flatten(),
+            }),
+            #[cfg(feature = "sets")]
+            Edn::Set(_) => visitor.visit_seq(SeqAccess {
+                iter: self.edn.set_iter().into_iter().flatten(),
+            }),
+            Edn::Map(_) | Edn::OrderedMap(_) => visitor.visit_map(MapAccess {
+                iter: self.edn.map_entries().into_iter().flatten(),
+                value: None,
+            }),
+            Edn::Key(s) | Edn::Symbol(s) | Edn::Str(s) => visitor.visit_borrowed_str(s),
+            // Exact digits, `rust_decimal` and most arbitrary-precision types read them
+            Edn::BigInt(n) | Edn::Decimal(n) => visitor.visit_borrowed_str(n),
+            Edn::Int(i) => visitor.visit_i64(*i),
+            Edn::UInt(u) => visitor.visit_u64(*u),
+            Edn::Double(_) | Edn::Rational(_) => match self.edn.to_float() {
+                Some(f) => visitor.visit_f64(f),
+                None => Err(de::Error::invalid_value(unexpected(self.edn), &visitor)),
+            },
+            Edn::Char(c) => visitor.visit_char(*c),
+            Edn::Bool(b) => visitor.visit_bool(*b),
+            Edn::Nil | Edn::Empty => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.edn {
+            Edn::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if name == token::EDN {
+            self.deserialize_edn(visitor)
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let (variant, content) = match self.edn {
+            Edn::Tagged(tag, edn) => (tag.as_str(), Some(&**edn)),
+            Edn::Map(_) | Edn::OrderedMap(_) => {
+                let mut entries = self.edn.map_entries().into_iter().flatten();
+                match (entries.next(), entries.next()) {
+                    (Some((key, edn)), None) => match identifier(key) {
+                        Some(variant) => (variant, Some(edn)),
+                        None => return Err(de::Error::invalid_type(unexpected(key), &visitor)),
+                    },
+                    _ => {
+                        return Err(de::Error::invalid_value(
+                            Unexpected::Map,
+                            &"a map with a single key",
+                        ));
+                    }
+                }
+            }
+            edn => match identifier(edn) {
+                Some(variant) => (variant, None),
+                None => return Err(de::Error::invalid_type(unexpected(edn), &visitor)),
+            },
+        };
+        visitor.visit_enum(EnumAccess { variant, content })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match identifier(self.edn) {
+            Some(id) => visitor.visit_borrowed_str(id),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Deserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+struct SeqAccess<I> {
+    iter: I,
+}
+
+impl<'de, I> de::SeqAccess<'de> for SeqAccess<I>
+where
+    I: Iterator<Item = &'de Edn>,
+{
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.iter
+            .next()
+            .map(|edn| seed.deserialize(Deserializer::new(edn)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        match self.iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(upper),
+            _ => None,
+        }
+    }
+}
+
+struct MapAccess<'de, I> {
+    iter: I,
+    value: Option<&'de Edn>,
+}
+
+impl<'de, I> de::MapAccess<'de> for MapAccess<'de, I>
+where
+    I: Iterator<Item = (&'de Edn, &'de Edn)>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(Deserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| <Error as de::Error>::custom("value is missing"))?;
+        seed.deserialize(Deserializer::new(value))
+    }
+}
+
+struct EnumAccess<'de> {
+    variant: &'de str,
+    content: Option<&'de Edn>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+    type Error = Error;
+    type Variant = VariantAccess<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess<'de>), Error> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<Error>::new(self.variant))?;
+        Ok((
+            variant,
+            VariantAccess {
+                content: self.content,
+            },
+        ))
+    }
+}
+
+struct VariantAccess<'de> {
+    content: Option<&'de Edn>,
+}
+
+impl<'de> VariantAccess<'de> {
+    fn content(self, expected: &str) -> Result<Deserializer<'de>, Error> {
+        self.content
+            .map(Deserializer::new)
+            .ok_or_else(|| de::Error::invalid_type(Unexpected::UnitVariant, &expected))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.content {
+            None | Some(Edn::Nil) => Ok(()),
+            Some(edn) => Err(de::Error::invalid_type(unexpected(edn), &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.content("newtype variant")?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self.content("tuple variant")?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self.content("struct variant")?, visitor)
+    }
+}
+
+enum RawContent<'de> {
+    Str(&'de str),
+    Seq(&'de Edn),
+    Tagged(&'de str, &'de Edn),
+    Unit,
+}
+
+struct RawEnum<'de> {
+    kind: &'static str,
+    content: RawContent<'de>,
+}
+
+impl<'de> de::EnumAccess<'de> for RawEnum<'de> {
+    type Error = Error;
+    type Variant = RawContent<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, RawContent<'de>), Error> {
+        let kind = seed.deserialize(BorrowedStrDeserializer::<Error>::new(self.kind))?;
+        Ok((kind, self.content))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for RawContent<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        match self {
+            Self::Str(s) => seed.deserialize(BorrowedStrDeserializer::new(s)),
+            Self::Seq(edn) => seed.deserialize(Deserializer::new(edn)),
+            Self::Tagged(tag, edn) => seed.deserialize(TaggedDeserializer { tag, edn }),
+            Self::Unit => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, _visitor: V) -> Result<V::Value, Error> {
+        Err(de::Error::invalid_type(
+            Unexpected::NewtypeVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Error> {
+        Err(de::Error::invalid_type(
+            Unexpected::NewtypeVariant,
+            &"struct variant",
+        ))
+    }
+}
+
+// `#tag edn` as the two element sequence `(tag, edn)`
+struct TaggedDeserializer<'de> {
+    tag: &'de str,
+    edn: &'de Edn,
+}
+
+impl<'de> de::Deserializer<'de> for TaggedDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(TaggedAccess {
+            tag: Some(self.tag),
+            edn: Some(self.edn),
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct TaggedAccess<'de> {
+    tag: Option<&'de str>,
+    edn: Option<&'de Edn>,
+}
+
+impl<'de> de::SeqAccess<'de> for TaggedAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if let Some(tag) = self.tag.take() {
+            return seed
+                .deserialize(BorrowedStrDeserializer::new(tag))
+                .map(Some);
+        }
+        self.edn
+            .take()
+            .map(|edn| seed.deserialize(Deserializer::new(edn)))
+            .transpose()
+    }
+}
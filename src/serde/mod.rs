@@ -0,0 +1,93 @@
+use alloc::string::{String, ToString};
+use core::str::FromStr;
+
+use ::serde::de::DeserializeOwned;
+use ::serde::{Deserialize, Serialize};
+
+use crate::edn::{Edn, Error};
+
+mod de;
+mod ser;
+mod value;
+
+pub use de::Deserializer;
+pub use ser::Serializer;
+
+// Newtype struct names used by the `Edn` serde impls to carry EDN-only types through
+// `Serializer` and `Deserializer` without losing them.
+pub(crate) mod token {
+    pub const EDN: &str = "$edn_rs::private::Edn";
+    pub const KEYWORD: &str = "$edn_rs::private::Keyword";
+    pub const SYMBOL: &str = "$edn_rs::private::Symbol";
+    pub const RATIONAL: &str = "$edn_rs::private::Rational";
+    pub const BIG_INT: &str = "$edn_rs::private::BigInt";
+    pub const DECIMAL: &str = "$edn_rs::private::Decimal";
+    pub const TAGGED: &str = "$edn_rs::private::Tagged";
+    pub const LIST: &str = "$edn_rs::private::List";
+    #[cfg(feature = "sets")]
+    pub const SET: &str = "$edn_rs::private::Set";
+    pub const EMPTY: &str = "$edn_rs::private::Empty";
+}
+
+/// `to_edn` converts any `T: serde::Serialize` into an `Edn`.
+///
+/// # Errors
+///
+/// Fails when `T`'s `Serialize` implementation fails or when an integer does not fit in
+/// an `i64`/`u64`.
+pub fn to_edn<T>(value: &T) -> Result<Edn, Error>
+where
+    T: ?Sized + Serialize,
+{
+    value.serialize(Serializer)
+}
+
+/// `to_string` converts any `T: serde::Serialize` into an EDN `String`.
+///
+/// # Errors
+///
+/// Same as [`to_edn`].
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: ?Sized + Serialize,
+{
+    to_edn(value).map(|edn| edn.to_string())
+}
+
+/// `from_edn` converts an `Edn` into any `T: serde::Deserialize`, borrowing strings from `edn`
+/// when `T` allows it.
+///
+/// # Errors
+///
+/// Fails with `Error::Deserialize` when `edn` does not match the shape of `T`.
+pub fn from_edn<'de, T>(edn: &'de Edn) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(Deserializer::new(edn))
+}
+
+/// `from_str` parses an EDN `&str` into any `T: serde::Deserialize`.
+///
+/// # Errors
+///
+/// Fails with `Error::Parse` when `s` is not valid EDN, otherwise same as [`from_edn`].
+pub fn from_str<T>(s: &str) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    let edn = Edn::from_str(s)?;
+    from_edn(&edn)
+}
+
+impl ::serde::ser::Error for Error {
+    fn custom<T: core::fmt::Display>(msg: T) -> Self {
+        Self::Serialize(msg.to_string())
+    }
+}
+
+impl ::serde::de::Error for Error {
+    fn custom<T: core::fmt::Display>(msg: T) -> Self {
+        Self::Deserialize(msg.to_string())
+    }
+}
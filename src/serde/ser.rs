@@ -0,0 +1,405 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use ::serde::ser::{self, Serialize};
+
+use super::token;
+#[cfg(feature = "sets")]
+use crate::edn::Set;
+use crate::edn::{Edn, Error, Keyword, List, Map, Symbol, Vector};
+
+/// `Serializer` is a `serde::Serializer` whose output is an [`Edn`], see [`super::to_edn`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Edn;
+    type Error = Error;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Edn, Error> {
+        Ok(Edn::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Edn, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Edn, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Edn, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Edn, Error> {
+        Ok(Edn::Int(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Edn, Error> {
+        i64::try_from(v)
+            .map(Edn::Int)
+            .map_err(|_| Error::Serialize(format!("{v} does not fit in an i64")))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Edn, Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Edn, Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Edn, Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Edn, Error> {
+        Ok(Edn::UInt(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Edn, Error> {
+        u64::try_from(v)
+            .map(Edn::UInt)
+            .map_err(|_| Error::Serialize(format!("{v} does not fit in an u64")))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Edn, Error> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Edn, Error> {
+        Ok(Edn::Double(v.into()))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Edn, Error> {
+        Ok(Edn::Char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Edn, Error> {
+        Ok(Edn::Str(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Edn, Error> {
+        Ok(Edn::Vector(Vector::new(
+            v.iter().map(|b| Edn::UInt(u64::from(*b))).collect(),
+        )))
+    }
+
+    fn serialize_none(self) -> Result<Edn, Error> {
+        Ok(Edn::Nil)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Edn, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Edn, Error> {
+        Ok(Edn::Nil)
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Edn, Error> {
+        if name == token::EMPTY {
+            Ok(Edn::Empty)
+        } else {
+            Ok(Edn::Nil)
+        }
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Edn, Error> {
+        keyword(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Edn, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        match (name, value.serialize(self)?) {
+            (token::KEYWORD, Edn::Str(k)) => Ok(Edn::Key(k)),
+            (token::SYMBOL, Edn::Str(s)) => Ok(Edn::Symbol(s)),
+            (token::RATIONAL, Edn::Str(r)) => Ok(Edn::Rational(r)),
+            (token::BIG_INT, Edn::Str(n)) => Ok(Edn::BigInt(n)),
+            (token::DECIMAL, Edn::Str(d)) => Ok(Edn::Decimal(d)),
+            (token::LIST, Edn::Vector(v)) => Ok(Edn::List(List::new(v.to_vec()))),
+            #[cfg(feature = "sets")]
+            (token::SET, Edn::Vector(v)) => {
+                Ok(Edn::Set(Set::new(v.to_vec().into_iter().collect())))
+            }
+            (token::TAGGED, Edn::Map(m)) => match m.to_map().pop_first() {
+                Some((Edn::Str(tag), edn)) => Ok(Edn::Tagged(tag, Box::new(edn))),
+                _ => Err(Error::Serialize(String::from(
+                    "Tagged element without a tag",
+                ))),
+            },
+            (_, edn) => Ok(edn),
+        }
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Edn, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(Edn::Tagged(tag(variant)?, Box::new(value.serialize(self)?)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec, Error> {
+        Ok(SerializeVec {
+            vec: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVec, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeVec, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, Error> {
+        Ok(SerializeTupleVariant {
+            tag: tag(variant)?,
+            vec: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            map: BTreeMap::new(),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeMap, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStructVariant, Error> {
+        Ok(SerializeStructVariant {
+            tag: tag(variant)?,
+            map: BTreeMap::new(),
+        })
+    }
+}
+
+// `user/id` is the keyword `:user/id`, names that cannot be keywords, like `first name`, are
+// errors rather than keywords that would not read back
+fn keyword(field: &str) -> Result<Edn, Error> {
+    let (namespace, name) = field
+        .split_once('/')
+        .filter(|(_, name)| !name.is_empty())
+        .map_or((None, field), |(namespace, name)| (Some(namespace), name));
+    Keyword::new(namespace, name)
+        .map(Edn::from)
+        .map_err(|_| Error::Serialize(format!("{field:?} cannot be written as a keyword")))
+}
+
+// Variants holding data are tagged elements, `#Circle 1.5`. A tag must be a symbol starting
+// with a letter, `_Private` would be read as the discard `#_` and `my variant` as two elements
+fn tag(variant: &str) -> Result<String, Error> {
+    if Symbol::parse(variant).is_ok() && variant.starts_with(char::is_alphabetic) {
+        Ok(variant.to_string())
+    } else {
+        Err(Error::Serialize(format!(
+            "{variant:?} cannot be written as a tag"
+        )))
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeVec {
+    vec: Vec<Edn>,
+}
+
+impl ser::SerializeSeq for SerializeVec {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.vec.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(Edn::Vector(Vector::new(self.vec)))
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeTupleVariant {
+    tag: String,
+    vec: Vec<Edn>,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.vec.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(Edn::Tagged(
+            self.tag,
+            Box::new(Edn::Vector(Vector::new(self.vec))),
+        ))
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeMap {
+    map: BTreeMap<Edn, Edn>,
+    next_key: Option<Edn>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.next_key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self.next_key.take().ok_or_else(|| {
+            Error::Serialize(String::from("serialize_value called before serialize_key"))
+        })?;
+        self.map.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(Edn::Map(Map::new(self.map)))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.map.insert(keyword(key)?, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeStructVariant {
+    tag: String,
+    map: BTreeMap<Edn, Edn>,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Edn;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.map.insert(keyword(key)?, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Edn, Error> {
+        Ok(Edn::Tagged(
+            self.tag,
+            Box::new(Edn::Map(Map::new(self.map))),
+        ))
+    }
+}
@@ -0,0 +1,225 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use ::serde::de::{self, Deserialize, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use super::token;
+#[cfg(feature = "sets")]
+use crate::edn::Set;
+use crate::edn::{Edn, List, Map, OrderedMap, Vector};
+
+/// Types without a serde equivalent are wrapped in newtype structs that [`super::Serializer`]
+/// turns back into the matching `Edn`. Other serializers see their inner value, a keyword is
+/// the string `":a"`, a list or set is a sequence and `#tag value` is the map `{"tag" value}`.
+/// An `OrderedMap` is a map with its entries in insertion order.
+impl Serialize for Edn {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Tagged(tag, edn) => {
+                serializer.serialize_newtype_struct(token::TAGGED, &TaggedRef(tag, edn))
+            }
+            Self::Vector(_) => serialize_seq(self.iter_some().into_iter().flatten(), serializer),
+            #[cfg(feature = "sets")]
+            Self::Set(_) => serializer.serialize_newtype_struct(
+                token::SET,
+                &SeqRef(self.set_iter().into_iter().flatten()),
+            ),
+            Self::Map(_) | Self::OrderedMap(_) => {
+                let mut map = serializer.serialize_map(None)?;
+                for (key, value) in self.map_entries().into_iter().flatten() {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            Self::List(_) => serializer.serialize_newtype_struct(
+                token::LIST,
+                &SeqRef(self.iter_some().into_iter().flatten()),
+            ),
+            Self::Key(k) => serializer.serialize_newtype_struct(token::KEYWORD, k),
+            Self::Symbol(s) => serializer.serialize_newtype_struct(token::SYMBOL, s),
+            Self::Str(s) => serializer.serialize_str(s),
+            Self::Int(i) => serializer.serialize_i64(*i),
+            Self::UInt(u) => serializer.serialize_u64(*u),
+            Self::Double(_) => serializer.serialize_f64(self.to_float().unwrap_or(f64::NAN)),
+            Self::Rational(r) => serializer.serialize_newtype_struct(token::RATIONAL, r),
+            Self::BigInt(n) => serializer.serialize_newtype_struct(token::BIG_INT, n),
+            Self::Decimal(d) => serializer.serialize_newtype_struct(token::DECIMAL, d),
+            Self::Char(c) => serializer.serialize_char(*c),
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            Self::Nil => serializer.serialize_unit(),
+            Self::Empty => serializer.serialize_unit_struct(token::EMPTY),
+        }
+    }
+}
+
+fn serialize_seq<'a, I, S>(iter: I, serializer: S) -> Result<S::Ok, S::Error>
+where
+    I: Iterator<Item = &'a Edn>,
+    S: Serializer,
+{
+    let mut seq = serializer.serialize_seq(None)?;
+    for edn in iter {
+        seq.serialize_element(edn)?;
+    }
+    seq.end()
+}
+
+struct SeqRef<I>(I);
+
+impl<'a, I> Serialize for SeqRef<I>
+where
+    I: Iterator<Item = &'a Edn> + Clone,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_seq(self.0.clone(), serializer)
+    }
+}
+
+struct TaggedRef<'a>(&'a str, &'a Edn);
+
+impl Serialize for TaggedRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.0, self.1)?;
+        map.end()
+    }
+}
+
+/// Deserializing from [`super::Deserializer`] is lossless. Other formats produce the closest
+/// `Edn`, strings stay `Edn::Str`, sequences become `Edn::Vector` and maps `Edn::Map`.
+impl<'de> Deserialize<'de> for Edn {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_newtype_struct(token::EDN, EdnVisitor)
+    }
+}
+
+struct EdnVisitor;
+
+impl<'de> Visitor<'de> for EdnVisitor {
+    type Value = Edn;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any EDN value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Edn, E> {
+        Ok(Edn::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Edn, E> {
+        Ok(Edn::Int(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Edn, E> {
+        Ok(Edn::UInt(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Edn, E> {
+        Ok(Edn::Double(v.into()))
+    }
+
+    fn visit_char<E>(self, v: char) -> Result<Edn, E> {
+        Ok(Edn::Char(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Edn, E> {
+        Ok(Edn::Str(String::from(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Edn, E> {
+        Ok(Edn::Str(v))
+    }
+
+    fn visit_none<E>(self) -> Result<Edn, E> {
+        Ok(Edn::Nil)
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Edn, D::Error> {
+        Edn::deserialize(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Edn, E> {
+        Ok(Edn::Nil)
+    }
+
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Edn, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Edn, A::Error> {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(edn) = seq.next_element()? {
+            vec.push(edn);
+        }
+        Ok(Edn::Vector(Vector::new(vec)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Edn, A::Error> {
+        let mut map = BTreeMap::new();
+        while let Some((key, value)) = access.next_entry()? {
+            map.insert(key, value);
+        }
+        Ok(Edn::Map(Map::new(map)))
+    }
+
+    // Only produced by `super::Deserializer` for EDN types serde has no equivalent for.
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Edn, A::Error> {
+        let (kind, variant): (&str, _) = data.variant()?;
+        match kind {
+            "Key" => variant.newtype_variant().map(Edn::Key),
+            "Symbol" => variant.newtype_variant().map(Edn::Symbol),
+            "Rational" => variant.newtype_variant().map(Edn::Rational),
+            "BigInt" => variant.newtype_variant().map(Edn::BigInt),
+            "Decimal" => variant.newtype_variant().map(Edn::Decimal),
+            "OrderedMap" => variant
+                .newtype_variant::<OrderedEntries>()
+                .map(|entries| Edn::OrderedMap(OrderedMap::new(entries.0))),
+            "List" => variant
+                .newtype_variant()
+                .map(|list| Edn::List(List::new(list))),
+            #[cfg(feature = "sets")]
+            "Set" => variant
+                .newtype_variant::<Vec<Edn>>()
+                .map(|set| Edn::Set(Set::new(set.into_iter().collect()))),
+            "Tagged" => variant
+                .newtype_variant::<(String, Edn)>()
+                .map(|(tag, edn)| Edn::Tagged(tag, Box::new(edn))),
+            "Empty" => variant.unit_variant().map(|()| Edn::Empty),
+            _ => Err(de::Error::unknown_variant(kind, &[])),
+        }
+    }
+}
+
+// Entries of a map in the order they are visited
+struct OrderedEntries(Vec<(Edn, Edn)>);
+
+impl<'de> Deserialize<'de> for OrderedEntries {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(OrderedEntriesVisitor)
+    }
+}
+
+struct OrderedEntriesVisitor;
+
+impl<'de> Visitor<'de> for OrderedEntriesVisitor {
+    type Value = OrderedEntries;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an EDN map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<OrderedEntries, A::Error> {
+        let mut entries = Vec::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some(entry) = access.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(OrderedEntries(entries))
+    }
+}
@@ -3,6 +3,8 @@ use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
+use crate::edn::{Edn, Inst, Keyword, Symbol, Uuid, char_to_edn, str_to_edn};
+
 /// Trait that allows you to implement Serialization for each type of your choice.
 /// Example:
 /// ```rust
@@ -190,19 +192,63 @@ impl Serialize for () {
 
 impl Serialize for String {
     fn serialize(&self) -> String {
-        format!("{self:?}")
+        str_to_edn(self)
     }
 }
 
 impl Serialize for &str {
     fn serialize(&self) -> String {
-        format!("{self:?}")
+        str_to_edn(self)
     }
 }
 
 impl Serialize for char {
     fn serialize(&self) -> String {
-        format!("\\{self}")
+        char_to_edn(*self)
+    }
+}
+
+impl Serialize for Keyword {
+    fn serialize(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Serialize for Inst {
+    fn serialize(&self) -> String {
+        format!("#inst \"{self}\"")
+    }
+}
+
+impl Serialize for Uuid {
+    fn serialize(&self) -> String {
+        format!("#uuid \"{self}\"")
+    }
+}
+
+impl Serialize for Edn {
+    fn serialize(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl Serialize for num_bigint::BigInt {
+    fn serialize(&self) -> String {
+        format!("{self}N")
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl Serialize for rust_decimal::Decimal {
+    fn serialize(&self) -> String {
+        format!("{self}M")
     }
 }
 
@@ -0,0 +1,516 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+#[cfg(feature = "sets")]
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "sets")]
+use crate::edn::Set;
+use crate::edn::{Edn, Error, Inst, List, Map, Vector};
+use crate::json::value::{self, Json};
+
+/// How [`to_string`] writes maps, tagged values and repeated keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Maps as `["^ ", key, value...]`, tagged values as `["~#tag", value]`, instants as
+    /// milliseconds `~m`, and keys, keywords, symbols and tags cached as `^0`, `^1`...
+    #[default]
+    Compact,
+    /// Maps as JSON objects, tagged values as `{"~#tag": value}`, instants as `~t` timestamps
+    /// and no cache, for people to read
+    Verbose,
+}
+
+/// Writes `edn` as transit+json.
+///
+/// Keywords, symbols, characters, big numbers, `#inst` and `#uuid` are written as tagged
+/// strings, lists, sets and ratios as tagged values, maps with vectors, lists, sets or maps
+/// as keys as `~#cmap`, and other tagged elements keep their tag, `#my/tag 1` is
+/// `["~#my/tag", 1]`. A scalar at the top is quoted with `~#'`.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use edn_rs::Edn;
+/// use edn_rs::transit::{self, Mode};
+///
+/// let edn = Edn::from_str("[{:id 1 :tags (:a)} {:id 2 :tags ()}]").unwrap();
+///
+/// assert_eq!(
+///     transit::to_string(&edn, Mode::Compact),
+///     r#"[["^ ","~:id",1,"~:tags",["~#list",["~:a"]]],["^ ","^0",2,"^1",["^2",[]]]]"#
+/// );
+/// assert_eq!(
+///     transit::to_string(&edn, Mode::Verbose),
+///     r#"[{"~:id":1,"~:tags":{"~#list":["~:a"]}},{"~:id":2,"~:tags":{"~#list":[]}}]"#
+/// );
+/// assert_eq!(transit::from_str(&transit::to_string(&edn, Mode::Compact)).unwrap(), edn);
+/// ```
+#[must_use]
+pub fn to_string(edn: &Edn, mode: Mode) -> String {
+    let mut writer = Writer {
+        mode,
+        out: String::new(),
+        cache: BTreeMap::new(),
+    };
+    writer.write(edn, false);
+    if writer.out.starts_with(['[', '{']) {
+        writer.out
+    } else {
+        let scalar = writer.out;
+        match mode {
+            Mode::Compact => format!("[\"~#'\",{scalar}]"),
+            Mode::Verbose => format!("{{\"~#'\":{scalar}}}"),
+        }
+    }
+}
+
+/// Reads transit+json, written in either mode, into `Edn`.
+///
+/// Instants become `#inst` elements, `~#ratio` a rational, and tags without an EDN
+/// counterpart, including scalar ones like `~rhttp://a.b`, become tagged elements.
+///
+/// ```rust
+/// use edn_rs::{Edn, transit};
+///
+/// let edn = transit::from_str(r#"["^ ","~:at","~m1594936394628","~:by","~u6f1c8b7e-93a2-4b1b-b5fe-6d9a0b2b4c3d"]"#).unwrap();
+///
+/// assert_eq!(
+///     edn.to_string(),
+///     "{:at #inst \"2020-07-16T21:53:14.628Z\", :by #uuid \"6f1c8b7e-93a2-4b1b-b5fe-6d9a0b2b4c3d\"}"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// `EdnError::Transit` when `transit` is not valid JSON, refers to a key missing from the
+/// cache, or holds a tagged value of the wrong shape.
+pub fn from_str(transit: &str) -> Result<Edn, Error> {
+    let json = value::parse(transit).map_err(Error::Transit)?;
+    Reader { cache: Vec::new() }.read(json)
+}
+
+// `^0` to `^{{`, codes of 1 or 2 digits in base 44 starting from `0`
+const CACHE_CODE_DIGITS: usize = 44;
+const CACHE_SIZE: usize = CACHE_CODE_DIGITS * CACHE_CODE_DIGITS;
+const MAP_AS_ARRAY: &str = "^ ";
+// Integers beyond this are written as strings, JavaScript cannot read them exactly
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+fn is_cacheable(s: &str, as_map_key: bool) -> bool {
+    s.len() > 3 && (as_map_key || s.starts_with("~:") || s.starts_with("~$") || s.starts_with("~#"))
+}
+
+fn cache_code(index: usize) -> String {
+    let digit = |d: usize| char::from(b'0' + u8::try_from(d).unwrap_or_default());
+    if index < CACHE_CODE_DIGITS {
+        format!("^{}", digit(index))
+    } else {
+        format!(
+            "^{}{}",
+            digit(index / CACHE_CODE_DIGITS),
+            digit(index % CACHE_CODE_DIGITS)
+        )
+    }
+}
+
+fn cache_index(code: &str) -> Option<usize> {
+    let digit = |b: u8| {
+        b.checked_sub(b'0')
+            .map(usize::from)
+            .filter(|&d| d < CACHE_CODE_DIGITS)
+    };
+    match code.as_bytes() {
+        [b'^', d] => digit(*d),
+        [b'^', hi, lo] => Some(digit(*hi)? * CACHE_CODE_DIGITS + digit(*lo)?),
+        _ => None,
+    }
+}
+
+struct Writer {
+    mode: Mode,
+    out: String,
+    cache: BTreeMap<String, usize>,
+}
+
+impl Writer {
+    fn string(&mut self, s: &str, as_map_key: bool) {
+        if self.mode == Mode::Compact && is_cacheable(s, as_map_key) {
+            if let Some(&index) = self.cache.get(s) {
+                let code = cache_code(index);
+                value::write_string(&mut self.out, &code);
+                return;
+            }
+            if self.cache.len() == CACHE_SIZE {
+                self.cache.clear();
+            }
+            let index = self.cache.len();
+            self.cache.insert(s.to_string(), index);
+        }
+        value::write_string(&mut self.out, s);
+    }
+
+    fn tagged(&mut self, tag: &str, write: impl FnOnce(&mut Self)) {
+        let tag = format!("~#{tag}");
+        match self.mode {
+            Mode::Compact => {
+                self.out.push('[');
+                self.string(&tag, false);
+                self.out.push(',');
+                write(self);
+                self.out.push(']');
+            }
+            Mode::Verbose => {
+                self.out.push('{');
+                self.string(&tag, false);
+                self.out.push(':');
+                write(self);
+                self.out.push('}');
+            }
+        }
+    }
+
+    fn array<'a>(&mut self, items: impl IntoIterator<Item = &'a Edn>) {
+        self.out.push('[');
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.write(item, false);
+        }
+        self.out.push(']');
+    }
+
+    fn map(&mut self, entries: &[(&Edn, &Edn)]) {
+        if entries.iter().any(|(k, _)| is_composite(k)) {
+            self.tagged("cmap", |writer| {
+                writer.array(entries.iter().flat_map(|&entry| <[&Edn; 2]>::from(entry)));
+            });
+            return;
+        }
+        let (open, close) = match self.mode {
+            Mode::Compact => ('[', ']'),
+            Mode::Verbose => ('{', '}'),
+        };
+        self.out.push(open);
+        if self.mode == Mode::Compact {
+            value::write_string(&mut self.out, MAP_AS_ARRAY);
+        }
+        for (i, (k, v)) in entries.iter().enumerate() {
+            if i > 0 || self.mode == Mode::Compact {
+                self.out.push(',');
+            }
+            self.write(k, true);
+            self.out.push(match self.mode {
+                Mode::Compact => ',',
+                Mode::Verbose => ':',
+            });
+            self.write(v, false);
+        }
+        self.out.push(close);
+    }
+
+    fn write(&mut self, edn: &Edn, as_map_key: bool) {
+        match edn {
+            Edn::Nil | Edn::Empty if as_map_key => self.string("~_", true),
+            Edn::Nil | Edn::Empty => self.out.push_str("null"),
+            Edn::Bool(b) if as_map_key => self.string(if *b { "~?t" } else { "~?f" }, true),
+            Edn::Bool(b) => self.out.push_str(if *b { "true" } else { "false" }),
+            Edn::UInt(n) if *n <= MAX_SAFE_INTEGER && !as_map_key => {
+                self.out.push_str(&n.to_string());
+            }
+            Edn::Int(n) if n.unsigned_abs() <= MAX_SAFE_INTEGER && !as_map_key => {
+                self.out.push_str(&n.to_string());
+            }
+            Edn::UInt(n) => self.string(&format!("~i{n}"), as_map_key),
+            Edn::Int(n) => self.string(&format!("~i{n}"), as_map_key),
+            Edn::Double(_) => {
+                let d = edn.to_float().unwrap_or_default();
+                if d.is_nan() {
+                    self.string("~zNaN", as_map_key);
+                } else if d.is_infinite() {
+                    self.string(if d > 0.0 { "~zINF" } else { "~z-INF" }, as_map_key);
+                } else {
+                    let mut n = d.to_string();
+                    if !n.contains(['.', 'e']) {
+                        n.push_str(".0");
+                    }
+                    if as_map_key {
+                        self.string(&format!("~d{n}"), true);
+                    } else {
+                        self.out.push_str(&n);
+                    }
+                }
+            }
+            Edn::BigInt(n) => self.string(&format!("~n{n}"), as_map_key),
+            Edn::Decimal(d) => self.string(&format!("~f{d}"), as_map_key),
+            Edn::Str(s) if s.starts_with(['~', '^', '`']) => {
+                self.string(&format!("~{s}"), as_map_key);
+            }
+            Edn::Str(s) => self.string(s, as_map_key),
+            Edn::Char(c) => self.string(&format!("~c{c}"), as_map_key),
+            Edn::Key(k) => {
+                let name = k.strip_prefix(':').unwrap_or(k);
+                self.string(&format!("~:{name}"), as_map_key);
+            }
+            Edn::Symbol(s) => self.string(&format!("~${s}"), as_map_key),
+            Edn::Rational(r) => {
+                let (numerator, denominator) = r.split_once('/').unwrap_or((r, "1"));
+                self.tagged("ratio", |writer| {
+                    writer.out.push('[');
+                    writer.string(&format!("~n{numerator}"), false);
+                    writer.out.push(',');
+                    writer.string(&format!("~n{denominator}"), false);
+                    writer.out.push(']');
+                });
+            }
+            Edn::Tagged(tag, element) => match (tag.as_str(), &**element) {
+                ("inst", Edn::Str(s)) => match (self.mode, Inst::parse(s)) {
+                    (Mode::Compact, Ok(inst)) => {
+                        let millis = inst.unix_timestamp_millis();
+                        self.string(&format!("~m{millis}"), as_map_key);
+                    }
+                    _ => self.string(&format!("~t{s}"), as_map_key),
+                },
+                ("uuid", Edn::Str(s)) => self.string(&format!("~u{s}"), as_map_key),
+                _ => self.tagged(tag, |writer| writer.write(element, false)),
+            },
+            Edn::Vector(Vector(items)) => self.array(items),
+            Edn::List(List(items)) => self.tagged("list", |writer| writer.array(items)),
+            #[cfg(feature = "sets")]
+            Edn::Set(Set(items)) => self.tagged("set", |writer| writer.array(items)),
+            Edn::Map(_) | Edn::OrderedMap(_) => self.map(&edn.map_entries().unwrap_or_default()),
+        }
+    }
+}
+
+// Elements that are not written as a string, so cannot be the key of a map as array
+fn is_composite(edn: &Edn) -> bool {
+    match edn {
+        Edn::Tagged(tag, element) => {
+            !(matches!(tag.as_str(), "inst" | "uuid") && matches!(**element, Edn::Str(_)))
+        }
+        #[cfg(feature = "sets")]
+        Edn::Set(_) => true,
+        Edn::Vector(_) | Edn::List(_) | Edn::Map(_) | Edn::OrderedMap(_) | Edn::Rational(_) => true,
+        _ => false,
+    }
+}
+
+struct Reader {
+    cache: Vec<String>,
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::Transit(message.into())
+}
+
+impl Reader {
+    // Resolves cache references and remembers cacheable strings, in the order they are read
+    fn resolve(&mut self, s: String, as_map_key: bool) -> Result<String, Error> {
+        if s.starts_with('^') && s != MAP_AS_ARRAY {
+            return cache_index(&s)
+                .and_then(|index| self.cache.get(index))
+                .cloned()
+                .ok_or_else(|| invalid(format!("Unknown cache reference {s:?}")));
+        }
+        if is_cacheable(&s, as_map_key) {
+            if self.cache.len() == CACHE_SIZE {
+                self.cache.clear();
+            }
+            self.cache.push(s.clone());
+        }
+        Ok(s)
+    }
+
+    fn read(&mut self, json: Json) -> Result<Edn, Error> {
+        match json {
+            Json::Null => Ok(Edn::Nil),
+            Json::Bool(b) => Ok(Edn::Bool(b)),
+            Json::Number(n) => Ok(number(&n)),
+            Json::String(s) => {
+                let s = self.resolve(s, false)?;
+                scalar(&s)
+            }
+            Json::Array(items) => {
+                let mut items = items.into_iter();
+                let first = match items.next() {
+                    None => return Ok(Edn::Vector(Vector::empty())),
+                    Some(Json::String(s)) => self.resolve(s, false)?,
+                    Some(first) => {
+                        let first = self.read(first)?;
+                        return self.vector(first, items);
+                    }
+                };
+                if first == MAP_AS_ARRAY {
+                    let mut map = BTreeMap::new();
+                    while let Some(key) = items.next() {
+                        let value = items
+                            .next()
+                            .ok_or_else(|| invalid("Map as array with a key without value"))?;
+                        map.insert(self.read_key(key)?, self.read(value)?);
+                    }
+                    return Ok(Edn::Map(Map::new(map)));
+                }
+                if let Some(tag) = first.strip_prefix("~#")
+                    && items.len() == 1
+                {
+                    let rep = items.next().unwrap_or(Json::Null);
+                    return self.tagged(tag, rep);
+                }
+                let first = scalar(&first)?;
+                self.vector(first, items)
+            }
+            Json::Object(entries) => {
+                let single = entries.len() == 1;
+                let mut map = BTreeMap::new();
+                for (key, value) in entries {
+                    let key = self.resolve(key, true)?;
+                    if let Some(tag) = key.strip_prefix("~#")
+                        && single
+                    {
+                        return self.tagged(tag, value);
+                    }
+                    map.insert(scalar(&key)?, self.read(value)?);
+                }
+                Ok(Edn::Map(Map::new(map)))
+            }
+        }
+    }
+
+    fn vector(&mut self, first: Edn, items: impl Iterator<Item = Json>) -> Result<Edn, Error> {
+        let mut vector = Vec::from([first]);
+        for item in items {
+            vector.push(self.read(item)?);
+        }
+        Ok(Edn::Vector(Vector::new(vector)))
+    }
+
+    fn read_key(&mut self, key: Json) -> Result<Edn, Error> {
+        match key {
+            Json::String(s) => {
+                let s = self.resolve(s, true)?;
+                scalar(&s)
+            }
+            key => self.read(key),
+        }
+    }
+
+    fn tagged(&mut self, tag: &str, rep: Json) -> Result<Edn, Error> {
+        let items = |edn: Edn| match edn {
+            Edn::Vector(items) => Ok(items.to_vec()),
+            _ => Err(invalid(format!("Expected an array in `~#{tag}`"))),
+        };
+        let rep = self.read(rep)?;
+        Ok(match tag {
+            "'" => rep,
+            "list" => Edn::List(List::new(items(rep)?)),
+            #[cfg(feature = "sets")]
+            "set" => Edn::Set(Set::new(items(rep)?.into_iter().collect::<BTreeSet<_>>())),
+            "cmap" => {
+                let items = items(rep)?;
+                if items.len() % 2 != 0 {
+                    return Err(invalid("`~#cmap` with a key without value"));
+                }
+                let mut entries = items.into_iter();
+                let mut map = BTreeMap::new();
+                while let (Some(k), Some(v)) = (entries.next(), entries.next()) {
+                    map.insert(k, v);
+                }
+                Edn::Map(Map::new(map))
+            }
+            "ratio" => match items(rep)?.as_slice() {
+                [numerator, denominator] if is_integer(numerator) && is_integer(denominator) => {
+                    Edn::Rational(format!("{}/{}", digits(numerator), digits(denominator)))
+                }
+                _ => return Err(invalid("Expected two integers in `~#ratio`")),
+            },
+            _ => Edn::Tagged(tag.to_string(), Box::new(rep)),
+        })
+    }
+}
+
+const fn is_integer(edn: &Edn) -> bool {
+    matches!(edn, Edn::Int(_) | Edn::UInt(_) | Edn::BigInt(_))
+}
+
+fn digits(edn: &Edn) -> String {
+    match edn {
+        Edn::BigInt(n) => n.clone(),
+        edn => edn.to_string(),
+    }
+}
+
+fn number(n: &str) -> Edn {
+    if n.contains(['.', 'e', 'E']) {
+        n.parse::<f64>()
+            .map_or_else(|_| Edn::Decimal(n.to_string()), |d| Edn::Double(d.into()))
+    } else {
+        integer(n)
+    }
+}
+
+fn integer(n: &str) -> Edn {
+    n.parse::<u64>()
+        .map(Edn::UInt)
+        .or_else(|_| n.parse::<i64>().map(Edn::Int))
+        .unwrap_or_else(|_| Edn::BigInt(n.trim_start_matches('+').to_string()))
+}
+
+fn scalar(s: &str) -> Result<Edn, Error> {
+    let Some(rest) = s.strip_prefix('~') else {
+        return Ok(Edn::Str(s.to_string()));
+    };
+    let mut chars = rest.chars();
+    let Some(tag) = chars.next() else {
+        return Ok(Edn::Str(s.to_string()));
+    };
+    let rep = chars.as_str();
+    let invalid_rep = || invalid(format!("Invalid transit value {s:?}"));
+    Ok(match tag {
+        '~' | '^' | '`' => Edn::Str(rest.to_string()),
+        '_' => Edn::Nil,
+        '?' => Edn::Bool(rep == "t"),
+        ':' => Edn::Key(format!(":{rep}")),
+        '$' => Edn::Symbol(rep.to_string()),
+        'c' => {
+            let mut chars = rep.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Edn::Char(c),
+                _ => return Err(invalid_rep()),
+            }
+        }
+        'i' if valid_integer(rep) => integer(rep),
+        'n' if valid_integer(rep) => Edn::BigInt(rep.to_string()),
+        'f' => Edn::Decimal(rep.to_string()),
+        'd' => Edn::Double(rep.parse::<f64>().map_err(|_| invalid_rep())?.into()),
+        'z' => Edn::Double(
+            match rep {
+                "NaN" => f64::NAN,
+                "INF" => f64::INFINITY,
+                "-INF" => f64::NEG_INFINITY,
+                _ => return Err(invalid_rep()),
+            }
+            .into(),
+        ),
+        'm' => {
+            let inst = rep
+                .parse::<i64>()
+                .ok()
+                .and_then(Inst::from_unix_timestamp_millis)
+                .ok_or_else(invalid_rep)?;
+            Edn::from(inst)
+        }
+        't' => Edn::Tagged("inst".to_string(), Box::new(Edn::Str(rep.to_string()))),
+        'u' => Edn::Tagged("uuid".to_string(), Box::new(Edn::Str(rep.to_string()))),
+        'i' | 'n' => return Err(invalid_rep()),
+        '#' => return Err(invalid(format!("Tag {s:?} outside of a tagged value"))),
+        tag => Edn::Tagged(tag.to_string(), Box::new(Edn::Str(rep.to_string()))),
+    })
+}
+
+fn valid_integer(n: &str) -> bool {
+    let digits = n.strip_prefix('-').unwrap_or(n);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
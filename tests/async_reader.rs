@@ -0,0 +1,95 @@
+#[cfg(feature = "async")]
+#[cfg(test)]
+mod tests {
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+    use std::io;
+    use std::str::FromStr;
+
+    use edn_rs::{AsyncEdnReader, Edn, EdnError, ParseErrorKind, ReaderOptions};
+    use tokio::io::{AsyncRead, AsyncWriteExt, ReadBuf};
+
+    #[tokio::test]
+    async fn reads_forms_as_they_arrive() {
+        let (client, mut server) = tokio::io::duplex(4);
+        let writer = tokio::spawn(async move {
+            for chunk in [
+                "{:op :put ",
+                ":v \"a \\\"b\\\"\"}",
+                " ; ack\n(1",
+                " 2) :d",
+                "one",
+            ] {
+                server.write_all(chunk.as_bytes()).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+
+        let mut reader = AsyncEdnReader::with_capacity(3, client);
+        assert_eq!(
+            reader.next_form().await,
+            Some(Edn::from_str("{:op :put :v \"a \\\"b\\\"\"}"))
+        );
+        assert_eq!(reader.next_form().await, Some(Edn::from_str("(1 2)")));
+        assert_eq!(
+            reader.next_form().await,
+            Some(Ok(Edn::Key(":done".to_string())))
+        );
+        assert_eq!(reader.next_form().await, None);
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reports_parse_and_io_errors() {
+        struct Failing<'a>(&'a [u8]);
+
+        impl AsyncRead for Failing<'_> {
+            fn poll_read(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                buf: &mut ReadBuf<'_>,
+            ) -> Poll<io::Result<()>> {
+                if self.0.is_empty() {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::ConnectionReset,
+                        "reset by peer",
+                    )));
+                }
+                let n = buf.remaining().min(self.0.len());
+                buf.put_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        let mut reader = AsyncEdnReader::new(Failing(b"} [1 2] "));
+        let Some(Err(EdnError::Parse(err))) = reader.next_form().await else {
+            panic!()
+        };
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedDelimiter('}'));
+        assert_eq!(reader.next_form().await, Some(Edn::from_str("[1 2]")));
+        assert_eq!(
+            reader.next_form().await,
+            Some(Err(EdnError::Io(
+                io::ErrorKind::ConnectionReset,
+                "reset by peer".to_string()
+            )))
+        );
+        assert_eq!(reader.next_form().await, None);
+    }
+
+    #[tokio::test]
+    async fn skips_forms_longer_than_max_form_len() {
+        let stream = &b"[1 2] {:a \"unterminated 0123456789"[..];
+        let mut reader =
+            AsyncEdnReader::with_capacity(3, stream).options(ReaderOptions::new().max_form_len(8));
+
+        assert_eq!(reader.next_form().await, Some(Edn::from_str("[1 2]")));
+        let Some(Err(EdnError::Parse(err))) = reader.next_form().await else {
+            panic!()
+        };
+        assert_eq!(err.kind, ParseErrorKind::FormTooLong(8));
+        assert_eq!(err.offset, 6);
+        assert_eq!(reader.next_form().await, None);
+    }
+}
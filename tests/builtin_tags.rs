@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::{Edn, EdnError, Inst, ParseErrorKind, Uuid, from_edn, from_str};
+
+    #[test]
+    fn inst_and_uuid_are_validated_while_parsing() {
+        let edn = Edn::from_str(
+            "[#inst \"2020-07-16T21:53:14.628-00:00\" #uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\"]",
+        )
+        .unwrap();
+
+        assert_eq!(
+            edn[0],
+            Edn::Tagged(
+                "inst".to_string(),
+                Box::new(Edn::Str("2020-07-16T21:53:14.628-00:00".to_string()))
+            )
+        );
+        assert_eq!(edn[0].to_inst().unwrap().nanosecond(), 628_000_000);
+        assert_eq!(
+            edn[1].to_uuid(),
+            Some(Uuid::from_u128(0xaf6d_8699_f442_4dfd_8b26_37d8_0543_186b))
+        );
+
+        let Err(EdnError::Parse(err)) = Edn::from_str("{:at\n #inst \"2020-13-01\"}") else {
+            panic!()
+        };
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidTagged {
+                tag: "inst".to_string(),
+                message: "invalid RFC 3339 timestamp \"2020-13-01\", month out of range"
+                    .to_string()
+            }
+        );
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let Err(EdnError::Parse(err)) = Edn::from_str("#uuid 42") else {
+            panic!()
+        };
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidTagged {
+                tag: "uuid".to_string(),
+                message: "expected a string, got 42".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_inst_and_uuid() {
+        let inst: Inst = from_str("#inst \"1985-04-12T23:20:50.52Z\"").unwrap();
+        let uuid: Uuid = from_str("#uuid \"00000000-0000-0000-0000-00000000002a\"").unwrap();
+
+        assert_eq!(inst.unix_timestamp(), 482_196_050);
+        assert_eq!(uuid.as_u128(), 42);
+        assert_eq!(
+            Edn::from(inst),
+            Edn::from_str("#inst \"1985-04-12T23:20:50.52Z\"").unwrap()
+        );
+        assert_eq!(
+            from_edn::<Inst>(&Edn::Str("1985".to_string())),
+            Err(EdnError::Deserialize(
+                "couldn't convert `\"1985\"` into `edn_rs::Inst`".to_string()
+            ))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_conversions() {
+        use chrono::{DateTime, FixedOffset, Utc};
+
+        let date: DateTime<FixedOffset> =
+            from_str("#inst \"2020-07-16T21:53:14.628-03:00\"").unwrap();
+        let utc: DateTime<Utc> = from_str("#inst \"2020-07-16T21:53:14.628-03:00\"").unwrap();
+
+        assert_eq!(date.to_rfc3339(), "2020-07-16T21:53:14.628-03:00");
+        assert_eq!(utc.to_rfc3339(), "2020-07-17T00:53:14.628+00:00");
+        assert_eq!(
+            Inst::try_from(date).unwrap(),
+            Inst::from_str("2020-07-16T21:53:14.628-03:00").unwrap()
+        );
+
+        let leap = Inst::from_str("2016-12-31T23:59:60.5Z").unwrap();
+        let date = DateTime::<FixedOffset>::try_from(leap).unwrap();
+        assert_eq!(Inst::try_from(date).unwrap(), leap);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_conversions() {
+        use time::OffsetDateTime;
+
+        let date: OffsetDateTime = from_str("#inst \"2020-07-16T21:53:14.628-03:00\"").unwrap();
+
+        assert_eq!(date.unix_timestamp(), 1_594_947_194);
+        assert_eq!(date.offset().whole_minutes(), -180);
+        assert_eq!(
+            Inst::try_from(date).unwrap().to_string(),
+            "2020-07-16T21:53:14.628-03:00"
+        );
+        assert!(OffsetDateTime::try_from(Inst::from_str("2016-12-31T23:59:60Z").unwrap()).is_err());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_conversions() {
+        let uuid: uuid::Uuid = from_str("#uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\"").unwrap();
+
+        assert_eq!(uuid.to_string(), "af6d8699-f442-4dfd-8b26-37d80543186b");
+        assert_eq!(
+            Uuid::from(uuid).to_string(),
+            "af6d8699-f442-4dfd-8b26-37d80543186b"
+        );
+    }
+}
@@ -0,0 +1,244 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::cst::{Document, NodeKind, Trivia};
+    use edn_rs::{Edn, EdnError, ParseErrorKind, ReaderOptions, TagRegistry};
+
+    const SOURCES: [&str; 10] = [
+        "",
+        "  ; only a comment\n",
+        "{:a 1, :b 2,,}",
+        "[1 2.5 -3 0x1F 2r101 3/4 42N 1.5M ##Inf ##-Inf]",
+        "(\\a \\newline \\u00e9 \\o101 \\( \\space) ; chars",
+        "[\"a\\\"b\" \"\\u00e9\\n\" \"ç\" :ns/kw ::auto sym ns/sym nil true false]",
+        "#:user {:id 1 :_/tag 2}\n#::{:id 3}",
+        "#inst \"2020-07-16T21:53:14.628Z\" #uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\"",
+        ";; config\n{:db {:host \"localhost\" ; primary\n      :port 5432}\n #_#_:old 1\n :replicas [#_ :a :b]}\n",
+        "#point\t[1 2] #_ #_ 1 2 3",
+    ];
+
+    #[test]
+    fn prints_back_byte_for_byte() {
+        for source in SOURCES {
+            assert_eq!(Document::from_str(source).unwrap().to_string(), source);
+        }
+    }
+
+    #[test]
+    fn lowers_like_the_parser() {
+        for source in SOURCES {
+            let doc = Document::from_str(source).unwrap();
+
+            for (node, edn) in doc.forms.iter().zip(doc.lower().unwrap()) {
+                assert_eq!(Edn::from_str(&node.to_string()).unwrap(), edn, "{source}");
+            }
+        }
+        assert_eq!(Document::from_str("").unwrap().lower().unwrap(), []);
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn lowers_sets() {
+        let source = "#{1 ; one\n 2}";
+        let doc = Document::from_str(source).unwrap();
+
+        assert_eq!(doc.to_string(), source);
+        assert_eq!(doc.lower().unwrap(), [Edn::from_str("#{1 2}").unwrap()]);
+    }
+
+    #[test]
+    fn keeps_trivia() {
+        let doc = Document::from_str("[1 #_ 2 ; two\n 3]").unwrap();
+        let NodeKind::Vector(vector) = &doc.forms[0].kind else {
+            panic!()
+        };
+
+        assert_eq!(vector.children.len(), 2);
+        assert_eq!(
+            vector.children[1]
+                .leading
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [" ", "#_ 2", " ", "; two", "\n "]
+        );
+        assert!(matches!(vector.children[1].leading[1], Trivia::Discard(_)));
+    }
+
+    #[test]
+    fn errors_like_the_parser() {
+        for source in [
+            "[1 2",
+            "{:a \"b}",
+            "(1 2]",
+            "[1 #_]",
+            "#_ ",
+            "\"\\q\"",
+            "[:a 1x]",
+            "#:a/b{:c 1}",
+            "##Nope",
+            "\\u12",
+            ":a/",
+        ] {
+            let Err(EdnError::Parse(cst)) = Document::from_str(source) else {
+                panic!("{source}")
+            };
+            let Err(EdnError::Parse(parser)) = Edn::from_str(source) else {
+                panic!("{source}")
+            };
+
+            assert_eq!(cst, parser, "{source}");
+        }
+    }
+
+    #[test]
+    fn lowers_with_options() {
+        let options = ReaderOptions::new()
+            .ordered_maps(true)
+            .tags(
+                TagRegistry::new().register("even", |edn: Edn| match edn.to_uint() {
+                    Some(n) if n % 2 == 0 => Ok(edn),
+                    _ => Err(format!("{edn} is not even")),
+                }),
+            );
+        let doc = Document::from_str("{:b #even 2 :a 1}\n; next\n[#even 3]").unwrap();
+
+        let Err(EdnError::Parse(err)) = doc.lower_with(&options) else {
+            panic!()
+        };
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidTagged {
+                tag: "even".to_string(),
+                message: "3 is not even".to_string()
+            }
+        );
+        assert_eq!((err.line, err.column), (3, 2));
+
+        let doc = Document::from_str("{:b #even 2 :a 1}").unwrap();
+        assert_eq!(
+            doc.lower_with(&options).unwrap()[0].to_string(),
+            "{:b 2, :a 1}"
+        );
+    }
+
+    const CONFIG: &str = ";; service config
+{:service/name \"billing\"
+ :db {:host \"localhost\" ; primary
+      :port 5432
+      :pool {:size 4}}
+ :features [:search
+            :audit-log] ; sorted
+ :retry   (1 2 4)
+ #_#_:legacy true
+ :debug   false}
+";
+
+    #[test]
+    fn edits_keep_the_rest_of_the_document() {
+        let mut doc = Document::from_str(CONFIG).unwrap();
+
+        doc.assoc_in([":db", ":port"], 5433).unwrap();
+        doc.assoc_in([":db", ":tls", ":enabled"], true).unwrap();
+        doc.update([":db", ":pool", ":size"], |size| {
+            Edn::UInt(size.to_uint().unwrap() * 2)
+        })
+        .unwrap();
+        doc.conj([":features"], Edn::Key(":export".to_string()))
+            .unwrap();
+        doc.conj([":retry"], 0).unwrap();
+        doc.assoc_in([":features", "0"], Edn::Key(":find".to_string()))
+            .unwrap();
+        assert!(doc.dissoc([":debug"]).unwrap());
+
+        assert_eq!(
+            doc.to_string(),
+            ";; service config
+{:service/name \"billing\"
+ :db {:host \"localhost\" ; primary
+      :port 5433
+      :pool {:size 8}
+      :tls {:enabled true}}
+ :features [:find
+            :audit-log
+            :export] ; sorted
+ :retry   (0 1 2 4)
+ #_#_:legacy true}
+"
+        );
+        assert_eq!(
+            doc.get_in([":db", ":tls"])
+                .unwrap()
+                .unwrap()
+                .to_edn()
+                .unwrap(),
+            Edn::from_str("{:enabled true}").unwrap()
+        );
+    }
+
+    #[test]
+    fn edits_namespaced_maps() {
+        let mut doc = Document::from_str("#:user{:id 1\n        :name \"a\"}").unwrap();
+
+        doc.assoc_in([":user/id"], 2).unwrap();
+        doc.assoc_in([":user/role"], Edn::Key(":admin".to_string()))
+            .unwrap();
+        doc.assoc_in([":foo"], 3).unwrap();
+        doc.assoc_in([":team/id"], 4).unwrap();
+        assert!(doc.dissoc([":user/name"]).unwrap());
+
+        assert_eq!(
+            doc.to_string(),
+            "#:user{:id 2\n        :role :admin\n        :_/foo 3\n        :team/id 4}"
+        );
+        assert_eq!(
+            doc.lower().unwrap(),
+            [Edn::from_str("{:user/id 2 :user/role :admin :foo 3 :team/id 4}").unwrap()]
+        );
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn conj_onto_sets_keeps_elements_distinct() {
+        let mut doc = Document::from_str("#{1 2}").unwrap();
+
+        doc.conj::<&str>([], 1).unwrap();
+        doc.conj::<&str>([], 3).unwrap();
+
+        assert_eq!(doc.to_string(), "#{1 2 3}");
+        assert_eq!(doc.lower().unwrap(), [Edn::from_str("#{1 2 3}").unwrap()]);
+    }
+
+    #[test]
+    fn edit_errors() {
+        let mut doc = Document::from_str("{:a [1 2] :b 3}").unwrap();
+
+        assert_eq!(
+            doc.assoc_in([":a", "5"], 1),
+            Err(EdnError::Edit(
+                "index 5 is out of bounds of 2 elements".to_string()
+            ))
+        );
+        assert_eq!(
+            doc.assoc_in([":b", ":c"], 1),
+            Err(EdnError::Edit("cannot look up :c in a scalar".to_string()))
+        );
+        assert_eq!(
+            doc.dissoc([":a", "0"]),
+            Err(EdnError::Edit("cannot dissoc from a vector".to_string()))
+        );
+        assert_eq!(
+            doc.conj([":b"], 1),
+            Err(EdnError::Edit("cannot conj onto a scalar".to_string()))
+        );
+        assert_eq!(doc.get_in([":z", ":y"]), Ok(None));
+        assert!(!doc.dissoc([":z", ":y"]).unwrap());
+        assert!(matches!(doc.get_in(["(("]), Err(EdnError::Parse(_))));
+        assert_eq!(
+            Document::default().assoc_in([":a"], 1),
+            Err(EdnError::Edit("the document has no element".to_string()))
+        );
+        assert_eq!(doc.to_string(), "{:a [1 2] :b 3}");
+    }
+}
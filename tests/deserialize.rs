@@ -6,7 +6,17 @@ mod test {
     use core::str::FromStr;
 
     use edn::Error;
-    use edn_rs::{Edn, List, Map, Vector, edn, from_edn, from_str, hmap, map};
+    use edn_rs::{
+        Edn, List, Map, ParseError, ParseErrorKind, ReaderOptions, TagRegistry, Vector, edn,
+        from_edn, from_str, hmap, map,
+    };
+
+    fn parse_error(edn: &str) -> ParseError {
+        match Edn::from_str(edn) {
+            Err(Error::Parse(err)) => err,
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
 
     #[test]
     fn unit() {
@@ -33,12 +43,10 @@ mod test {
     #[cfg(not(feature = "sets"))]
     // Special case of running into a set without the feature enabled
     fn parse_set_without_set_feature() {
-        assert_eq!(
-            Edn::from_str("#{true, \\c, 3,four, }"),
-            Err(Error::ParseEdn(
-                "Could not parse set due to feature not being enabled".to_string()
-            ))
-        )
+        let err = parse_error("#{true, \\c, 3,four, }");
+
+        assert_eq!(err.kind, ParseErrorKind::SetsDisabled);
+        assert_eq!((err.line, err.column), (1, 1));
     }
 
     #[test]
@@ -108,18 +116,64 @@ mod test {
 
     #[test]
     fn parse_str_with_invalid_escape() {
+        let err = parse_error(r#""hello\n \r \t \"world\" with escaped \\ \g characters""#);
+
+        assert_eq!(err.kind, ParseErrorKind::InvalidEscape('g'));
+        assert_eq!((err.line, err.column, err.offset), (1, 42, 41));
+    }
+
+    #[test]
+    fn parse_str_with_unicode_and_octal_escapes() {
         assert_eq!(
-            Edn::from_str(r#""hello\n \r \t \"world\" with escaped \\ \g characters""#),
-            Err(Error::ParseEdn("Invalid escape sequence \\g".to_string()))
+            Edn::from_str(r#""caf\u00e9 \u20AC \b\f \101\0\377 \ud83d\ude00""#).unwrap(),
+            Edn::Str("café € \u{8}\u{c} A\0\u{ff} 😀".to_string())
+        );
+        // At most 3 octal digits are part of the escape
+        assert_eq!(
+            Edn::from_str(r#""\1011""#).unwrap(),
+            Edn::Str("A1".to_string())
         );
     }
 
     #[test]
-    fn parse_unterminated_string() {
+    fn parse_str_with_invalid_unicode_escapes() {
+        let err = parse_error(r#""abc \u12g4""#);
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidUnicodeEscape("\\u12g4".to_string())
+        );
+        assert_eq!((err.line, err.column, err.offset), (1, 6, 5));
+
+        let err = parse_error(r#""\u12""#);
         assert_eq!(
-            Edn::from_str(r#""hello\n \r \t \"world\" with escaped \\ characters"#),
-            Err(Error::ParseEdn("Unterminated string".to_string()))
+            err.kind,
+            ParseErrorKind::InvalidUnicodeEscape("\\u12\"".to_string())
         );
+
+        let err = parse_error(r#""lone \ud83d surrogate""#);
+        assert_eq!(err.kind, ParseErrorKind::InvalidCodePoint(0xd83d));
+        assert_eq!((err.line, err.column, err.offset), (1, 7, 6));
+        assert_eq!(err.kind.to_string(), "Invalid code point U+D83D");
+
+        let err = parse_error(r#""\ud83d\u0041""#);
+        assert_eq!(err.kind, ParseErrorKind::InvalidCodePoint(0xd83d));
+
+        let err = parse_error(r#""\ude00""#);
+        assert_eq!(err.kind, ParseErrorKind::InvalidCodePoint(0xde00));
+
+        let err = parse_error(r#""\400""#);
+        assert_eq!(err.kind, ParseErrorKind::InvalidEscape('4'));
+
+        let err = parse_error(r#""abc\"#);
+        assert_eq!(err.kind, ParseErrorKind::UnterminatedString);
+    }
+
+    #[test]
+    fn parse_unterminated_string() {
+        let err = parse_error(r#""hello\n \r \t \"world\" with escaped \\ characters"#);
+
+        assert_eq!(err.kind, ParseErrorKind::UnterminatedString);
+        assert_eq!((err.line, err.column, err.offset), (1, 1, 0));
     }
 
     #[test]
@@ -146,6 +200,44 @@ mod test {
         assert_eq!(Edn::from_str("\\k").unwrap(), Edn::Char('k'));
     }
 
+    #[test]
+    fn parse_escaped_chars() {
+        assert_eq!(Edn::from_str("\\formfeed").unwrap(), Edn::Char('\u{c}'));
+        assert_eq!(Edn::from_str("\\backspace").unwrap(), Edn::Char('\u{8}'));
+        assert_eq!(Edn::from_str("\\u20AC").unwrap(), Edn::Char('€'));
+        assert_eq!(Edn::from_str("\\o101").unwrap(), Edn::Char('A'));
+        assert_eq!(Edn::from_str("\\o0").unwrap(), Edn::Char('\0'));
+        assert_eq!(Edn::from_str("\\o").unwrap(), Edn::Char('o'));
+        assert_eq!(
+            Edn::from_str("[\\u00e9 \\o377 \\u]").unwrap(),
+            Edn::Vector(Vector::new(vec![
+                Edn::Char('é'),
+                Edn::Char('\u{ff}'),
+                Edn::Char('u')
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_escaped_chars() {
+        let err = parse_error("[1 \\u12]");
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidUnicodeEscape("\\u12".to_string())
+        );
+        assert_eq!((err.line, err.column, err.offset), (1, 4, 3));
+
+        let err = parse_error("\\ud800");
+        assert_eq!(err.kind, ParseErrorKind::InvalidCodePoint(0xd800));
+        assert_eq!(err.kind.to_string(), "Invalid code point U+D800");
+
+        let err = parse_error("\\o400");
+        assert_eq!(err.kind, ParseErrorKind::InvalidChar("o400".to_string()));
+
+        let err = parse_error("\\o18");
+        assert_eq!(err.kind, ParseErrorKind::InvalidChar("o18".to_string()));
+    }
+
     #[test]
     fn parse_bool_or_nil() {
         assert_eq!(Edn::from_str("true").unwrap(), Edn::Bool(true));
@@ -339,8 +431,8 @@ mod test {
         assert_eq!(
             Edn::from_str("{:a \"2\" :b false :c nil }").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("2".to_string()),
-                ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil}
+                map! {Edn::Key(":a".to_string()) => Edn::Str("2".to_string()),
+                Edn::Key(":b".to_string()) => Edn::Bool(false), Edn::Key(":c".to_string()) => Edn::Nil}
             ))
         );
     }
@@ -352,8 +444,8 @@ mod test {
         assert_eq!(
             Edn::from_str(edn),
             Ok(Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("2".to_string()),
-                ":b".to_string() => Edn::Bool(true), ":c".to_string() => Edn::Nil}
+                map! {Edn::Key(":a".to_string()) => Edn::Str("2".to_string()),
+                Edn::Key(":b".to_string()) => Edn::Bool(true), Edn::Key(":c".to_string()) => Edn::Nil}
             )))
         );
     }
@@ -361,8 +453,8 @@ mod test {
     #[test]
     fn deser_btreemap() {
         let ns_map = Edn::Map(Map::new(map! {
-            ":a".to_string() => Edn::Vector(Vector::new(vec![Edn::Key(":val".to_string())])),
-            ":b".to_string() => Edn::Vector(Vector::new(vec![Edn::Key(":value".to_string())]))
+            Edn::Key(":a".to_string()) => Edn::Vector(Vector::new(vec![Edn::Key(":val".to_string())])),
+            Edn::Key(":b".to_string()) => Edn::Vector(Vector::new(vec![Edn::Key(":value".to_string())]))
         }));
         let expected = map! {
             ":a".to_string() => vec![":val".to_string()],
@@ -376,8 +468,8 @@ mod test {
     #[cfg(feature = "std")]
     fn deser_hashmap() {
         let ns_map = Edn::Map(Map::new(map! {
-            ":a".to_string() => Edn::Bool(true),
-            ":b".to_string() => Edn::Bool(false)
+            Edn::Key(":a".to_string()) => Edn::Bool(true),
+            Edn::Key(":b".to_string()) => Edn::Bool(false)
         }));
         let expected = hmap! {
             ":a".to_string() => true,
@@ -387,12 +479,83 @@ mod test {
         assert_eq!(map, expected);
     }
 
+    #[test]
+    fn deser_btreemap_with_edn_keys() {
+        let edn = Edn::from_str("{[1 2] :a, \"1\" :b, 1 :c}").unwrap();
+        let expected = map! {
+            Edn::Vector(Vector::new(vec![Edn::UInt(1), Edn::UInt(2)])) => ":a".to_string(),
+            Edn::Str("1".to_string()) => ":b".to_string(),
+            Edn::UInt(1) => ":c".to_string()
+        };
+        let map: BTreeMap<Edn, String> = from_edn(&edn).unwrap();
+        assert_eq!(map, expected);
+
+        let map: BTreeMap<String, String> = from_edn(&edn).unwrap();
+        assert_eq!(map["[1 2]"], ":a");
+        assert_eq!(map["1"], ":c");
+    }
+
+    #[test]
+    fn parse_map_keeps_key_types() {
+        let edn = Edn::from_str("{:a 1 \"a\" 2 a 3 [1 2] 4 \"1\" 5 1 6}").unwrap();
+
+        assert_eq!(
+            edn,
+            Edn::Map(Map::new(map! {
+                Edn::Key(":a".to_string()) => Edn::UInt(1),
+                Edn::Str("a".to_string()) => Edn::UInt(2),
+                Edn::Symbol("a".to_string()) => Edn::UInt(3),
+                Edn::Vector(Vector::new(vec![Edn::UInt(1), Edn::UInt(2)])) => Edn::UInt(4),
+                Edn::Str("1".to_string()) => Edn::UInt(5),
+                Edn::UInt(1) => Edn::UInt(6)
+            }))
+        );
+        assert_eq!(edn[":a"], Edn::UInt(1));
+        assert_eq!(edn["\"a\""], Edn::UInt(2));
+        assert_eq!(edn["a"], Edn::UInt(3));
+        assert_eq!(edn["[1 2]"], Edn::UInt(4));
+        assert_eq!(edn["\"1\""], Edn::UInt(5));
+        assert_eq!(edn["1"], Edn::UInt(6));
+        assert_eq!(edn[1], Edn::UInt(6));
+        assert_eq!(edn[Edn::Int(1)], Edn::UInt(6));
+        assert_eq!(edn[":b"], Edn::Nil);
+    }
+
+    #[test]
+    fn str_index_of_other_than_one_element() {
+        let edn = Edn::from_str("{hello 1 :a 2 \"hello world\" 3}").unwrap();
+
+        assert_eq!(edn["hello"], Edn::UInt(1));
+        assert_eq!(edn["hello world"], Edn::Nil);
+        assert_eq!(edn[":a :zzz"], Edn::Nil);
+        assert_eq!(edn[":a)"], Edn::Nil);
+        assert_eq!(edn["(("], Edn::Nil);
+        assert_eq!(edn[""], Edn::Nil);
+        assert_eq!(edn[Edn::Str("hello world".to_string())], Edn::UInt(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot access key \":b :c\", it is not a single EDN element")]
+    fn str_index_assign_of_other_than_one_element() {
+        let mut edn = Edn::from_str("{:a 1}").unwrap();
+
+        edn[":b :c"] = Edn::UInt(5);
+    }
+
+    #[test]
+    fn map_round_trips_composite_keys() {
+        let source = "{[1 2] :a, \"1\" :b, (:x nil) :c}";
+        let edn = Edn::from_str(source).unwrap();
+
+        assert_eq!(Edn::from_str(&edn.to_string()).unwrap(), edn);
+    }
+
     #[test]
     fn parse_inst() {
         assert_eq!(
             Edn::from_str("{:date  #inst \"2020-07-16T21:53:14.628-00:00\"}").unwrap(),
             Edn::Map(Map::new(map! {
-                ":date".to_string() =>
+                Edn::Key(":date".to_string()) =>
                     Edn::Tagged("inst".to_string(),
                                 Box::new(Edn::Str("2020-07-16T21:53:14.628-00:00".to_string())))
             }))
@@ -428,12 +591,10 @@ mod test {
 
     #[test]
     fn parse_discard_invalid() {
-        assert_eq!(
-            Edn::from_str("#_{ 234"),
-            Err(Error::ParseEdn(
-                "None could not be parsed at char count 3".to_string()
-            ))
-        );
+        let err = parse_error("#_{ 234");
+
+        assert_eq!(err.kind, ParseErrorKind::Unclosed("{"));
+        assert_eq!((err.line, err.column), (1, 3));
     }
 
     #[test]
@@ -459,19 +620,17 @@ mod test {
         assert_eq!(
             Edn::from_str("#_ ,, #_{discard again} #_ {:and :again} :okay {:a map}").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Symbol("map".to_string())}
+                map! {Edn::Key(":a".to_string()) => Edn::Symbol("map".to_string())}
             ))
         );
     }
 
     #[test]
     fn parse_discard_no_follow_element() {
-        assert_eq!(
-            Edn::from_str("#_ ,, "),
-            Err(Error::ParseEdn(
-                "Discard sequence must have a following element at char count 2".to_string()
-            ))
-        );
+        let err = parse_error("#_ ,, ");
+
+        assert_eq!(err.kind, ParseErrorKind::DiscardWithoutElement);
+        assert_eq!((err.line, err.column), (1, 1));
     }
 
     #[test]
@@ -484,12 +643,10 @@ mod test {
 
     #[test]
     fn parse_discard_end_of_seq_no_follow() {
-        assert_eq!(
-            Edn::from_str("[:foo #_ ]"),
-            Err(Error::ParseEdn(
-                "Discard sequence must have a following element at char count 8".to_string()
-            ))
-        );
+        let err = parse_error("[:foo #_ ]");
+
+        assert_eq!(err.kind, ParseErrorKind::DiscardWithoutElement);
+        assert_eq!((err.line, err.column), (1, 7));
     }
 
     #[test]
@@ -510,8 +667,8 @@ mod test {
         assert_eq!(
             Edn::from_str("{ :a :something, :b false, :c nil, }").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Key(":something".to_string()),
-                ":b".to_string() => Edn::Bool(false), ":c".to_string() => Edn::Nil}
+                map! {Edn::Key(":a".to_string()) => Edn::Key(":something".to_string()),
+                Edn::Key(":b".to_string()) => Edn::Bool(false), Edn::Key(":c".to_string()) => Edn::Nil}
             ))
         );
     }
@@ -522,7 +679,7 @@ mod test {
             Edn::from_str("{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" }")
                 .unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {Edn::Key(":a".to_string()) => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
             ))
         );
     }
@@ -553,7 +710,7 @@ mod test {
         assert_eq!(
             Edn::from_str(" ;; this is a map\n{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" }").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {Edn::Key(":a".to_string()) => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
             ))
         );
     }
@@ -563,7 +720,7 @@ mod test {
         assert_eq!(
             Edn::from_str("{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" ; escaped chars\n }").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {Edn::Key(":a".to_string()) => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
             ))
         );
     }
@@ -573,7 +730,7 @@ mod test {
         assert_eq!(
             Edn::from_str(";; this is a map\n{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" }\n ;; end of file\n").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {Edn::Key(":a".to_string()) => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
             ))
         );
     }
@@ -583,7 +740,7 @@ mod test {
         assert_eq!(
             Edn::from_str(";; this is a map\n{ :a \"hello\n \r \t \\\"world\\\" with escaped \\\\ characters\" }\n ;; end of file").unwrap(),
             Edn::Map(Map::new(
-                map! {":a".to_string() => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
+                map! {Edn::Key(":a".to_string()) => Edn::Str("hello\n \r \t \"world\" with escaped \\ characters".to_string())}
             ))
         );
     }
@@ -623,8 +780,8 @@ mod test {
         assert_eq!(
             Edn::from_str("{ :model #domain/model [1 2 3] :int 2 }").unwrap(),
             Edn::Map(Map::new(map! {
-                ":int".to_string() => Edn::UInt(2),
-                ":model".to_string() => Edn::Tagged(
+                Edn::Key(":int".to_string()) => Edn::UInt(2),
+                Edn::Key(":model".to_string()) => Edn::Tagged(
                 String::from("domain/model"),
                 Box::new(Edn::Vector(Vector::new(vec![
                     Edn::UInt(1),
@@ -668,9 +825,9 @@ mod test {
             Edn::Tagged(
                 String::from("domain/model"),
                 Box::new(Edn::Map(Map::new(map! {
-                    "1".to_string() =>
+                    Edn::UInt(1) =>
                     Edn::UInt(2),
-                    "3".to_string() =>
+                    Edn::UInt(3) =>
                     Edn::UInt(4)
                 })))
             )
@@ -717,15 +874,15 @@ mod test {
             Edn::Tagged(
                 "domain/model".to_string(),
                 Box::new(Edn::Map(Map::new(map! {
-                    "#keyword :4".to_string() => Edn::Map(
+                    Edn::Tagged("keyword".to_string(), Box::new(Edn::Key(":4".to_string()))) => Edn::Map(
                         Map::new(map!
                             {
-                                ":cool-tagged".to_string() => Edn::Tagged(
+                                Edn::Key(":cool-tagged".to_string()) => Edn::Tagged(
                                     "yay".to_string(),
                                     Box::new(Edn::Map(
                                         Map::new(
                                             map!{
-                                                ":stuff".to_string() => Edn::Str(
+                                                Edn::Key(":stuff".to_string()) => Edn::Str(
                                                     "hehe".to_string(),
                                                 )
                                             },
@@ -735,10 +892,10 @@ mod test {
                             },
                         ),
                     ),
-                    "1".to_string() => Edn::Str(
+                    Edn::UInt(1) => Edn::Str(
                         "hello".to_string(),
                     ),
-                    "3".to_string() => Edn::Vector(
+                    Edn::UInt(3) => Edn::Vector(
                         Vector::new(
                             vec![
                                 Edn::Vector(
@@ -780,12 +937,12 @@ mod test {
                             ],
                         ),
                     ),
-                    "5".to_string() => Edn::Tagged(
+                    Edn::UInt(5) => Edn::Tagged(
                         "wow".to_string(),
                         Box::new(Edn::Map(
                             Map::new(map!
                                 {
-                                    ":a".to_string() => Edn::Key(
+                                    Edn::Key(":a".to_string()) => Edn::Key(
                                         ":b".to_string(),
                                     )
                                 },
@@ -857,31 +1014,50 @@ mod test {
     #[test]
     fn parse_invalid_ints() {
         assert_eq!(
-            Edn::from_str("42invalid123"),
-            Err(Error::ParseEdn(
-                "42invalid123 could not be parsed with radix 10".to_string()
-            ))
+            parse_error("42invalid123").kind,
+            ParseErrorKind::InvalidNumber {
+                number: "42invalid123".to_string(),
+                radix: 10
+            }
         );
 
         assert_eq!(
-            Edn::from_str("0xxyz123"),
-            Err(Error::ParseEdn(
-                "xyz123 could not be parsed with radix 16".to_string()
-            ))
+            parse_error("0xxyz123").kind,
+            ParseErrorKind::InvalidNumber {
+                number: "xyz123".to_string(),
+                radix: 16
+            }
         );
 
         assert_eq!(
-            Edn::from_str("42rabcxzy"),
-            Err(Error::ParseEdn("Radix of 42 is out of bounds".to_string()))
+            parse_error("[1 42rabcxzy]").kind,
+            ParseErrorKind::RadixOutOfBounds(42)
         );
+        assert_eq!(parse_error("[1 42rabcxzy]").column, 4);
 
         assert_eq!(
-            Edn::from_str("42crazyrabcxzy"),
-            Err(Error::ParseEdn(
-                "invalid digit found in string while trying to parse radix from 42crazyrabcxzy"
-                    .to_string()
-            ))
+            parse_error("42crazyrabcxzy").kind,
+            ParseErrorKind::InvalidRadix("42crazyrabcxzy".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_symbolic_values() {
+        assert_eq!(
+            Edn::from_str("[##Inf ##-Inf]").unwrap(),
+            Edn::Vector(Vector::new(vec![
+                Edn::Double(f64::INFINITY.into()),
+                Edn::Double(f64::NEG_INFINITY.into())
+            ]))
+        );
+        assert!(Edn::from_str("##NaN").unwrap().to_float().unwrap().is_nan());
+
+        let err = parse_error("[1 ##Infinity]");
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidSymbolicValue("Infinity".to_string())
         );
+        assert_eq!(err.column, 4);
     }
 
     #[test]
@@ -890,6 +1066,135 @@ mod test {
         assert_eq!(Edn::from_str("+0x2a").unwrap(), Edn::UInt(42));
     }
 
+    #[test]
+    fn parse_big_ints() {
+        assert_eq!(Edn::from_str("42N").unwrap(), Edn::BigInt("42".to_string()));
+        assert_eq!(
+            Edn::from_str("-0042N").unwrap(),
+            Edn::BigInt("-42".to_string())
+        );
+        assert_eq!(Edn::from_str("-0N").unwrap(), Edn::BigInt("0".to_string()));
+        assert_eq!(
+            Edn::from_str("0xFFN").unwrap(),
+            Edn::BigInt("255".to_string())
+        );
+        assert_eq!(
+            Edn::from_str("36rN").unwrap(),
+            Edn::UInt(23),
+            "N is a digit in radix 36"
+        );
+        assert_eq!(
+            Edn::from_str("[18446744073709551616 -9223372036854775809]").unwrap(),
+            Edn::Vector(Vector::new(vec![
+                Edn::BigInt("18446744073709551616".to_string()),
+                Edn::BigInt("-9223372036854775809".to_string()),
+            ]))
+        );
+        assert_eq!(
+            Edn::from_str("0x1000000000000000000000000").unwrap(),
+            Edn::BigInt("79228162514264337593543950336".to_string())
+        );
+        assert_eq!(
+            Edn::from_str("123456789012345678901234567890N")
+                .unwrap()
+                .to_string(),
+            "123456789012345678901234567890N"
+        );
+    }
+
+    #[test]
+    fn parse_decimals() {
+        assert_eq!(
+            Edn::from_str("3.14M").unwrap(),
+            Edn::Decimal("3.14".to_string())
+        );
+        assert_eq!(
+            Edn::from_str("[+1M -0.10M 1.M 1.5e-3M]").unwrap(),
+            Edn::Vector(Vector::new(vec![
+                Edn::Decimal("1".to_string()),
+                Edn::Decimal("-0.10".to_string()),
+                Edn::Decimal("1.".to_string()),
+                Edn::Decimal("1.5e-3".to_string()),
+            ]))
+        );
+
+        let edn = Edn::from_str("{:amount 12345678901234567890.123456789M}").unwrap();
+        assert_eq!(edn.to_string(), "{:amount 12345678901234567890.123456789M}");
+        assert_eq!(
+            edn[":amount"].to_float(),
+            Some(12_345_678_901_234_567_890.0)
+        );
+    }
+
+    #[test]
+    fn parse_invalid_big_numbers() {
+        for (number, radix) in [("1.5N", 10), ("1/2N", 10), ("0xGN", 16)] {
+            assert_eq!(
+                parse_error(number).kind,
+                ParseErrorKind::InvalidNumber {
+                    number: number.trim_start_matches("0x").to_string(),
+                    radix
+                }
+            );
+        }
+        for number in ["1/2M", "1e5eM", "1..5M"] {
+            assert_eq!(
+                parse_error(number).kind,
+                ParseErrorKind::InvalidNumber {
+                    number: number.to_string(),
+                    radix: 10
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn deser_big_numbers() {
+        let amounts: Vec<String> = from_str("[1.10M 123456789012345678901234567890N 7]").unwrap();
+
+        assert_eq!(
+            amounts,
+            vec![
+                "1.10".to_string(),
+                "123456789012345678901234567890".to_string(),
+                "7".to_string()
+            ]
+        );
+        assert_eq!(from_str::<i64>("-42N"), Ok(-42));
+        assert_eq!(from_str::<u64>("42N"), Ok(42));
+        assert!(from_str::<u64>("18446744073709551616").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn deser_num_bigint() {
+        use num_bigint::BigInt;
+
+        let n: BigInt = from_str("-123456789012345678901234567890N").unwrap();
+
+        assert_eq!(n.to_string(), "-123456789012345678901234567890");
+        assert_eq!(from_str::<BigInt>("42"), Ok(BigInt::from(42)));
+        assert_eq!(Edn::from(n).to_string(), "-123456789012345678901234567890N");
+        assert!(from_str::<BigInt>("1.5").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn deser_rust_decimal() {
+        use rust_decimal::Decimal;
+
+        let d: Decimal = from_str("1234567890.1234567890M").unwrap();
+
+        assert_eq!(d.to_string(), "1234567890.1234567890");
+        assert_eq!(
+            from_str::<Decimal>("1.5e-3M").unwrap().to_string(),
+            "0.0015"
+        );
+        assert_eq!(from_str::<Decimal>("0.1").unwrap().to_string(), "0.1");
+        assert_eq!(Edn::from(d).to_string(), "1234567890.1234567890M");
+        assert!(from_str::<Decimal>("123456789012345678901234567890123M").is_err());
+    }
+
     #[test]
     fn lisp_quoted() {
         assert_eq!(
@@ -942,15 +1247,143 @@ mod test {
     }
 
     #[test]
-    fn weird_input() {
-        let edn = "{:a]";
+    fn parse_invalid_symbols_and_keywords() {
+        let err = parse_error("[a a/-1b]");
+        assert_eq!(err.kind, ParseErrorKind::InvalidSymbol("a/-1b".to_string()));
+        assert_eq!((err.line, err.column), (1, 4));
+
+        let err = parse_error("(a/b/c)");
+        assert_eq!(err.kind, ParseErrorKind::InvalidSymbol("a/b/c".to_string()));
+
+        let err = parse_error(".5");
+        assert_eq!(err.kind, ParseErrorKind::InvalidSymbol(".5".to_string()));
+
+        let err = parse_error("{:a 1 :b/ 2}");
+        assert_eq!(err.kind, ParseErrorKind::InvalidKeyword(":b/".to_string()));
+        assert_eq!((err.line, err.column), (1, 7));
+
+        let err = parse_error(":");
+        assert_eq!(err.kind, ParseErrorKind::InvalidKeyword(":".to_string()));
+    }
+
+    #[test]
+    fn deser_keywords_and_symbols() {
+        use edn_rs::{Keyword, Symbol};
+
+        let edn = Edn::from_str("[:user/id :name ::local clojure.core/inc /]").unwrap();
+
+        let keyword = from_edn::<Keyword>(&edn[0]).unwrap();
+        assert_eq!((keyword.namespace(), keyword.name()), (Some("user"), "id"));
+        assert_eq!(keyword, Keyword::new(Some("user"), "id").unwrap());
+
+        let keyword = from_edn::<Keyword>(&edn[1]).unwrap();
+        assert_eq!((keyword.namespace(), keyword.name()), (None, "name"));
+
+        let keyword = edn[2].to_keyword().unwrap();
+        assert!(keyword.is_auto_resolved());
+        assert_eq!(keyword.to_string(), "::local");
 
+        let symbol = from_edn::<Symbol>(&edn[3]).unwrap();
         assert_eq!(
-            Edn::from_str(edn),
-            Err(Error::ParseEdn(
-                "Could not identify symbol index".to_string()
-            ))
+            (symbol.namespace(), symbol.name()),
+            (Some("clojure.core"), "inc")
         );
+        assert_eq!(Edn::from(symbol), edn[3]);
+
+        let symbol = from_edn::<Symbol>(&edn[4]).unwrap();
+        assert_eq!((symbol.namespace(), symbol.name()), (None, "/"));
+
+        assert!(from_edn::<Keyword>(&edn[3]).is_err());
+        assert!(from_edn::<Symbol>(&edn[0]).is_err());
+        assert!(Keyword::new(Some("user"), "").is_err());
+        assert!(Keyword::new(Some("1user"), "id").is_err());
+        assert!(Symbol::new(None, "+1").is_err());
+        assert_eq!(
+            Edn::from(Keyword::new(Some("user"), "id").unwrap()),
+            Edn::Key(":user/id".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_namespaced_maps() {
+        assert_eq!(
+            Edn::from_str("#:user{:id 1 :name \"a\" :role/id 2 :_/tag 3 ::local 4 \"s\" 5}")
+                .unwrap(),
+            Edn::Map(Map::new(map! {
+                Edn::Key(":user/id".to_string()) => Edn::UInt(1),
+                Edn::Key(":user/name".to_string()) => Edn::Str("a".to_string()),
+                Edn::Key(":role/id".to_string()) => Edn::UInt(2),
+                Edn::Key(":tag".to_string()) => Edn::UInt(3),
+                Edn::Key("::local".to_string()) => Edn::UInt(4),
+                Edn::Str("s".to_string()) => Edn::UInt(5)
+            }))
+        );
+        assert_eq!(
+            Edn::from_str("[#:user {a 1 _/b 2} #:user,{}]").unwrap(),
+            Edn::Vector(Vector::new(vec![
+                Edn::Map(Map::new(BTreeMap::from([
+                    (Edn::Symbol("user/a".to_string()), Edn::UInt(1)),
+                    (Edn::Symbol("b".to_string()), Edn::UInt(2)),
+                ]))),
+                Edn::Map(Map::new(BTreeMap::new())),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_auto_resolved_namespaced_maps() {
+        let edn = Edn::from_str("[#::{:id 1 x 2} #::alias{:id 3}]").unwrap();
+
+        assert_eq!(
+            edn,
+            Edn::Vector(Vector::new(vec![
+                Edn::Map(Map::new(BTreeMap::from([
+                    (Edn::Key("::id".to_string()), Edn::UInt(1)),
+                    (Edn::Symbol("x".to_string()), Edn::UInt(2)),
+                ]))),
+                Edn::Map(Map::new(BTreeMap::from([(
+                    Edn::Key("::alias/id".to_string()),
+                    Edn::UInt(3)
+                )]))),
+            ]))
+        );
+        let keyword = edn[1].map_iter().unwrap().next().unwrap().0.to_keyword();
+        assert_eq!(keyword.unwrap().namespace(), Some("alias"));
+    }
+
+    #[test]
+    fn parse_invalid_namespaced_maps() {
+        let err = parse_error("[#:user [1 2]]");
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidNamespacedMap("#:user".to_string())
+        );
+        assert_eq!((err.line, err.column), (1, 2));
+
+        let err = parse_error("#:{:a 1}");
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidNamespacedMap("#:".to_string())
+        );
+
+        let err = parse_error("#:a/b{:a 1}");
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidNamespacedMap("#:a/b".to_string())
+        );
+
+        let err = parse_error("#:user{:a 1");
+        assert_eq!(err.kind, ParseErrorKind::Unclosed("{"));
+    }
+
+    #[test]
+    fn weird_input() {
+        let edn = "{:a]";
+
+        let err = parse_error(edn);
+
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedDelimiter(']'));
+        assert_eq!((err.line, err.column), (1, 4));
     }
 
     #[test]
@@ -994,4 +1427,165 @@ mod test {
             ]))
         );
     }
+
+    #[test]
+    fn parse_error_position_multiline() {
+        let err = parse_error("{:a 1\n :b [1 2\n :c 3}");
+
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedDelimiter('}'));
+        assert_eq!((err.line, err.column, err.offset), (3, 6, 20));
+
+        let err = parse_error("{:a 1\n :b [1 2]\n :c 3");
+
+        assert_eq!(err.kind, ParseErrorKind::Unclosed("{"));
+        assert_eq!((err.line, err.column, err.offset), (1, 1, 0));
+
+        let err = parse_error("{:a 1\n :b \"two\n :c 3}");
+
+        assert_eq!(err.kind, ParseErrorKind::UnterminatedString);
+        assert_eq!((err.line, err.column, err.offset), (2, 5, 10));
+        assert_eq!(err.snippet, " :b \"two");
+    }
+
+    #[test]
+    fn parse_error_display() {
+        let err = Edn::from_str("[1 2\n  3 4)").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Unexpected delimiter `)` at line 2, column 6\n  |\n2 |   3 4)\n  |      ^"
+        );
+    }
+
+    #[test]
+    fn parse_single_char_symbols() {
+        assert_eq!(Edn::from_str("a").unwrap(), Edn::Symbol("a".to_string()));
+        assert_eq!(Edn::from_str("t").unwrap(), Edn::Symbol("t".to_string()));
+    }
+
+    #[test]
+    fn parse_with_tag_handlers() {
+        let options = ReaderOptions::new().tags(
+            TagRegistry::new()
+                .register("myapp/money", |edn: Edn| match &edn[0] {
+                    Edn::UInt(cents) => Ok(Edn::Rational(format!("{cents}/100"))),
+                    _ => Err(format!("invalid amount {edn}")),
+                })
+                .register("upper", |edn: Edn| {
+                    Ok::<_, &str>(Edn::Str(edn.to_string().to_uppercase()))
+                }),
+        );
+
+        assert_eq!(
+            options
+                .parse("{:total #myapp/money [1250 :eur] :note #upper abc :at #inst \"2020\"}")
+                .unwrap(),
+            Edn::Map(Map::new(map! {
+                Edn::Key(":total".to_string()) => Edn::Rational("1250/100".to_string()),
+                Edn::Key(":note".to_string()) => Edn::Str("ABC".to_string()),
+                Edn::Key(":at".to_string()) => Edn::Tagged(
+                    "inst".to_string(),
+                    Box::new(Edn::Str("2020".to_string()))
+                )
+            }))
+        );
+        assert_eq!(
+            options.parse("#upper #myapp/money [7]").unwrap(),
+            Edn::Str("7/100".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_tag_handler_errors() {
+        let options = ReaderOptions::new().tags(
+            TagRegistry::new()
+                .register("even", |edn: Edn| match edn.to_uint() {
+                    Some(n) if n % 2 == 0 => Ok(edn),
+                    _ => Err(format!("{edn} is not even")),
+                })
+                .default_handler(|_: &str, edn: Edn| Ok::<_, String>(edn)),
+        );
+
+        let Err(Error::Parse(err)) = options.parse("[#even 2\n #even 3]") else {
+            panic!()
+        };
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::InvalidTagged {
+                tag: "even".to_string(),
+                message: "3 is not even".to_string()
+            }
+        );
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(
+            err.to_string(),
+            "Invalid #even element: 3 is not even at line 2, column 2\n  |\n2 |  #even 3]\n  |  ^"
+        );
+        assert_eq!(
+            options.parse("#unknown {:a 1}").unwrap(),
+            Edn::from_str("{:a 1}").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_ordered_maps() {
+        let options = ReaderOptions::new().ordered_maps(true);
+        let edn = options
+            .parse("{:name \"app\" :port 8080 :db {:user \"z\" :host \"h\"} :debug false}")
+            .unwrap();
+
+        assert_eq!(
+            edn.to_string(),
+            "{:name \"app\", :port 8080, :db {:user \"z\", :host \"h\"}, :debug false}"
+        );
+        assert_eq!(
+            edn.ordered_map_iter()
+                .unwrap()
+                .map(|(k, _)| k.to_string())
+                .collect::<Vec<_>>(),
+            [":name", ":port", ":db", ":debug"]
+        );
+        assert_eq!(edn[":db"][":host"], Edn::Str("h".to_string()));
+
+        let mut edn = edn;
+        edn[":port"] = Edn::UInt(9090);
+        edn[":tls"] = Edn::Bool(true);
+        assert_eq!(
+            edn.to_string(),
+            "{:name \"app\", :port 9090, :db {:user \"z\", :host \"h\"}, :debug false, :tls true}"
+        );
+        assert_eq!(
+            options
+                .parse("#:user{:name \"a\" :id 1}")
+                .unwrap()
+                .to_string(),
+            "{:user/name \"a\", :user/id 1}"
+        );
+        assert_eq!(
+            options.parse("{:b 1 :a 2 :b 3}").unwrap().to_string(),
+            "{:b 3, :a 2}"
+        );
+        assert_ne!(
+            options.parse("{:a 1}").unwrap(),
+            Edn::from_str("{:a 1}").unwrap()
+        );
+        assert_eq!(
+            options.parse("{:a 1 :b 2}").unwrap(),
+            options.parse("{:b 2 :a 1}").unwrap()
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn ordered_map_to_json() {
+        let edn = ReaderOptions::new()
+            .ordered_maps(true)
+            .parse("{:zeta 1 :alpha [{:b 2 :a 3}]}")
+            .unwrap();
+
+        assert_eq!(
+            edn.to_json(),
+            "{\"zeta\": 1, \"alpha\": [{\"b\": 2, \"a\": 3}]}"
+        );
+    }
 }
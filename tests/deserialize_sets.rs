@@ -7,7 +7,7 @@ mod test {
     use core::str::FromStr;
 
     use edn::{Error, List, Vector};
-    use edn_rs::{Edn, Map, Set, edn, from_edn, from_str, hset, map, set};
+    use edn_rs::{Edn, Map, ParseErrorKind, Set, edn, from_edn, from_str, hset, map, set};
 
     #[test]
     fn parse_set_with_commas() {
@@ -116,11 +116,11 @@ mod test {
         assert_eq!(
             Edn::from_str(edn),
             Ok(Edn::Map(Map::new(map! {
-            ":a".to_string() =>Edn::Str("2".to_string()),
-            ":b".to_string() => Edn::Vector(Vector::new(vec![Edn::Bool(true), Edn::Bool(false)])),
-            ":c".to_string() => Edn::Set(Set::new(
+            Edn::Key(":a".to_string()) =>Edn::Str("2".to_string()),
+            Edn::Key(":b".to_string()) => Edn::Vector(Vector::new(vec![Edn::Bool(true), Edn::Bool(false)])),
+            Edn::Key(":c".to_string()) => Edn::Set(Set::new(
                 set!{
-                    Edn::Map(Map::new(map!{":a".to_string() => Edn::Key(":b".to_string())})),
+                    Edn::Map(Map::new(map!{Edn::Key(":a".to_string()) => Edn::Key(":b".to_string())})),
                     Edn::Key(":A".to_string()),
                     Edn::Nil}))})))
         );
@@ -137,9 +137,9 @@ mod test {
                 Edn::Key(":a".to_string()),
                 Edn::Key(":b".to_string()),
                 Edn::Map(Map::new(map! {
-                    ":c".to_string() => Edn::Key(":d".to_string()),
-                    ":date".to_string() => Edn::Tagged("inst".to_string(), Box::new(Edn::Str("2020-07-16T21:53:14.628-00:00".to_string()))),
-                    "::c".to_string() => Edn::Key("::d".to_string())
+                    Edn::Key(":c".to_string()) => Edn::Key(":d".to_string()),
+                    Edn::Key(":date".to_string()) => Edn::Tagged("inst".to_string(), Box::new(Edn::Str("2020-07-16T21:53:14.628-00:00".to_string()))),
+                    Edn::Key("::c".to_string()) => Edn::Key("::d".to_string())
                 })),
                 Edn::Nil
             }))
@@ -148,14 +148,14 @@ mod test {
 
     #[test]
     fn parse_discard_space_invalid() {
-        assert_eq!(
-            Edn::from_str(
-                "#_ ,, #{hello, this will be discarded} #_{so will this} #{this is invalid"
-            ),
-            Err(Error::ParseEdn(
-                "None could not be parsed at char count 58".to_string()
-            ))
-        );
+        let Err(Error::Parse(err)) = Edn::from_str(
+            "#_ ,, #{hello, this will be discarded} #_{so will this} #{this is invalid",
+        ) else {
+            panic!()
+        };
+
+        assert_eq!(err.kind, ParseErrorKind::Unclosed("#{"));
+        assert_eq!((err.line, err.column), (1, 57));
     }
 
     #[test]
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::diff::Change;
+    use edn_rs::{Diff, Edn, EdnError, ReaderOptions, Serialize, diff, patch};
+
+    const PAIRS: [(&str, &str); 7] = [
+        ("{:a 1}", "{:a 1}"),
+        ("nil", "{:a 1}"),
+        (
+            "{:service \"billing\" :db {:host \"localhost\" :port 5432} :debug true}",
+            "{:service \"billing\" :db {:host \"db-1\" :port 5432 :tls true}}",
+        ),
+        ("[1 [2 3] 4 5]", "[1 [2 3 6]]"),
+        ("(:a {:b 1})", "(:z {:b 2} nil)"),
+        ("{1 :one 2 :two}", "{2 :deux 3 :trois}"),
+        ("{:at #inst \"2020-07-16T21:53:14.628Z\"}", "{:at 1}"),
+    ];
+
+    #[test]
+    fn patch_applies_the_diff() {
+        for (old, new) in PAIRS {
+            let (old, new) = (Edn::from_str(old).unwrap(), Edn::from_str(new).unwrap());
+            let changes = diff(&old, &new);
+
+            let mut patched = old.clone();
+            patch(&mut patched, &changes).unwrap();
+            assert_eq!(patched, new, "{old} -> {new}");
+            assert_eq!(
+                edn_rs::from_str::<Diff>(&changes.serialize()).unwrap(),
+                changes
+            );
+        }
+    }
+
+    #[test]
+    fn diffs_nested_paths() {
+        let old = Edn::from_str(PAIRS[2].0).unwrap();
+        let new = Edn::from_str(PAIRS[2].1).unwrap();
+
+        assert_eq!(
+            diff(&old, &new).0,
+            [
+                Change::Replace {
+                    path: vec![Edn::Key(":db".to_string()), Edn::Key(":host".to_string())],
+                    old: Edn::Str("localhost".to_string()),
+                    new: Edn::Str("db-1".to_string()),
+                },
+                Change::Add {
+                    path: vec![Edn::Key(":db".to_string()), Edn::Key(":tls".to_string())],
+                    value: Edn::Bool(true),
+                },
+                Change::Remove {
+                    path: vec![Edn::Key(":debug".to_string())],
+                    value: Edn::Bool(true),
+                },
+            ]
+        );
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn diffs_sets_by_element() {
+        let old = Edn::from_str("{:roles #{:dev :ops}}").unwrap();
+        let new = Edn::from_str("{:roles #{:dev :admin}}").unwrap();
+        let changes = diff(&old, &new);
+
+        assert_eq!(
+            changes.serialize(),
+            "[{:op :remove, :path [:roles :ops], :value :ops} \
+              {:op :add, :path [:roles :admin], :value :admin}]"
+        );
+
+        let mut patched = old;
+        patch(&mut patched, &changes).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn patches_ordered_maps_in_place() {
+        let options = ReaderOptions::new().ordered_maps(true);
+        let old = options.parse("{:z 1 :y 2 :x 3}").unwrap();
+        let new = options.parse("{:z 1 :x 4 :w 5}").unwrap();
+
+        let mut patched = old.clone();
+        patch(&mut patched, &diff(&old, &new)).unwrap();
+        assert_eq!(patched.to_string(), "{:z 1, :x 4, :w 5}");
+    }
+
+    #[test]
+    fn replaces_maps_of_another_variant() {
+        let old = Edn::from_str("{:a 1}").unwrap();
+        let new = ReaderOptions::new()
+            .ordered_maps(true)
+            .parse("{:a 1}")
+            .unwrap();
+        let changes = diff(&old, &new);
+
+        assert_eq!(
+            changes.0,
+            [Change::Replace {
+                path: vec![],
+                old: old.clone(),
+                new: new.clone(),
+            }]
+        );
+
+        let mut patched = old;
+        patch(&mut patched, &changes).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn patch_errors() {
+        let base = Edn::from_str("{:a {:b 1} :c [1]}").unwrap();
+        let mut edn = base.clone();
+
+        let stale = diff(
+            &Edn::from_str("{:a {:b 2}}").unwrap(),
+            &Edn::from_str("{:a {:b 3}}").unwrap(),
+        );
+        assert_eq!(
+            patch(&mut edn, &stale),
+            Err(EdnError::Edit("[:a :b] is 1, not 2".to_string()))
+        );
+
+        let add_twice = diff(
+            &Edn::from_str("{:a {}}").unwrap(),
+            &Edn::from_str("{:a {:b 1}}").unwrap(),
+        );
+        assert_eq!(
+            patch(&mut edn, &add_twice),
+            Err(EdnError::Edit("[:a :b] already exists".to_string()))
+        );
+
+        let removed = diff(
+            &Edn::from_str("{:x {:y 1}}").unwrap(),
+            &Edn::from_str("{:x {}}").unwrap(),
+        );
+        assert_eq!(
+            patch(&mut edn, &removed),
+            Err(EdnError::Edit("no element at [:x]".to_string()))
+        );
+        assert_eq!(edn, base);
+
+        assert!(matches!(
+            edn_rs::from_str::<Diff>("[{:op :move :path [:a]}]"),
+            Err(EdnError::Deserialize(_))
+        ));
+    }
+}
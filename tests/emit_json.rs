@@ -67,18 +67,7 @@ mod tests {
         }",
         );
         let edn = String::from(
-            "{
-            :hello [
-                {
-                    :country-name \"brazil\",
-                    :word \"mundo\"
-                },
-                {
-                    :country-name \"usa\",
-                    :word \"world\"
-                }
-            ]
-        }",
+            "{:hello [{:country-name \"brazil\", :word \"mundo\"} {:country-name \"usa\", :word \"world\"}]}",
         );
 
         assert_eq!(edn, json_to_edn(json));
@@ -116,14 +105,14 @@ mod tests {
                 })),
             ])),
             Edn::Map(Map::new(map![
-                    String::from("false") => Edn::Key(":f".to_string()),
-                    String::from("nil") => Edn::Rational("3/4".to_string()),
-                    String::from(":my-crazy-map") => Edn::Map(Map::new(map![
-                        String::from("false") => Edn::Map(
+                    Edn::Bool(false) => Edn::Key(":f".to_string()),
+                    Edn::Nil => Edn::Rational("3/4".to_string()),
+                    Edn::Key(String::from(":my-crazy-map")) => Edn::Map(Map::new(map![
+                        Edn::Bool(false) => Edn::Map(
                             Map::new( map![
-                                String::from(":f") => Edn::Key(String::from(":b"))
+                                Edn::Key(String::from(":f")) => Edn::Key(String::from(":b"))
                             ])),
-                        String::from("nil") => Edn::Vector(
+                        Edn::Nil => Edn::Vector(
                             Vector::new( vec![
                                 Edn::Rational("3/4".to_string()),
                                 Edn::Int(1i64)
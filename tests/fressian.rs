@@ -0,0 +1,171 @@
+#[cfg(feature = "fressian")]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::{Edn, EdnError, fressian};
+
+    const VALUES: [&str; 12] = [
+        "nil",
+        ":foo",
+        "[1 -2 2.5 1.0 0.0 \"a\" \"hello, fressian\" \"é€😀\" \\c true false]",
+        "{:a {:b [1 2 3]} \"abcd\" {\"abcd\" ns/sym}}",
+        "[:ns/a :ns/b :ns/a sym ns/sym]",
+        "{1 :one -1 :minus-one 2.5 :double nil :nil}",
+        "[63 64 -64 -65 4096 -4097 16777216 1099511627776 9223372036854775807 -9223372036854775808]",
+        "[123456789012345678901234567890N -42N 1.50M -0.005M]",
+        "[1/2 -3/4 1/12345678901234567890]",
+        "#inst \"2020-07-16T21:53:14.628Z\"",
+        "{#uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\" #inst \"1985-04-12T23:20:50.52Z\"}",
+        "[#my/point [1 #other/tag {:a 1}] #my/point [2 3]]",
+    ];
+
+    #[test]
+    fn round_trips() {
+        for source in VALUES {
+            let edn = Edn::from_str(source).unwrap();
+            let bytes = fressian::to_bytes(&edn);
+
+            assert_eq!(
+                fressian::from_bytes(&bytes),
+                Ok(edn),
+                "{source} {bytes:02X?}"
+            );
+        }
+    }
+
+    #[test]
+    fn writes_like_fressian() {
+        for (source, bytes) in [
+            ("nil", &[0xF7][..]),
+            ("[true false]", &[0xE6, 0xF5, 0xF6]),
+            ("2.5", &[0xFA, 0x40, 0x04, 0, 0, 0, 0, 0, 0]),
+            ("{:a 1}", &[0xC0, 0xE6, 0xCA, 0xF7, 0xCD, 0xDB, b'a', 0x01]),
+            ("(1 2)", &[0xE6, 0x01, 0x02]),
+            ("#inst \"1970-01-01T00:00:01Z\"", &[0xC8, 0x53, 0xE8]),
+            (
+                "#uuid \"00000000-0000-0000-0000-000000000001\"",
+                &[
+                    0xC3, 0xD9, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+                ],
+            ),
+            (
+                "12345678901234567890N",
+                &[
+                    0xC6, 0xD9, 0x09, 0x00, 0xAB, 0x54, 0xA9, 0x8C, 0xEB, 0x1F, 0x0A, 0xD2,
+                ],
+            ),
+            (
+                "18446744073709551615",
+                &[
+                    0xC6, 0xD9, 0x09, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+                ],
+            ),
+            ("1.5M", &[0xC7, 0xD1, 0x0F, 0x01]),
+            (
+                "\\a",
+                &[0xEF, 0xDE, b'c', b'h', b'a', b'r', 0x01, 0x50, 0x61],
+            ),
+            (
+                "[#t 1 #t 2]",
+                &[0xE6, 0xEF, 0xDB, b't', 0x01, 0x01, 0xA0, 0x02],
+            ),
+        ] {
+            assert_eq!(
+                fressian::to_bytes(&Edn::from_str(source).unwrap()),
+                bytes,
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn caches_beyond_packed_references() {
+        let keys = (0..40).map(|i| format!(":k{i}")).collect::<Vec<_>>();
+        let tags = (0..20).map(|i| format!("#t{i} {i}")).collect::<Vec<_>>();
+        let source = format!("[{0} {0} {1} {1}]", keys.join(" "), tags.join(" "));
+        let edn = Edn::from_str(&source).unwrap();
+        let bytes = fressian::to_bytes(&edn);
+
+        // `:k39` is the 40th entry of the priority cache and `#t19` the 20th struct type
+        assert!(bytes.windows(3).any(|w| w == [0xF7, 0xCC, 0x27]));
+        assert!(bytes.windows(3).any(|w| w == [0xF0, 0x13, 0x13]));
+        assert_eq!(fressian::from_bytes(&bytes), Ok(edn));
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn round_trips_sets() {
+        let edn = Edn::from_str("{#{1 2} [#{:a}] :b #{}}").unwrap();
+        let bytes = fressian::to_bytes(&edn);
+
+        assert_eq!(fressian::from_bytes(&bytes), Ok(edn));
+        assert_eq!(
+            fressian::to_bytes(&Edn::from_str("#{}").unwrap()),
+            [0xC1, 0xE4]
+        );
+    }
+
+    #[test]
+    fn reads_other_codes() {
+        let chunked = [
+            0xED, 0xE2, 0x02, b'a', b'b', 0xDB, b'c', 0xD2, 0x01, 0x02, 0xB0, 0x02, 0x01, 0x02,
+            0xF9, 0x3F, 0xC0, 0x00, 0x00, 0xC5, 0xDF, b'h', b't', b't', b'p', b':', 0xFD,
+        ];
+        assert_eq!(
+            fressian::from_bytes(&chunked).unwrap().to_string(),
+            "[\"abc\" #bytes [1 2] [1 2] 1.5 #uri \"http:\"]"
+        );
+
+        // STRUCTTYPE "point" of 2 fields, META, RESET_CACHES and a footer, then an open list
+        let bytes = [
+            0xE6, 0xEF, 0xDF, b'p', b'o', b'i', b'n', b't', 0x02, 0x01, 0x02, 0xF1, 0xF7, 0xFE,
+            0xE5, 0x03, 0xCF, 0xCF, 0xCF, 0xCF, 0x00,
+        ];
+        assert_eq!(
+            fressian::from_bytes(&bytes).unwrap().to_string(),
+            "[#point [1 2] [3]]"
+        );
+        assert_eq!(
+            fressian::from_bytes(&[0xEE, 0x01, 0x02]),
+            Ok(Edn::from_str("[1 2]").unwrap())
+        );
+    }
+
+    #[test]
+    fn fressian_errors() {
+        for (bytes, message) in [
+            (&[0xE6, 0x01][..], "unexpected end of input at offset 2"),
+            (
+                &[0x80],
+                "reference to an empty priority cache entry at offset 1",
+            ),
+            (
+                &[0xA1],
+                "reference to an empty struct cache entry at offset 1",
+            ),
+            (
+                &[0xC0, 0xE5, 0x01],
+                "map of an odd number of elements at offset 3",
+            ),
+            (
+                &[0xC3, 0xD1, 0x01],
+                "uuid of other than 16 bytes at offset 3",
+            ),
+            (&[0xC2], "unexpected code 0xC2 at offset 0"),
+            (&[0x01, 0x02], "unexpected trailing bytes at offset 1"),
+            (&[0xDB, 0xFF], "invalid string at offset 2"),
+            (
+                &[
+                    0xC7, 0xD1, 0x01, 0xF8, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                "decimal scale out of range at offset 12",
+            ),
+        ] {
+            assert_eq!(
+                fressian::from_bytes(bytes),
+                Err(EdnError::Fressian(message.to_string())),
+                "{bytes:02X?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(feature = "json")]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::{
+        BigIntegers, Decimals, Edn, EdnError, JsonOptions, JsonReadOptions, KeyCase, KeyPolicy,
+        Rationals, Sets, Tags, from_json, json_to_edn, to_json_with,
+    };
+
+    #[test]
+    fn reads_json_into_edn() {
+        assert_eq!(
+            from_json(
+                r#" {"id": 1, "ratio": -2.5e3, "items": [true, false, null, "é😀\n"], "nested": {}} "#
+            ),
+            Ok(Edn::from_str(
+                "{:id 1 :ratio -2500.0 :items [true false nil \"é😀\n\"] :nested {}}"
+            )
+            .unwrap())
+        );
+        assert_eq!(from_json("[]"), Ok(Edn::from_str("[]").unwrap()));
+        assert_eq!(from_json("-7"), Ok(Edn::Int(-7)));
+    }
+
+    #[test]
+    fn keys_with_punctuation_and_non_ascii_letters() {
+        assert_eq!(
+            from_json(
+                r#"{"são paulo": 1, "a.b?": 2, "first_name": 3, "@type": 4, "a/b": 5, "": 6}"#
+            ),
+            Ok(
+                Edn::from_str("{:são-paulo 1 :a.b? 2 :first-name 3 \"@type\" 4 \"a/b\" 5 \"\" 6}")
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn strings_are_left_alone() {
+        assert_eq!(
+            from_json(r#"{"a": "null", "b": "x\":y", "c": "\"quoted\""}"#),
+            Ok(Edn::from_str(r#"{:a "null" :b "x\":y" :c "\"quoted\""}"#).unwrap())
+        );
+        assert_eq!(
+            json_to_edn(r#"{"a": "null", "b": "x\": y", "c\"d": 1}"#),
+            r#"{:a "null", :b "x\": y", "c\"d" 1}"#
+        );
+    }
+
+    #[test]
+    fn key_policies() {
+        let json = r#"{"userId": 1, "@id": 2}"#;
+
+        assert_eq!(
+            JsonReadOptions::new().keys(KeyPolicy::Strings).parse(json),
+            Ok(Edn::from_str(r#"{"userId" 1 "@id" 2}"#).unwrap())
+        );
+        assert_eq!(
+            JsonReadOptions::new()
+                .keys(KeyPolicy::Namespace("user".to_string()))
+                .parse(json),
+            Ok(Edn::from_str(r#"{:user/user-id 1 "@id" 2}"#).unwrap())
+        );
+    }
+
+    #[test]
+    fn numbers_and_big_integers() {
+        let json = "[18446744073709551615, -9223372036854775808, 18446744073709551616, 0.1, 1e2]";
+
+        assert_eq!(
+            from_json(json),
+            Ok(Edn::from_str(
+                "[18446744073709551615 -9223372036854775808 18446744073709551616N 0.1 100.0]"
+            )
+            .unwrap())
+        );
+        assert_eq!(
+            JsonReadOptions::new()
+                .decimals(Decimals::Decimal)
+                .big_integers(BigIntegers::Double)
+                .parse(json),
+            Ok(Edn::from_str(
+                "[18446744073709551615 -9223372036854775808 1.8446744073709552E19 0.1M 1e2M]"
+            )
+            .unwrap())
+        );
+        assert_eq!(
+            JsonReadOptions::new()
+                .big_integers(BigIntegers::Reject)
+                .parse(json),
+            Err(EdnError::Json(
+                "Integer 18446744073709551616 is too large".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn ordered_maps_keep_the_source_order() {
+        let edn = JsonReadOptions::new()
+            .ordered_maps(true)
+            .parse(r#"{"z": 1, "a": {"y": 2, "b": 3}, "z": 4}"#)
+            .unwrap();
+
+        assert_eq!(edn.to_string(), "{:z 4, :a {:y 2, :b 3}}");
+    }
+
+    #[test]
+    fn json_errors() {
+        for (json, message) in [
+            ("", "unexpected end of input at offset 0"),
+            ("{\"a\": 1,}", "expected a string key at offset 8"),
+            ("[1 2]", "expected `,` or `]` at offset 3"),
+            ("{'a': 1}", "expected a string key at offset 1"),
+            ("[1] 2", "unexpected trailing characters at offset 4"),
+        ] {
+            assert_eq!(
+                from_json(json),
+                Err(EdnError::Json(message.to_string())),
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn json_to_edn_rewrites_invalid_json_keeping_the_layout() {
+        assert_eq!(
+            json_to_edn("{\n  \"helloWorld\": [1, 'c', null],\n  \"x\" : true\n}"),
+            "{\n  :hello-world [1, \\c, nil],\n  :x  true\n}"
+        );
+    }
+
+    const VALUES: [&str; 6] = [
+        "[\\c \\newline \\u0000 \"tab\\tquote\\\"back\\\\slash\\u001f\" nil]",
+        "{:first-name 1 :my.ns/last-name 2 sym 3 \"s\" 4 1 5 nil 6 [1] 7 \\c 8}",
+        "[1/3 -3/4 1/0 ##NaN ##Inf 2.5 -0.0 12345678901234567890N 1.M 1.5e+3M]",
+        "#my/point [1 #other \"x\"]",
+        "{:at #inst \"2020-07-16T21:53:14.628Z\" :id #uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\"}",
+        "(1 (2) [3] {})",
+    ];
+
+    #[test]
+    fn to_json_with_is_always_valid_json() {
+        let mut options = vec![JsonOptions::new()];
+        for keywords in [KeyCase::Snake, KeyCase::DropColon, KeyCase::Keep] {
+            options.push(JsonOptions::new().keywords(keywords));
+        }
+        for tags in [Tags::Array, Tags::Value] {
+            options.push(JsonOptions::new().tags(tags));
+        }
+        for rationals in [Rationals::String, Rationals::Array] {
+            options.push(JsonOptions::new().rationals(rationals));
+        }
+        for sets in [Sets::Object, Sets::Tagged] {
+            options.push(JsonOptions::new().sets(sets));
+        }
+        let mut values = VALUES.map(|s| Edn::from_str(s).unwrap()).to_vec();
+        values.push(Edn::Empty);
+        values.push(Edn::BigInt("+1".to_string()));
+        values.push(Edn::Rational("x/y".to_string()));
+        for edn in values {
+            for options in &options {
+                let json = to_json_with(&edn, *options);
+                assert!(from_json(&json).is_ok(), "{edn:?} {options:?} {json}");
+            }
+        }
+    }
+
+    #[test]
+    fn to_json_with_options() {
+        let edn = Edn::from_str(
+            "{:first-name \\r :ratio -3/4 :tagged #my/tag [1] :f 1.0 :n ##NaN :d 1.M}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            to_json_with(&edn, JsonOptions::new()),
+            r#"{"d": 1.0, "f": 1.0, "firstName": "r", "n": null, "ratio": -0.75, "tagged": {"my/tag": [1]}}"#
+        );
+        assert_eq!(
+            to_json_with(
+                &edn,
+                JsonOptions::new()
+                    .keywords(KeyCase::Keep)
+                    .tags(Tags::Array)
+                    .rationals(Rationals::Array)
+            ),
+            r#"{":d": 1.0, ":f": 1.0, ":first-name": "r", ":n": null, ":ratio": [-3, 4], ":tagged": ["my/tag", [1]]}"#
+        );
+        assert_eq!(
+            to_json_with(&Edn::Str("\u{1}é\"".to_string()), JsonOptions::new()),
+            r#""\u0001é\"""#
+        );
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn to_json_with_sets() {
+        let edn = Edn::from_str("#{:a-b 1}").unwrap();
+
+        assert_eq!(to_json_with(&edn, JsonOptions::new()), r#"["aB", 1]"#);
+        assert_eq!(
+            to_json_with(&edn, JsonOptions::new().sets(Sets::Object)),
+            r#"{"aB": true, "1": true}"#
+        );
+        assert_eq!(
+            to_json_with(
+                &edn,
+                JsonOptions::new()
+                    .sets(Sets::Tagged)
+                    .keywords(KeyCase::Snake)
+            ),
+            r#"{"set": ["a_b", 1]}"#
+        );
+    }
+}
@@ -3,10 +3,25 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod async_reader;
+pub mod builtin_tags;
+pub mod cst;
 pub mod deserialize;
 pub mod deserialize_sets;
+pub mod diff;
 pub mod emit;
 pub mod emit_json;
+pub mod fressian;
+pub mod json;
+pub mod merge;
 pub mod parse;
+pub mod parse_borrowed;
 pub mod parse_sets;
+pub mod pretty;
+pub mod query;
+pub mod reader;
+pub mod round_trip;
+pub mod schema;
 pub mod ser;
+pub mod serde;
+pub mod transit;
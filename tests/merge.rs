@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::merge::{MergeStrategy, SeqMerge};
+    use edn_rs::{Edn, ReaderOptions};
+
+    const BASE: &str = "{:service \"billing\"
+                         :db {:host \"localhost\" :port 5432 :pool {:size 4}}
+                         :features [:search]
+                         :log-level :info}";
+    const PRODUCTION: &str = "{:db {:host \"db-1\" :pool {:size 16 :timeout-ms 500}}
+                               :features [:audit-log]
+                               :log-level :warn}";
+    const LOCAL: &str = "{:db {:port 5433} :log-level :debug}";
+
+    #[test]
+    fn layers_configuration() {
+        let strategy = MergeStrategy::new().vectors(SeqMerge::Concat);
+        let mut config = Edn::from_str(BASE).unwrap();
+        for layer in [PRODUCTION, LOCAL] {
+            config.deep_merge(Edn::from_str(layer).unwrap(), &strategy);
+        }
+
+        assert_eq!(
+            config,
+            Edn::from_str(
+                "{:service \"billing\"
+                  :db {:host \"db-1\" :port 5433 :pool {:size 16 :timeout-ms 500}}
+                  :features [:search :audit-log]
+                  :log-level :debug}"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn resolver_only_sees_conflicts() {
+        let strategy =
+            MergeStrategy::new().resolve(|path: &[Edn], current: Edn, other: Edn| match path {
+                [Edn::Key(k)] if k == ":log-level" => current,
+                _ => other,
+            });
+        let mut config = Edn::from_str(BASE).unwrap();
+        config.deep_merge(Edn::from_str(PRODUCTION).unwrap(), &strategy);
+
+        assert_eq!(config[":log-level"], Edn::from_str(":info").unwrap());
+        assert_eq!(config[":db"][":host"], Edn::Str("db-1".to_string()));
+        assert_eq!(config[":features"], Edn::from_str("[:audit-log]").unwrap());
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn unites_sets() {
+        let mut roles = Edn::from_str("{:roles #{:dev} :owners #{:ana}}").unwrap();
+        roles.deep_merge(
+            Edn::from_str("{:roles #{:ops}}").unwrap(),
+            &MergeStrategy::new(),
+        );
+
+        assert_eq!(
+            roles,
+            Edn::from_str("{:roles #{:dev :ops} :owners #{:ana}}").unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_ordered_maps_in_order() {
+        let options = ReaderOptions::new().ordered_maps(true);
+        let mut config = options.parse("{:z 1 :a {:y 2}}").unwrap();
+        config.deep_merge(
+            options.parse("{:b 3 :a {:x 4 :y 5}}").unwrap(),
+            &MergeStrategy::new(),
+        );
+
+        assert_eq!(config.to_string(), "{:z 1, :a {:y 5, :x 4}, :b 3}");
+    }
+}
@@ -63,8 +63,8 @@ mod tests {
     #[test]
     fn parse_simple_map() {
         let expected = Edn::Map(Map::new(map! {
-            String::from("1.2") => Edn::Bool(false),
-            String::from(":b") => Edn::Rational(String::from("3/4"))
+            Edn::Double(1.2.into()) => Edn::Bool(false),
+            Edn::Key(String::from(":b")) => Edn::Rational(String::from("3/4"))
         }));
 
         assert_eq!(edn!({1.2 false, :b 3/4}), expected);
@@ -111,8 +111,8 @@ mod tests {
             Edn::Double(1.2.into()),
             Edn::Int(3),
             Edn::Map(Map::new(map![
-                    String::from("false") => Edn::Key(":f".to_string()),
-                    String::from("nil") => Edn::Rational("3/4".to_string())
+                    Edn::Bool(false) => Edn::Key(":f".to_string()),
+                    Edn::Nil => Edn::Rational("3/4".to_string())
             ])),
         ]));
 
@@ -126,11 +126,11 @@ mod tests {
             Edn::Double(1.2.into()),
             Edn::Int(3),
             Edn::Map(Map::new(map![
-                    String::from("false") => Edn::Map(
+                    Edn::Bool(false) => Edn::Map(
                         Map::new( map![
-                            String::from(":f") => Edn::Key(String::from(":b"))
+                            Edn::Key(String::from(":f")) => Edn::Key(String::from(":b"))
                         ])),
-                    String::from("nil") => Edn::Vector(
+                    Edn::Nil => Edn::Vector(
                         Vector::new( vec![
                             Edn::Rational("3/4".to_string()),
                             Edn::Int(1i64)
@@ -141,6 +141,16 @@ mod tests {
         assert_eq!(edn!(( 1 1.2 3 {false {:f :b} nil [3/4 1]})), expected);
     }
 
+    #[test]
+    fn parse_map_with_composite_keys() {
+        let expected = Edn::Map(Map::new(map! {
+            Edn::Vector(Vector::new(vec![Edn::Int(1), Edn::Int(2)])) => Edn::Key(":a".to_string()),
+            Edn::Str("one".to_string()) => Edn::Key(":b".to_string())
+        }));
+
+        assert_eq!(edn!({[1 2] :a "one" :b}), expected);
+    }
+
     #[test]
     fn navigate_data_structure() {
         let edn = edn!([1 1.2 3 {false :f nil 3/4 2 "banana"}]);
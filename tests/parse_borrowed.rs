@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::borrow::Cow;
+    use core::str::FromStr;
+
+    use edn_rs::{Edn, EdnRef, parse_borrowed};
+
+    #[test]
+    fn borrows_keywords_symbols_and_strings() {
+        let edn = parse_borrowed("(:kw sym \"str\" #tag 1)").unwrap();
+
+        assert_eq!(
+            edn,
+            EdnRef::List(vec![
+                EdnRef::Key(Cow::Borrowed(":kw")),
+                EdnRef::Symbol(Cow::Borrowed("sym")),
+                EdnRef::Str(Cow::Borrowed("str")),
+                EdnRef::Tagged(Cow::Borrowed("tag"), Box::new(EdnRef::UInt(1))),
+            ])
+        );
+        let EdnRef::List(items) = edn else { panic!() };
+        assert!(
+            items
+                .iter()
+                .all(|e| !matches!(e, EdnRef::Str(Cow::Owned(_)) | EdnRef::Key(Cow::Owned(_))))
+        );
+    }
+
+    #[test]
+    fn escaped_strings_are_owned() {
+        let edn = parse_borrowed(r#""hello \"world\"""#).unwrap();
+
+        assert!(matches!(edn, EdnRef::Str(Cow::Owned(ref s)) if s == "hello \"world\""));
+    }
+
+    #[test]
+    fn multibyte_tokens() {
+        let edn = parse_borrowed("{:café \"açaí\" λ ∞}").unwrap();
+
+        assert_eq!(
+            edn,
+            EdnRef::Map(vec![
+                (
+                    EdnRef::Key(Cow::Borrowed(":café")),
+                    EdnRef::Str(Cow::Borrowed("açaí"))
+                ),
+                (
+                    EdnRef::Symbol(Cow::Borrowed("λ")),
+                    EdnRef::Symbol(Cow::Borrowed("∞"))
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn map_keeps_source_order() {
+        let edn = parse_borrowed("{:b 1 :a 2}").unwrap();
+
+        assert_eq!(
+            edn,
+            EdnRef::Map(vec![
+                (EdnRef::Key(Cow::Borrowed(":b")), EdnRef::UInt(1)),
+                (EdnRef::Key(Cow::Borrowed(":a")), EdnRef::UInt(2)),
+            ])
+        );
+    }
+
+    #[test]
+    fn into_owned_matches_from_str() {
+        let edn = "{:a \"2\" :b [true false] :c {:A {:a :b}} :d (1.5 -3 3/4 \\c) :e #inst \"2020-07-16T21:53:14.628-00:00\"}";
+
+        assert_eq!(
+            parse_borrowed(edn).unwrap().into_owned(),
+            Edn::from_str(edn).unwrap()
+        );
+    }
+
+    #[test]
+    fn errors_match_from_str() {
+        let edn = "[1 2 \"unterminated]";
+
+        assert_eq!(
+            parse_borrowed(edn).unwrap_err(),
+            Edn::from_str(edn).unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::Edn;
+    use edn_rs::pretty::{PrettyOptions, to_string_pretty};
+
+    const CONFIG: &str = "{:server {:host \"localhost\" :port 8080 :timeouts {:read 30 :write 10}} \
+                          :features [:search :billing] :name \"app\" :tags (:a :b) \
+                          :empty [] :retry (1 2 4 8 16 32 64 128)}";
+
+    fn pretty(source: &str, options: PrettyOptions) -> String {
+        to_string_pretty(&Edn::from_str(source).unwrap(), options)
+    }
+
+    #[test]
+    fn fits_on_one_line() {
+        assert_eq!(
+            pretty("{:a [1 2] :b \"c\"}", PrettyOptions::default()),
+            "{:a [1 2] :b \"c\"}"
+        );
+        assert_eq!(pretty("42", PrettyOptions::default()), "42");
+    }
+
+    #[test]
+    fn breaks_what_does_not_fit() {
+        let options = PrettyOptions {
+            width: 30,
+            ..PrettyOptions::default()
+        };
+
+        assert_eq!(
+            pretty(CONFIG, options),
+            "{
+  :empty []
+  :features [:search :billing]
+  :name \"app\"
+  :retry (
+    1
+    2
+    4
+    8
+    16
+    32
+    64
+    128
+  )
+  :server {
+    :host \"localhost\"
+    :port 8080
+    :timeouts {
+      :read 30
+      :write 10
+    }
+  }
+  :tags (:a :b)
+}"
+        );
+    }
+
+    #[test]
+    fn indent_alignment_commas_and_sorting() {
+        let options = PrettyOptions {
+            indent: 4,
+            width: 40,
+            align_map_values: true,
+            map_commas: true,
+            sort_keys: true,
+            namespace_maps: false,
+        };
+
+        assert_eq!(
+            pretty(CONFIG, options),
+            "{
+    :empty    [],
+    :features [:search :billing],
+    :name     \"app\",
+    :retry    (1 2 4 8 16 32 64 128),
+    :server   {
+        :host     \"localhost\",
+        :port     8080,
+        :timeouts {:read 30, :write 10}
+    },
+    :tags     (:a :b)
+}"
+        );
+    }
+
+    #[test]
+    fn tagged_elements_keep_their_tag_on_the_line() {
+        let options = PrettyOptions {
+            width: 20,
+            ..PrettyOptions::default()
+        };
+
+        assert_eq!(
+            pretty(
+                "[#point {:x 100 :y 200} #inst \"2020-07-16T21:53:14.628Z\"]",
+                options
+            ),
+            "[
+  #point {
+    :x 100
+    :y 200
+  }
+  #inst \"2020-07-16T21:53:14.628Z\"
+]"
+        );
+    }
+
+    #[test]
+    fn compacts_namespaced_maps() {
+        let options = PrettyOptions {
+            width: 30,
+            namespace_maps: true,
+            ..PrettyOptions::default()
+        };
+
+        assert_eq!(
+            pretty(
+                "[{:user/id 1 :user/name \"a\"} {:user/id 2 :role/name \"b\"} {:id 3} {}]",
+                options
+            ),
+            "[
+  #:user{:id 1 :name \"a\"}
+  {:role/name \"b\" :user/id 2}
+  {:id 3}
+  {}
+]"
+        );
+        assert_eq!(
+            pretty("{:user/id 1 :user/roles [:admin :support]}", options),
+            "#:user{
+  :id 1
+  :roles [:admin :support]
+}"
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let options = PrettyOptions {
+            width: 10,
+            map_commas: true,
+            ..PrettyOptions::default()
+        };
+        let edn = Edn::from_str(CONFIG).unwrap();
+
+        assert_eq!(
+            Edn::from_str(&to_string_pretty(&edn, options)).unwrap(),
+            edn
+        );
+
+        let options = PrettyOptions {
+            namespace_maps: true,
+            ..options
+        };
+        let edn = Edn::from_str("{:a #:user{:id 1 :name \"a\" :_/tag 2}}").unwrap();
+
+        assert_eq!(
+            Edn::from_str(&to_string_pretty(&edn, options)).unwrap(),
+            edn
+        );
+    }
+}
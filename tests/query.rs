@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::query::{Predicate, Query, Step, Test};
+    use edn_rs::{Edn, EdnError, ReaderOptions};
+
+    const PULL: &str = "{:db/id 17
+                         :order/items [{:item/sku \"a-1\" :item/qty 2 :item/price 9.5}
+                                       {:item/sku \"b-2\" :item/qty 1 :item/price 120}
+                                       {:item/sku \"c-3\" :item/qty 5}]
+                         :order/customer {:db/id 3 :customer/name \"ana\" :customer/tier :gold}}";
+
+    fn values<'a>(edn: &'a Edn, query: &str) -> Vec<&'a Edn> {
+        edn.query(query)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.value)
+            .collect()
+    }
+
+    #[test]
+    fn get_in_follows_keys_and_indexes() {
+        let edn = Edn::from_str(PULL).unwrap();
+
+        assert_eq!(
+            edn.get_in(&[":order/items".into(), 1.into(), ":item/sku".into()]),
+            Some(&Edn::Str("b-2".to_string()))
+        );
+        assert_eq!(
+            edn.get_in(&[":order/customer".into(), ":customer/tier".into()]),
+            Some(&Edn::Key(":gold".to_string()))
+        );
+        assert_eq!(edn.get_in(&[":order/items".into(), 3.into()]), None);
+        assert_eq!(edn.get_in(&[":order/items".into(), (-1).into()]), None);
+        assert_eq!(edn.get_in(&[":missing".into(), 0.into()]), None);
+        assert_eq!(edn.get_in(&[]), Some(&edn));
+    }
+
+    #[test]
+    fn wildcards_and_descendants() {
+        let edn = Edn::from_str(PULL).unwrap();
+
+        assert_eq!(
+            values(&edn, ":order/items * :item/sku"),
+            ["\"a-1\"", "\"b-2\"", "\"c-3\""]
+                .map(|s| Edn::from_str(s).unwrap())
+                .iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            edn.query(".. :db/id")
+                .unwrap()
+                .into_iter()
+                .map(|m| (m.path, m.value.clone()))
+                .collect::<Vec<_>>(),
+            [
+                (vec![Edn::Key(":db/id".to_string())], Edn::UInt(17)),
+                (
+                    vec![
+                        Edn::Key(":order/customer".to_string()),
+                        Edn::Key(":db/id".to_string())
+                    ],
+                    Edn::UInt(3)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn predicates() {
+        let edn = Edn::from_str(PULL).unwrap();
+
+        assert_eq!(
+            values(&edn, ":order/items (>= :item/qty 2) :item/sku"),
+            [&Edn::Str("a-1".to_string()), &Edn::Str("c-3".to_string())]
+        );
+        assert_eq!(
+            values(&edn, ":order/items (> :item/price 10.0) :item/qty"),
+            [&Edn::UInt(1)]
+        );
+        assert_eq!(
+            values(&edn, ":order/items (some? :item/price) :item/sku").len(),
+            2
+        );
+        assert_eq!(
+            values(&edn, ":order/items (= :item/price nil) :item/sku"),
+            [&Edn::Str("c-3".to_string())]
+        );
+        assert_eq!(
+            values(&edn, ":order/items (not= :item/sku \"a-1\") (= [] 5)"),
+            [&Edn::UInt(5)]
+        );
+
+        let ids = Edn::from_str("[{:id 9007199254740993} {:id 9007199254740992}]").unwrap();
+        assert_eq!(
+            values(&ids, "(= :id 9007199254740992) :id"),
+            [&Edn::UInt(9_007_199_254_740_992)]
+        );
+
+        let predicate = Predicate {
+            path: vec![Edn::Key(":customer/tier".to_string())],
+            test: Test::Eq(Edn::Key(":gold".to_string())),
+        };
+        assert!(predicate.holds(&edn[":order/customer"]));
+        assert_eq!(
+            edn.select(&[Step::Filter(predicate)])[0].path,
+            [Edn::Key(":order/customer".to_string())]
+        );
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn set_membership() {
+        let edn = Edn::from_str(PULL).unwrap();
+
+        assert_eq!(
+            values(&edn, ":order/customer #{:db/id :customer/tier}"),
+            [&Edn::Key(":gold".to_string()), &Edn::UInt(3)]
+        );
+        assert_eq!(
+            values(&edn, ":order/items * #{:item/qty} #{2 5}"),
+            Vec::<&Edn>::new()
+        );
+        assert_eq!(
+            values(&edn, ":order/items * :item/qty"),
+            [&Edn::UInt(2), &Edn::UInt(1), &Edn::UInt(5)]
+        );
+
+        let tags = Edn::from_str("{:tags #{:a :b :c}}").unwrap();
+        assert_eq!(
+            values(&tags, ":tags #{:b :z}"),
+            [&Edn::Key(":b".to_string())]
+        );
+        assert_eq!(
+            tags.query(":tags :c").unwrap()[0].path,
+            [Edn::Key(":tags".to_string()), Edn::Key(":c".to_string())]
+        );
+    }
+
+    #[test]
+    fn ordered_maps_keep_their_order() {
+        let edn = ReaderOptions::new()
+            .ordered_maps(true)
+            .parse("{:z 1 :a {:y 2 :b 3}}")
+            .unwrap();
+
+        assert_eq!(
+            values(&edn, ".. *"),
+            [&Edn::UInt(1), &edn[":a"], &Edn::UInt(2), &Edn::UInt(3)]
+        );
+        assert_eq!(
+            edn.query(":a *")
+                .unwrap()
+                .into_iter()
+                .map(|m| m.path)
+                .collect::<Vec<_>>(),
+            [
+                vec![Edn::Key(":a".to_string()), Edn::Key(":y".to_string())],
+                vec![Edn::Key(":a".to_string()), Edn::Key(":b".to_string())]
+            ]
+        );
+    }
+
+    #[test]
+    fn query_errors() {
+        assert!(matches!(
+            Query::from_str(":a (= :b"),
+            Err(EdnError::Parse(err)) if err.column == 4
+        ));
+        assert_eq!(
+            Query::from_str(":a (:b)"),
+            Err(EdnError::ParseEdn(
+                "Invalid query predicate (:b)".to_string()
+            ))
+        );
+        assert_eq!(Query::from_str("").unwrap(), Query::default());
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+    use std::str::FromStr;
+
+    use edn_rs::{Edn, EdnError, EdnReader, ParseErrorKind, ReaderOptions, TagRegistry};
+
+    // Hands out at most `chunk` bytes per read, then fails if `error` is set
+    struct Chunked<'a> {
+        data: &'a [u8],
+        chunk: usize,
+        error: bool,
+    }
+
+    impl Read for Chunked<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.data.is_empty() && self.error {
+                return Err(io::Error::other("connection reset"));
+            }
+            let n = self.chunk.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    const LOG: &str = "{:tx 1 :data [\"a b\" \\space]}\n; comment ]\n(:tx 2 #inst \"2020\")\n:tx3 #_ \"skip\" 4.5,açaí[(:x 1)]";
+
+    #[test]
+    fn reads_forms_across_chunks() {
+        let expected = vec![
+            Edn::from_str("{:tx 1 :data [\"a b\" \\space]}").unwrap(),
+            Edn::from_str("(:tx 2 #inst \"2020\")").unwrap(),
+            Edn::Key(":tx3".to_string()),
+            Edn::Double(4.5.into()),
+            Edn::Symbol("açaí".to_string()),
+            Edn::from_str("[(:x 1)]").unwrap(),
+        ];
+
+        for chunk in [1, 2, 3, 7, 64] {
+            let reader = EdnReader::with_capacity(
+                chunk,
+                Chunked {
+                    data: LOG.as_bytes(),
+                    chunk,
+                    error: false,
+                },
+            );
+
+            assert_eq!(
+                reader.collect::<Result<Vec<_>, _>>().unwrap(),
+                expected,
+                "chunk of {chunk}"
+            );
+        }
+    }
+
+    #[test]
+    fn discards_inside_tags_and_other_discards() {
+        let source = "#foo #_ 1 2 #_ #bar 3 4 #_ #_ 5 6 7";
+        let forms = EdnReader::new(source.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(forms[0], Edn::from_str("#foo #_ 1 2").unwrap());
+        assert_eq!(
+            forms,
+            [
+                Edn::Tagged("foo".to_string(), Box::new(Edn::UInt(2))),
+                Edn::UInt(4),
+                Edn::UInt(7),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_symbolic_values_as_elements() {
+        let forms = EdnReader::new(&b"##Inf ##-Inf [##NaN] 1.0"[..])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            forms.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["##Inf", "##-Inf", "[##NaN]", "1.0"]
+        );
+    }
+
+    #[test]
+    fn reads_namespaced_maps_as_elements() {
+        let forms = EdnReader::new(&b"#:user{:id 1} #:user {:id 2} #::{:id 3}"[..])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            forms.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["{:user/id 1}", "{:user/id 2}", "{::id 3}"]
+        );
+    }
+
+    #[test]
+    fn continues_after_parse_error() {
+        let mut reader = EdnReader::new(&b"[1 2]\n{:a 1 :b \"\\q\"} :ok\n(1"[..]);
+
+        assert_eq!(reader.next(), Some(Edn::from_str("[1 2]")));
+        let Some(Err(EdnError::Parse(err))) = reader.next() else {
+            panic!()
+        };
+        assert_eq!(err.kind, ParseErrorKind::InvalidEscape('q'));
+        assert_eq!((err.line, err.column), (2, 11));
+        assert_eq!(reader.next(), Some(Ok(Edn::Key(":ok".to_string()))));
+        let Some(Err(EdnError::Parse(err))) = reader.next() else {
+            panic!()
+        };
+        assert_eq!(err.kind, ParseErrorKind::Unclosed("("));
+        assert_eq!((err.line, err.column, err.offset), (3, 1, 25));
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn skips_forms_longer_than_max_form_len() {
+        let stream = ":a \"0123456789\" [1 [2 3 4 5 6 7 8]] :b\n\"unterminated 0123456789";
+        let mut reader = EdnReader::with_capacity(4, stream.as_bytes())
+            .options(ReaderOptions::new().max_form_len(12));
+
+        assert_eq!(reader.next(), Some(Ok(Edn::Key(":a".to_string()))));
+        assert_eq!(reader.next(), Some(Ok(Edn::Str("0123456789".to_string()))));
+        let Some(Err(EdnError::Parse(err))) = reader.next() else {
+            panic!()
+        };
+        assert_eq!(err.kind, ParseErrorKind::FormTooLong(12));
+        assert_eq!((err.line, err.column, err.offset), (1, 17, 16));
+        assert_eq!(reader.next(), Some(Ok(Edn::Key(":b".to_string()))));
+        let Some(Err(EdnError::Parse(err))) = reader.next() else {
+            panic!()
+        };
+        assert_eq!(err.kind, ParseErrorKind::FormTooLong(12));
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn stops_on_io_error() {
+        let mut reader = EdnReader::new(Chunked {
+            data: b":a [1",
+            chunk: 4,
+            error: true,
+        });
+
+        assert_eq!(reader.next(), Some(Ok(Edn::Key(":a".to_string()))));
+        assert_eq!(
+            reader.next(),
+            Some(Err(EdnError::Io(
+                io::ErrorKind::Other,
+                "connection reset".to_string()
+            )))
+        );
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn empty_streams() {
+        assert_eq!(EdnReader::new(&b""[..]).next(), None);
+        assert_eq!(EdnReader::new(&b" ,\n; nothing"[..]).next(), None);
+    }
+
+    #[test]
+    fn reads_with_options() {
+        let options = ReaderOptions::new().tags(TagRegistry::new().register("neg", |edn: Edn| {
+            edn.to_int().map(|i| Edn::Int(-i)).ok_or("not an int")
+        }));
+        let mut reader = EdnReader::new(&b"#neg 1\n#neg :a\n[#neg 3]"[..]).options(options);
+
+        assert_eq!(reader.next(), Some(Ok(Edn::Int(-1))));
+        let Some(Err(EdnError::Parse(err))) = reader.next() else {
+            panic!()
+        };
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(reader.next(), Some(Edn::from_str("[-3]")));
+        assert_eq!(reader.next(), None);
+    }
+}
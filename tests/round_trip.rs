@@ -0,0 +1,264 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    #[cfg(feature = "sets")]
+    use edn_rs::Set;
+    use edn_rs::pretty::{PrettyOptions, to_string_pretty};
+    use edn_rs::{Edn, Keyword, List, Map, Symbol, Vector};
+
+    const CASES: usize = 2_000;
+
+    /// xorshift64*, so every run generates the same values and failures can be reproduced.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            usize::try_from(self.next() % n as u64).unwrap()
+        }
+
+        fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+            items[self.below(items.len())]
+        }
+    }
+
+    // Chars that have tripped up emitters: quotes, escapes, control chars, non-ASCII whitespace
+    // and chars outside the BMP.
+    const SPECIAL_CHARS: [char; 20] = [
+        '"',
+        '\\',
+        '\n',
+        '\r',
+        '\t',
+        ' ',
+        '\0',
+        '\u{1f}',
+        '\u{7f}',
+        '\u{85}',
+        '\u{a0}',
+        '\u{c}',
+        '\u{2028}',
+        '\u{feff}',
+        'é',
+        '€',
+        '😀',
+        '\u{10ffff}',
+        ']',
+        ';',
+    ];
+    // Every char of the symbol grammar, weighted towards the letters of `nil`, `true` and
+    // `false` and the chars with rules of their own: `-`, `+` and `.` before a digit, `:` and
+    // `#` after the first char, and `/` between the namespace and the name.
+    const SYMBOL_CHARS: &[char] = &[
+        'n', 'i', 'l', 't', 'r', 'u', 'e', 'f', 'a', 's', 'x', 'Z', 'λ', '0', '1', '9', '*', '+',
+        '!', '-', '_', '?', '$', '%', '&', '=', '<', '>', '.', '\'', '#', ':', '/',
+    ];
+    const RESERVED: [&str; 3] = ["nil", "true", "false"];
+
+    fn char(rng: &mut Rng) -> char {
+        if rng.below(3) == 0 {
+            rng.pick(&SPECIAL_CHARS)
+        } else {
+            char::from(u8::try_from(0x20 + rng.below(0x5f)).unwrap())
+        }
+    }
+
+    fn string(rng: &mut Rng) -> String {
+        (0..rng.below(12)).map(|_| char(rng)).collect()
+    }
+
+    fn candidate(rng: &mut Rng) -> String {
+        (0..=rng.below(8)).map(|_| rng.pick(SYMBOL_CHARS)).collect()
+    }
+
+    fn symbol(rng: &mut Rng) -> String {
+        loop {
+            let symbol = candidate(rng);
+            if Symbol::from_str(&symbol).is_ok() && !RESERVED.contains(&symbol.as_str()) {
+                return symbol;
+            }
+        }
+    }
+
+    fn keyword(rng: &mut Rng) -> String {
+        loop {
+            let keyword = format!(":{}", candidate(rng));
+            if Keyword::from_str(&keyword).is_ok() {
+                return keyword;
+            }
+        }
+    }
+
+    fn digits(rng: &mut Rng) -> String {
+        let mut digits = (rng.below(9) + 1).to_string();
+        for _ in 0..rng.below(30) {
+            digits.push_str(&rng.below(10).to_string());
+        }
+        digits
+    }
+
+    fn sign(rng: &mut Rng) -> &'static str {
+        rng.pick(&["", "-"])
+    }
+
+    fn double(rng: &mut Rng) -> f64 {
+        match rng.below(3) {
+            0 => rng.pick(&[
+                0.0,
+                -0.0,
+                1.0,
+                -1.0,
+                0.1,
+                1e21,
+                1e300,
+                -1e-300,
+                5e-324,
+                f64::MAX,
+                f64::MIN_POSITIVE,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NAN,
+            ]),
+            #[allow(clippy::cast_precision_loss)]
+            1 => rng.next() as f64 / 1024.0,
+            _ => f64::from_bits(rng.next()),
+        }
+    }
+
+    fn scalar(rng: &mut Rng) -> Edn {
+        match rng.below(13) {
+            0 => Edn::Key(keyword(rng)),
+            1 => Edn::Symbol(symbol(rng)),
+            2 => Edn::Str(string(rng)),
+            // Non-negative integers are read as `UInt`
+            3 => Edn::Int(i64::MIN + i64::try_from(rng.next() >> 1).unwrap()),
+            4 => Edn::UInt(rng.next()),
+            5 => Edn::Double(double(rng).into()),
+            6 => Edn::Rational(format!("{}{}/{}", sign(rng), digits(rng), digits(rng))),
+            7 => Edn::BigInt(format!("{}{}", sign(rng), digits(rng))),
+            8 => {
+                let exponent = rng.pick(&["", "e10", "E-3", "e+7"]);
+                let decimal = format!("{}{}.{}{exponent}", sign(rng), digits(rng), digits(rng));
+                Edn::Decimal(decimal)
+            }
+            9 => Edn::Char(char(rng)),
+            10 => Edn::Bool(rng.below(2) == 0),
+            _ => Edn::Nil,
+        }
+    }
+
+    fn edn(rng: &mut Rng, depth: usize) -> Edn {
+        if depth == 0 || rng.below(3) == 0 {
+            return scalar(rng);
+        }
+        let elements = |rng: &mut Rng| -> Vec<Edn> {
+            (0..rng.below(5)).map(|_| edn(rng, depth - 1)).collect()
+        };
+        match rng.below(5) {
+            0 => Edn::Vector(Vector::new(elements(rng))),
+            1 => Edn::List(List::new(elements(rng))),
+            2 => Edn::Map(Map::new(
+                (0..rng.below(5))
+                    .map(|_| (edn(rng, depth - 1), edn(rng, depth - 1)))
+                    .collect(),
+            )),
+            #[cfg(feature = "sets")]
+            3 => Edn::Set(Set::new(elements(rng).into_iter().collect())),
+            _ => {
+                // Tags begin with a letter, `#_` discards and `#:` is a namespaced map, and
+                // `#inst` and `#uuid` are validated
+                let tag = loop {
+                    let tag = symbol(rng);
+                    if tag.starts_with(char::is_alphabetic) && tag != "inst" && tag != "uuid" {
+                        break tag;
+                    }
+                };
+                Edn::Tagged(tag, Box::new(edn(rng, depth - 1)))
+            }
+        }
+    }
+
+    fn assert_round_trips(edn: &Edn) {
+        let emitted = edn.to_string();
+        assert_eq!(
+            Edn::from_str(&emitted).as_ref(),
+            Ok(edn),
+            "emitted as {emitted}"
+        );
+    }
+
+    #[test]
+    fn generated_values_round_trip() {
+        let mut rng = Rng(0x5eed_1234_abcd_ef01);
+
+        for _ in 0..CASES {
+            assert_round_trips(&edn(&mut rng, 4));
+        }
+    }
+
+    #[test]
+    fn generated_values_round_trip_pretty() {
+        let mut rng = Rng(0x0dd_ba11);
+        let options = PrettyOptions {
+            width: 20,
+            ..PrettyOptions::default()
+        };
+
+        for _ in 0..CASES / 4 {
+            let edn = edn(&mut rng, 4);
+            let emitted = to_string_pretty(&edn, options);
+            assert_eq!(
+                Edn::from_str(&emitted).as_ref(),
+                Ok(&edn),
+                "emitted as {emitted}"
+            );
+        }
+    }
+
+    #[test]
+    fn doubles_keep_their_type() {
+        assert_eq!(Edn::Double(1.0.into()).to_string(), "1.0");
+        assert_eq!(Edn::Double((-0.0).into()).to_string(), "-0.0");
+        assert_eq!(
+            Edn::Double(1e21.into()).to_string(),
+            "1000000000000000000000.0"
+        );
+        assert_eq!(Edn::Double(f64::INFINITY.into()).to_string(), "##Inf");
+        assert_eq!(Edn::Double(f64::NEG_INFINITY.into()).to_string(), "##-Inf");
+        assert_eq!(Edn::Double(f64::NAN.into()).to_string(), "##NaN");
+        assert_round_trips(&Edn::Double(1.0.into()));
+        assert_round_trips(&Edn::Double(f64::NAN.into()));
+    }
+
+    #[test]
+    fn strings_use_edn_escapes() {
+        let edn = Edn::Str(String::from(
+            "tab\there \"quoted\" \\ \u{8}\u{c}\u{1f}\u{7f} é",
+        ));
+
+        assert_eq!(
+            edn.to_string(),
+            "\"tab\\there \\\"quoted\\\" \\\\ \\b\\f\\u001f\\u007f é\""
+        );
+        assert_round_trips(&edn);
+    }
+
+    #[test]
+    fn whitespace_and_control_chars_use_unicode_escapes() {
+        assert_eq!(Edn::Char('\u{a0}').to_string(), "\\u00a0");
+        assert_eq!(Edn::Char('\0').to_string(), "\\u0000");
+        assert_eq!(Edn::Char('é').to_string(), "\\é");
+        assert_eq!(Edn::Char('\u{c}').to_string(), "\\formfeed");
+        assert_eq!(Edn::Char('\u{8}').to_string(), "\\backspace");
+        assert_eq!(Edn::from_str("\\u00e9").unwrap(), Edn::Char('é'));
+        assert_eq!(Edn::from_str("\\u").unwrap(), Edn::Char('u'));
+        assert_round_trips(&Edn::Char('\u{2028}'));
+    }
+}
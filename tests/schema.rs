@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::schema::{ProblemKind, Schema};
+    use edn_rs::{Edn, EdnError};
+
+    const ORDER: &str = "[:map {:closed true}
+                          [:id :uuid]
+                          [:placed-at :inst]
+                          [:customer [:map [:name [:string {:min 1}]] [:tier [:enum :free :gold]]]]
+                          [:items [:vector {:min 1} [:tuple :keyword [:int {:min 1}]]]]
+                          [:note {:optional true} [:maybe :string]]
+                          [:meta [:map-of :qualified-keyword [:or :string :number]]]]";
+
+    #[test]
+    fn validates_payloads() {
+        let schema = Schema::from_str(ORDER).unwrap();
+
+        assert_eq!(
+            schema.validate(
+                &Edn::from_str(
+                    "{:id #uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\"
+                  :placed-at #inst \"2020-07-16T21:53:14.628Z\"
+                  :customer {:name \"ana\" :tier :gold :since 2019}
+                  :items [[:sku-1 2] [:sku-2 1]]
+                  :note nil
+                  :meta {:app/version \"1.2\" :app/retries 3}}"
+                )
+                .unwrap()
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn explains_every_problem() {
+        let schema = Schema::from_str(ORDER).unwrap();
+        let value = Edn::from_str(
+            "{:id \"af6d8699\"
+                          :customer {:name \"\" :tier :silver}
+                          :items [[:sku-1 0] [:sku-2]]
+                          :meta {:version 1}
+                          :debug true}",
+        )
+        .unwrap();
+        let explanation = schema.validate(&value).unwrap_err();
+
+        assert_eq!(explanation.value, value);
+        assert_eq!(
+            explanation
+                .errors
+                .iter()
+                .map(|e| (
+                    e.path
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    e.kind
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (":id".to_string(), ProblemKind::Invalid),
+                (":placed-at".to_string(), ProblemKind::MissingKey),
+                (":customer :name".to_string(), ProblemKind::Invalid),
+                (":customer :tier".to_string(), ProblemKind::Invalid),
+                (":items 0 1".to_string(), ProblemKind::Invalid),
+                (":items 1".to_string(), ProblemKind::Invalid),
+                (":meta :version".to_string(), ProblemKind::Invalid),
+                (":debug".to_string(), ProblemKind::ExtraKey),
+            ]
+        );
+        assert_eq!(
+            explanation.errors[1].to_string(),
+            "{:path [:placed-at], :in [:placed-at], :schema :inst, :value nil, :type :malli.core/missing-key}"
+        );
+        assert_eq!(
+            explanation.errors[4].to_edn().to_string(),
+            "{:path [:items 0 1], :in [:items 0 1], :schema [:int {:min 1}], :value 0}"
+        );
+        assert_eq!(
+            explanation.errors[6].to_string(),
+            "{:path [:meta 0], :in [:meta :version], :schema :qualified-keyword, :value :version}"
+        );
+        assert_eq!(
+            Edn::from_str(&explanation.to_string()).unwrap()[":errors"][7][":type"],
+            Edn::from_str(":malli.core/extra-key").unwrap()
+        );
+    }
+
+    #[test]
+    fn combines_schemas() {
+        let schema = Schema::from_str("[:and [:sequential :int] [:vector {:max 2} :any]]").unwrap();
+
+        assert!(schema.is_valid(&Edn::from_str("[1 2]").unwrap()));
+        assert!(!schema.is_valid(&Edn::from_str("(1 2)").unwrap()));
+        assert_eq!(
+            schema
+                .validate(&Edn::from_str("[1 :b 3]").unwrap())
+                .unwrap_err()
+                .errors
+                .len(),
+            2
+        );
+        assert!(
+            Schema::from_str("[:= 1]")
+                .unwrap()
+                .is_valid(&Edn::from_str("1").unwrap())
+        );
+        assert!(
+            Schema::from_str("[:double {:max 0.5}]")
+                .unwrap()
+                .is_valid(&Edn::from_str("0.5").unwrap())
+        );
+        assert!(
+            !Schema::from_str(":double")
+                .unwrap()
+                .is_valid(&Edn::from_str("1").unwrap())
+        );
+        assert!(
+            Schema::from_str(":number")
+                .unwrap()
+                .is_valid(&Edn::from_str("3/4").unwrap())
+        );
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn validates_sets() {
+        let schema = Schema::from_str("[:map [:tags [:set {:min 1} :keyword]]]").unwrap();
+
+        assert!(schema.is_valid(&Edn::from_str("{:tags #{:a :b}}").unwrap()));
+        let explanation = schema
+            .validate(&Edn::from_str("{:tags #{:a \"b\"}}").unwrap())
+            .unwrap_err();
+        assert_eq!(
+            explanation.errors[0].path,
+            [Edn::from_str(":tags").unwrap(), Edn::Str("b".to_string())]
+        );
+        assert!(!schema.is_valid(&Edn::from_str("{:tags #{}}").unwrap()));
+    }
+
+    #[test]
+    fn schema_errors() {
+        assert!(matches!(Schema::from_str("[:map"), Err(EdnError::Parse(_))));
+        assert_eq!(
+            Schema::from_str("[:map [:id :int] :name]"),
+            Err(EdnError::Schema(
+                "Invalid schema [:map [:id :int] :name]".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,287 @@
+#[cfg(all(feature = "serde", feature = "sets"))]
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::collections::BTreeMap;
+    use core::str::FromStr;
+    use std::collections::HashMap;
+
+    use edn_rs::serde::{from_edn, from_str, to_edn, to_string};
+    use edn_rs::{Edn, EdnError, List, Map, Vector, map};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct Person {
+        first_name: String,
+        age: u8,
+        nickname: Option<String>,
+        scores: Vec<f64>,
+        shape: Shape,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Line(i32, i32),
+        Rect { width: u32, height: u32 },
+    }
+
+    fn person() -> Person {
+        Person {
+            first_name: "rose".to_string(),
+            age: 66,
+            nickname: None,
+            scores: vec![1.5, 2.0],
+            shape: Shape::Rect {
+                width: 2,
+                height: 3,
+            },
+        }
+    }
+
+    #[test]
+    fn struct_to_edn() {
+        assert_eq!(
+            to_edn(&person()).unwrap(),
+            Edn::Map(Map::new(map! {
+                Edn::Key(":first-name".to_string()) => Edn::Str("rose".to_string()),
+                Edn::Key(":age".to_string()) => Edn::UInt(66),
+                Edn::Key(":nickname".to_string()) => Edn::Nil,
+                Edn::Key(":scores".to_string()) => Edn::Vector(Vector::new(vec![
+                    Edn::Double(1.5.into()),
+                    Edn::Double(2.0.into())
+                ])),
+                Edn::Key(":shape".to_string()) => Edn::Tagged(
+                    "Rect".to_string(),
+                    Box::new(Edn::Map(Map::new(map! {
+                        Edn::Key(":width".to_string()) => Edn::UInt(2),
+                        Edn::Key(":height".to_string()) => Edn::UInt(3)
+                    })))
+                )
+            }))
+        );
+    }
+
+    #[test]
+    fn struct_round_trip() {
+        let edn = to_string(&person()).unwrap();
+
+        assert_eq!(from_str::<Person>(&edn).unwrap(), person());
+    }
+
+    #[test]
+    fn struct_from_str() {
+        let person: Person = from_str(
+            "{:first-name \"rose\" :age 66 :nickname \"ro\" :scores (1 2.5) :shape #Circle 1.5}",
+        )
+        .unwrap();
+
+        assert_eq!(person.nickname, Some("ro".to_string()));
+        assert_eq!(person.scores, vec![1.0, 2.5]);
+        assert_eq!(person.shape, Shape::Circle(1.5));
+    }
+
+    #[test]
+    fn enum_forms() {
+        assert_eq!(to_string(&Shape::Point).unwrap(), ":Point");
+        assert_eq!(to_string(&Shape::Circle(1.5)).unwrap(), "#Circle 1.5");
+        assert_eq!(to_string(&Shape::Line(1, -2)).unwrap(), "#Line [1 -2]");
+
+        assert_eq!(from_str::<Shape>(":Point").unwrap(), Shape::Point);
+        assert_eq!(from_str::<Shape>("Point").unwrap(), Shape::Point);
+        assert_eq!(
+            from_str::<Shape>("#Line [1 -2]").unwrap(),
+            Shape::Line(1, -2)
+        );
+        assert_eq!(
+            from_str::<Shape>("{:Rect {:width 1 :height 2}}").unwrap(),
+            Shape::Rect {
+                width: 1,
+                height: 2
+            }
+        );
+    }
+
+    #[test]
+    fn maps_and_tuples() {
+        let map: HashMap<String, (bool, char)> = from_str("{\"a\" [true \\b]}").unwrap();
+        assert_eq!(map["a"], (true, 'b'));
+
+        let map: BTreeMap<u64, String> = from_str("{1 :one 2 \"two\"}").unwrap();
+        assert_eq!(map[&1], ":one");
+        assert_eq!(map[&2], "two");
+        assert_eq!(to_string(&map).unwrap(), "{1 \":one\", 2 \"two\"}");
+    }
+
+    #[test]
+    fn tags_are_ignored_outside_enums() {
+        let inst: String = from_str("#inst \"2020-07-16T21:53:14.628-00:00\"").unwrap();
+
+        assert_eq!(inst, "2020-07-16T21:53:14.628-00:00");
+    }
+
+    #[test]
+    fn borrows_from_edn() {
+        #[derive(Deserialize)]
+        struct Borrowed<'a> {
+            name: &'a str,
+        }
+
+        let edn = Edn::from_str("{:name \"rose\"}").unwrap();
+        let borrowed: Borrowed<'_> = from_edn(&edn).unwrap();
+
+        assert_eq!(borrowed.name, "rose");
+    }
+
+    #[test]
+    fn edn_round_trips_through_serde() {
+        let edn = Edn::from_str(
+            "{:a (1 -2 3/4 \\c) \"b\" [sym nil true 1.5 42N 1.50M] #{:x 1} #tag {:c \"d\"} [1 2] :vec}",
+        )
+        .unwrap();
+
+        assert_eq!(to_edn(&edn).unwrap(), edn);
+        assert_eq!(from_edn::<Edn>(&edn).unwrap(), edn);
+        assert_eq!(from_str::<Edn>(&edn.to_string()).unwrap(), edn);
+    }
+
+    #[test]
+    fn big_numbers_deserialize_as_exact_strings() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Payment {
+            amount: String,
+        }
+
+        let payment: Payment = from_str("{:amount 1234567890.123456789012M}").unwrap();
+
+        assert_eq!(payment.amount, "1234567890.123456789012");
+    }
+
+    #[test]
+    fn edn_inside_struct() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Wrapper {
+            raw: Edn,
+        }
+
+        let wrapper: Wrapper = from_str("{:raw (:a b)}").unwrap();
+        assert_eq!(
+            wrapper.raw,
+            Edn::List(List::new(vec![
+                Edn::Key(":a".to_string()),
+                Edn::Symbol("b".to_string())
+            ]))
+        );
+        assert_eq!(to_string(&wrapper).unwrap(), "{:raw (:a b)}");
+    }
+
+    #[test]
+    fn edn_with_serde_json() {
+        let edn = Edn::from_str("{:a [1 2.5 \"s\"] :b nil :c #tag :d}").unwrap();
+        let json = serde_json::to_string(&edn).unwrap();
+
+        assert_eq!(json, r#"{":a":[1,2.5,"s"],":b":null,":c":{"tag":":d"}}"#);
+        assert_eq!(
+            serde_json::from_str::<Edn>("{\"a\": [1, -2, 2.5, null, true]}").unwrap(),
+            Edn::Map(Map::new(map! {
+                Edn::Str("a".to_string()) => Edn::Vector(Vector::new(vec![
+                    Edn::UInt(1),
+                    Edn::Int(-2),
+                    Edn::Double(2.5.into()),
+                    Edn::Nil,
+                    Edn::Bool(true)
+                ]))
+            }))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            from_str::<Person>("{:first-name \"rose\"}"),
+            Err(EdnError::Deserialize("missing field `age`".to_string()))
+        );
+        assert_eq!(
+            from_str::<u8>("300"),
+            Err(EdnError::Deserialize(
+                "invalid value: integer `300`, expected u8".to_string()
+            ))
+        );
+        assert!(matches!(from_str::<u8>("[1"), Err(EdnError::Parse(_))));
+        assert_eq!(
+            to_edn(&u128::MAX),
+            Err(EdnError::Serialize(format!(
+                "{} does not fit in an u64",
+                u128::MAX
+            )))
+        );
+    }
+
+    #[test]
+    fn field_names_must_be_keywords() {
+        #[derive(Debug, Serialize)]
+        struct Renamed {
+            #[serde(rename = "user/id")]
+            id: u8,
+        }
+
+        #[derive(Debug, Serialize)]
+        struct Spaced {
+            #[serde(rename = "first name")]
+            first_name: u8,
+        }
+
+        #[derive(Debug, Serialize)]
+        enum Status {
+            #[serde(rename = "on hold")]
+            OnHold,
+        }
+
+        assert_eq!(to_string(&Renamed { id: 1 }).unwrap(), "{:user/id 1}");
+        assert_eq!(
+            to_edn(&Spaced { first_name: 1 }),
+            Err(EdnError::Serialize(
+                "\"first name\" cannot be written as a keyword".to_string()
+            ))
+        );
+        assert_eq!(
+            to_edn(&Status::OnHold),
+            Err(EdnError::Serialize(
+                "\"on hold\" cannot be written as a keyword".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn variant_names_must_be_tags() {
+        #[derive(Debug, Serialize)]
+        enum Shape {
+            #[serde(rename = "_Private")]
+            Private(u8),
+            #[serde(rename = "my variant")]
+            Spaced(u8, u8),
+            #[serde(rename = "my/point")]
+            Point { x: u8 },
+        }
+
+        assert_eq!(
+            to_edn(&Shape::Private(1)),
+            Err(EdnError::Serialize(
+                "\"_Private\" cannot be written as a tag".to_string()
+            ))
+        );
+        assert_eq!(
+            to_edn(&Shape::Spaced(1, 2)),
+            Err(EdnError::Serialize(
+                "\"my variant\" cannot be written as a tag".to_string()
+            ))
+        );
+        assert_eq!(
+            to_string(&Shape::Point { x: 1 }).unwrap(),
+            "#my/point {:x 1}"
+        );
+    }
+}
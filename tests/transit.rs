@@ -0,0 +1,132 @@
+#[cfg(feature = "transit")]
+mod tests {
+    use core::str::FromStr;
+
+    use edn_rs::transit::{self, Mode};
+    use edn_rs::{Edn, EdnError};
+
+    const VALUES: [&str; 12] = [
+        "nil",
+        ":foo",
+        "[1 -2 2.5 1.0 \"a\" \"~tilde\" \"^caret\" \"`tick\" \\c true]",
+        "{:a {:b [1 2 3]} \"abcd\" {\"abcd\" ns/sym}}",
+        "(1 (2) [3])",
+        "{1 :one -1 :minus-one 2.5 :double nil :nil true :true \\c :char}",
+        "{[1 2] :vector {:a 1} :map}",
+        "[9007199254740991 9007199254740992 -9007199254740992 18446744073709551615 123456789012345678901234567890N 1.50M]",
+        "[1/2 -3/4]",
+        "#inst \"2020-07-16T21:53:14.628Z\"",
+        "{#uuid \"af6d8699-f442-4dfd-8b26-37d80543186b\" #inst \"1985-04-12T23:20:50.52Z\"}",
+        "#my/point [1 #other/tag {:a 1}]",
+    ];
+
+    #[test]
+    fn round_trips_in_both_modes() {
+        for source in VALUES {
+            let edn = Edn::from_str(source).unwrap();
+            for mode in [Mode::Compact, Mode::Verbose] {
+                let transit = transit::to_string(&edn, mode);
+
+                assert_eq!(
+                    transit::from_str(&transit),
+                    Ok(edn.clone()),
+                    "{source} {mode:?} {transit}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn writes_like_transit_clj() {
+        for (source, compact, verbose) in [
+            (":foo", r#"["~#'","~:foo"]"#, r#"{"~#'":"~:foo"}"#),
+            ("nil", r#"["~#'",null]"#, r#"{"~#'":null}"#),
+            ("{:a 1}", r#"["^ ","~:a",1]"#, r#"{"~:a":1}"#),
+            ("[:abc :abc]", r#"["~:abc","^0"]"#, r#"["~:abc","~:abc"]"#),
+            (
+                "[\"~a\" \"^b\" 9007199254740992]",
+                r#"["~~a","~^b","~i9007199254740992"]"#,
+                r#"["~~a","~^b","~i9007199254740992"]"#,
+            ),
+            ("{[1] 2}", r#"["~#cmap",[[1],2]]"#, r#"{"~#cmap":[[1],2]}"#),
+            (
+                "[1/2 1/3]",
+                r#"[["~#ratio",["~n1","~n2"]],["^0",["~n1","~n3"]]]"#,
+                r#"[{"~#ratio":["~n1","~n2"]},{"~#ratio":["~n1","~n3"]}]"#,
+            ),
+            (
+                "{1 2.5 nil true}",
+                r#"["^ ","~i1",2.5,"~_",true]"#,
+                r#"{"~i1":2.5,"~_":true}"#,
+            ),
+            (
+                "[#inst \"2020-07-16T21:53:14.628-03:00\"]",
+                r#"["~m1594947194628"]"#,
+                r#"["~t2020-07-16T21:53:14.628-03:00"]"#,
+            ),
+        ] {
+            let edn = Edn::from_str(source).unwrap();
+            assert_eq!(transit::to_string(&edn, Mode::Compact), compact);
+            assert_eq!(transit::to_string(&edn, Mode::Verbose), verbose);
+        }
+    }
+
+    #[test]
+    fn cache_codes_grow_to_two_digits() {
+        let keys = (0..50).map(|i| format!(":key-{i}")).collect::<Vec<_>>();
+        let edn = Edn::from_str(&format!("[{0} {0}]", keys.join(" "))).unwrap();
+        let transit = transit::to_string(&edn, Mode::Compact);
+
+        assert!(transit.contains(r#""~:key-43","~:key-44""#));
+        assert!(transit.contains(r#""^0","^1""#));
+        assert!(transit.ends_with(r#""^[","^10","^11","^12","^13","^14","^15"]"#));
+        assert_eq!(transit::from_str(&transit), Ok(edn));
+    }
+
+    #[cfg(feature = "sets")]
+    #[test]
+    fn round_trips_sets() {
+        let edn = Edn::from_str("{#{1 2} [#{:a}] :b #{}}").unwrap();
+        for mode in [Mode::Compact, Mode::Verbose] {
+            let transit = transit::to_string(&edn, mode);
+            assert_eq!(transit::from_str(&transit), Ok(edn.clone()), "{transit}");
+        }
+        assert_eq!(
+            transit::to_string(&Edn::from_str("#{:a :b}").unwrap(), Mode::Compact),
+            r#"["~#set",["~:a","~:b"]]"#
+        );
+    }
+
+    #[test]
+    fn reads_other_tags() {
+        assert_eq!(
+            transit::from_str(r#"["~rhttp://edn-format.org","~zINF",["~#point",[1,2]]]"#)
+                .unwrap()
+                .to_string(),
+            "[#r \"http://edn-format.org\" ##Inf #point [1 2]]"
+        );
+        assert_eq!(
+            transit::from_str(r#"{"~:a":{"~#list":[]},"b":["~#'","~~x"]}"#),
+            Ok(Edn::from_str("{:a () \"b\" \"~x\"}").unwrap())
+        );
+    }
+
+    #[test]
+    fn transit_errors() {
+        for (transit, message) in [
+            ("[1,", "unexpected end of input at offset 3"),
+            (r#"["^0"]"#, "Unknown cache reference \"^0\""),
+            (r#"["^ ","~:a"]"#, "Map as array with a key without value"),
+            (r#"["~#ratio",[1]]"#, "Expected two integers in `~#ratio`"),
+            (r#"["~#list",1]"#, "Expected an array in `~#list`"),
+            (r#""~ix""#, "Invalid transit value \"~ix\""),
+            (r#""~#tag""#, "Tag \"~#tag\" outside of a tagged value"),
+        ] {
+            assert_eq!(
+                transit::from_str(transit),
+                Err(EdnError::Transit(message.to_string())),
+                "{transit}"
+            );
+        }
+    }
+}